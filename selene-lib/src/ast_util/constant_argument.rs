@@ -0,0 +1,104 @@
+use full_moon::{
+    ast,
+    tokenizer::{Symbol, TokenReference, TokenType},
+};
+
+use super::{string_literal_chars, strip_parentheses};
+
+/// The value of an expression that can be known without running the code, such as `1` or `"text"`.
+/// Only literals are resolved, as well as negative numbers.
+#[derive(Debug, PartialEq)]
+pub enum ConstantArgument {
+    Bool,
+    Function,
+    Nil,
+    Number(f64),
+    String(String),
+    Table,
+}
+
+impl ConstantArgument {
+    pub fn from_expression(expression: &ast::Expression) -> Option<Self> {
+        match strip_parentheses(expression) {
+            ast::Expression::Function(_) => Some(ConstantArgument::Function),
+            ast::Expression::TableConstructor(_) => Some(ConstantArgument::Table),
+
+            ast::Expression::Number(token) => {
+                // Only Luau tokenizes numbers with underscores, such as `1_000`
+                parse_number(&token.token().to_string(), true).map(ConstantArgument::Number)
+            }
+
+            ast::Expression::String(token) => ConstantArgument::from_string_literal(token),
+
+            ast::Expression::Symbol(token) => match token.token_type() {
+                TokenType::Symbol {
+                    symbol: Symbol::True | Symbol::False,
+                } => Some(ConstantArgument::Bool),
+                TokenType::Symbol {
+                    symbol: Symbol::Nil,
+                } => Some(ConstantArgument::Nil),
+                _ => None,
+            },
+
+            ast::Expression::UnaryOperator {
+                unop: ast::UnOp::Minus(_),
+                expression,
+            } => match ConstantArgument::from_expression(expression)? {
+                ConstantArgument::Number(number) => Some(ConstantArgument::Number(-number)),
+                _ => None,
+            },
+
+            _ => None,
+        }
+    }
+
+    pub fn from_string_literal(token: &TokenReference) -> Option<Self> {
+        string_literal_chars(token)
+            .map(|chars| ConstantArgument::String(chars.iter().map(|char| char.value).collect()))
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            ConstantArgument::Bool => "boolean",
+            ConstantArgument::Function => "function",
+            ConstantArgument::Nil => "nil",
+            ConstantArgument::Number(_) => "number",
+            ConstantArgument::String(_) => "string",
+            ConstantArgument::Table => "table",
+        }
+    }
+
+    // Strings are coerced to numbers when they can be.
+    // Underscores are never allowed in them, even in Luau, so `"1_000"` is not a number.
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            ConstantArgument::Number(number) => Some(*number),
+            ConstantArgument::String(text) => parse_number(text.trim(), false),
+            _ => None,
+        }
+    }
+}
+
+fn parse_number(text: &str, allow_underscores: bool) -> Option<f64> {
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(text) => (true, text),
+        None => (false, text),
+    };
+
+    // Rust accepts these, but Lua does not
+    let lowercase = text.to_ascii_lowercase();
+    if lowercase.contains("inf") || lowercase.contains("nan") {
+        return None;
+    }
+
+    if text.contains('_') && !allow_underscores {
+        return None;
+    }
+
+    let number = match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(&hex.replace('_', ""), 16).ok()? as f64,
+        None => text.replace('_', "").parse::<f64>().ok()?,
+    };
+
+    Some(if negative { -number } else { number })
+}
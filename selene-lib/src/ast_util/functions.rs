@@ -0,0 +1,139 @@
+use full_moon::{ast, node::Node, visitors::Visitor};
+
+use super::range;
+
+/// A function found in the AST, shared between the lints that measure functions so that they
+/// all report them the same way.
+pub struct FunctionInfo<'a> {
+    /// The name the function was declared with, such as `Module.method` or `Class:method`.
+    /// None for anonymous functions.
+    pub name: Option<String>,
+    /// From the start of the `function` keyword to the end of the parameters, such as
+    /// `function Class:method(a, b)`. This is what lints should use for their primary label.
+    pub signature: (usize, usize),
+    pub body: &'a ast::FunctionBody,
+}
+
+impl FunctionInfo<'_> {
+    /// Describes the function for use in diagnostic messages, such as "function `Class:method`".
+    pub fn description(&self) -> String {
+        match &self.name {
+            Some(name) => format!("function `{name}`"),
+            None => "anonymous function".to_owned(),
+        }
+    }
+}
+
+/// Calls `callback` for every function in the AST, including nested and anonymous ones.
+pub fn visit_functions(ast: &ast::Ast, mut callback: impl FnMut(FunctionInfo)) {
+    let mut visitor = FunctionVisitor {
+        callback: &mut callback,
+    };
+
+    visitor.visit_ast(ast);
+}
+
+struct FunctionVisitor<'a> {
+    callback: &'a mut dyn FnMut(FunctionInfo),
+}
+
+impl Visitor for FunctionVisitor<'_> {
+    fn visit_local_function(&mut self, local_function: &ast::LocalFunction) {
+        (self.callback)(FunctionInfo {
+            name: Some(local_function.name().token().to_string()),
+            signature: (
+                range(local_function.function_token()).0,
+                range(local_function.body().parameters_parentheses()).1,
+            ),
+            body: local_function.body(),
+        });
+    }
+
+    fn visit_function_declaration(&mut self, function_declaration: &ast::FunctionDeclaration) {
+        let name = function_declaration.name();
+
+        let mut full_name = name
+            .names()
+            .iter()
+            .map(|name| name.token().to_string())
+            .collect::<Vec<_>>()
+            .join(".");
+
+        if let Some(method_name) = name.method_name() {
+            full_name.push(':');
+            full_name.push_str(&method_name.token().to_string());
+        }
+
+        (self.callback)(FunctionInfo {
+            name: Some(full_name),
+            signature: (
+                range(function_declaration.function_token()).0,
+                range(function_declaration.body().parameters_parentheses()).1,
+            ),
+            body: function_declaration.body(),
+        });
+    }
+
+    fn visit_expression(&mut self, expression: &ast::Expression) {
+        if let ast::Expression::Function(function_box) = expression {
+            let function_body = &function_box.1;
+
+            (self.callback)(FunctionInfo {
+                name: None,
+                signature: (
+                    expression.start_position().unwrap().bytes(),
+                    range(function_body.parameters_parentheses()).1,
+                ),
+                body: function_body,
+            });
+        }
+    }
+}
+
+/// Returns the blocks directly nested inside a statement, such as the body of an `if` or a loop.
+/// Function bodies are not included, as they are functions of their own.
+pub fn nested_blocks(stmt: &ast::Stmt) -> Vec<&ast::Block> {
+    #[cfg_attr(
+        feature = "force_exhaustive_checks",
+        deny(non_exhaustive_omitted_patterns)
+    )]
+    match stmt {
+        ast::Stmt::Do(do_) => vec![do_.block()],
+        ast::Stmt::GenericFor(generic_for) => vec![generic_for.block()],
+        ast::Stmt::NumericFor(numeric_for) => vec![numeric_for.block()],
+        ast::Stmt::Repeat(repeat) => vec![repeat.block()],
+        ast::Stmt::While(while_loop) => vec![while_loop.block()],
+
+        ast::Stmt::If(if_block) => {
+            let mut blocks = vec![if_block.block()];
+
+            if let Some(else_ifs) = if_block.else_if() {
+                blocks.extend(else_ifs.iter().map(|else_if| else_if.block()));
+            }
+
+            if let Some(else_block) = if_block.else_block() {
+                blocks.push(else_block);
+            }
+
+            blocks
+        }
+
+        ast::Stmt::Assignment(_)
+        | ast::Stmt::FunctionCall(_)
+        | ast::Stmt::FunctionDeclaration(_)
+        | ast::Stmt::LocalAssignment(_)
+        | ast::Stmt::LocalFunction(_) => Vec::new(),
+
+        #[cfg(feature = "roblox")]
+        ast::Stmt::CompoundAssignment(_)
+        | ast::Stmt::ExportedTypeDeclaration(_)
+        | ast::Stmt::ExportedTypeFunction(_)
+        | ast::Stmt::TypeDeclaration(_)
+        | ast::Stmt::TypeFunction(_) => Vec::new(),
+
+        #[cfg(feature = "lua52")]
+        ast::Stmt::Goto(_) | ast::Stmt::Label(_) => Vec::new(),
+
+        _ => Vec::new(),
+    }
+}
@@ -6,18 +6,27 @@ use full_moon::{
     tokenizer::{self, Position, TokenReference},
 };
 
+mod constant_argument;
 mod extract_static_token;
+pub mod functions;
 mod loop_tracker;
 pub mod name_paths;
 mod purge_trivia;
+#[cfg(feature = "roblox")]
+pub mod roblox_instances;
 pub mod scopes;
 mod side_effects;
+mod string_library_call;
+mod string_literal;
 mod strip_parentheses;
 pub mod visit_nodes;
 
+pub use constant_argument::ConstantArgument;
 pub use extract_static_token::extract_static_token;
 pub use purge_trivia::purge_trivia;
 pub use side_effects::HasSideEffects;
+pub use string_library_call::{string_library_call, StringLibraryCall};
+pub use string_literal::{string_literal_chars, LiteralChar};
 pub use strip_parentheses::strip_parentheses;
 
 pub fn is_type_function(name: &str, roblox: bool) -> bool {
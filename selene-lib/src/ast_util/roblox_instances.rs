@@ -0,0 +1,139 @@
+use std::collections::{BTreeMap, HashMap};
+
+use full_moon::{
+    ast,
+    tokenizer::{TokenReference, TokenType},
+};
+
+use crate::standard_library::{FieldKind, RobloxClass, StandardLibrary};
+
+use super::{
+    range,
+    scopes::{ReferenceWrite, ScopeManager},
+    strip_parentheses,
+};
+
+/// Tracks what Roblox class instances are, such as `part` in `local part = Instance.new("Part")`.
+/// Locals must be recorded with `record_local_assignment` as they are visited.
+pub struct InstanceClasses<'a> {
+    // Classes of locals defined by `local x = Instance.new("Part")`, keyed by the range of their identifier
+    local_classes: HashMap<(usize, usize), String>,
+
+    roblox_classes: &'a BTreeMap<String, RobloxClass>,
+    scope_manager: &'a ScopeManager,
+    standard_library: &'a StandardLibrary,
+}
+
+impl<'a> InstanceClasses<'a> {
+    pub fn new(scope_manager: &'a ScopeManager, standard_library: &'a StandardLibrary) -> Self {
+        Self {
+            local_classes: HashMap::new(),
+
+            roblox_classes: &standard_library.roblox_classes,
+            scope_manager,
+            standard_library,
+        }
+    }
+
+    pub fn record_local_assignment(&mut self, node: &ast::LocalAssignment) {
+        for (name, expression) in node.names().iter().zip(node.expressions().iter()) {
+            if let Some(class_name) = self.created_class(expression) {
+                self.local_classes.insert(range(name), class_name);
+            }
+        }
+    }
+
+    /// Returns the class name in a string literal, if it is a real class.
+    pub fn string_class_name(&self, token: &TokenReference) -> Option<String> {
+        let TokenType::StringLiteral { literal, .. } = token.token_type() else {
+            return None;
+        };
+
+        let name = literal.to_string();
+        self.roblox_classes.contains_key(&name).then_some(name)
+    }
+
+    /// Returns the class name passed to a call such as `Instance.new("Part")` or `:GetService("Workspace")`.
+    pub fn single_string_argument(&self, call: &ast::Call) -> Option<String> {
+        let arguments = match call {
+            ast::Call::AnonymousCall(arguments) => arguments,
+            ast::Call::MethodCall(method_call) => method_call.args(),
+            _ => return None,
+        };
+
+        match arguments {
+            ast::FunctionArgs::Parentheses { arguments, .. } => {
+                match arguments.iter().next().map(strip_parentheses) {
+                    Some(ast::Expression::String(token)) => self.string_class_name(token),
+                    _ => None,
+                }
+            }
+
+            ast::FunctionArgs::String(token) => self.string_class_name(token),
+
+            _ => None,
+        }
+    }
+
+    /// Returns the class of an instance created by `Instance.new("Part")` or `game:GetService("Workspace")`.
+    pub fn created_class(&self, expression: &ast::Expression) -> Option<String> {
+        let ast::Expression::FunctionCall(call) = strip_parentheses(expression) else {
+            return None;
+        };
+
+        let suffixes = call.suffixes().collect::<Vec<_>>();
+
+        match suffixes.as_slice() {
+            [ast::Suffix::Index(ast::Index::Dot { name, .. }), ast::Suffix::Call(call_suffix)]
+                if name.token().to_string() == "new"
+                    && matches!(call.prefix(), ast::Prefix::Name(prefix) if prefix.token().to_string() == "Instance") =>
+            {
+                self.single_string_argument(call_suffix)
+            }
+
+            [.., ast::Suffix::Call(call_suffix @ ast::Call::MethodCall(method_call))]
+                if method_call.name().token().to_string() == "GetService" =>
+            {
+                self.single_string_argument(call_suffix)
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Returns the class of a name, if it is either a local that was only ever assigned an instance of a known class,
+    /// or a global such as `workspace`.
+    pub fn class_of_name(&self, name: &TokenReference) -> Option<String> {
+        let reference = self
+            .scope_manager
+            .reference_at_byte(name.token().start_position().bytes())?;
+
+        let Some(variable_id) = reference.resolved else {
+            let field = self
+                .standard_library
+                .find_global(&[name.token().to_string()])?;
+
+            return match &field.field_kind {
+                FieldKind::Struct(struct_name) if self.roblox_classes.contains_key(struct_name) => {
+                    Some(struct_name.to_owned())
+                }
+
+                _ => None,
+            };
+        };
+
+        let variable = self.scope_manager.variables.get(variable_id)?;
+        let class_name = self.local_classes.get(variable.identifiers.first()?)?;
+
+        let assignments = variable
+            .references
+            .iter()
+            .filter(|reference_id| {
+                let reference = &self.scope_manager.references[**reference_id];
+                reference.write == Some(ReferenceWrite::Assign) && reference.indexing.is_none()
+            })
+            .count();
+
+        (assignments == 1).then(|| class_name.to_owned())
+    }
+}
@@ -141,12 +141,29 @@ pub struct Variable {
     pub references: Vec<Id<Reference>>,
     pub shadowed: Option<Id<Variable>>,
     pub is_self: bool,
+    pub kind: VariableKind,
     pub value: Option<AssignedValue>,
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum VariableKind {
+    /// `local x`, as well as for loop variables
+    #[default]
+    Local,
+    /// `local function x()`
+    LocalFunction,
+    /// `function(x)`
+    Parameter,
+    /// `x = 1` without a local `x` in scope
+    Global,
+    /// `function x()` without a local `x` in scope
+    GlobalFunction,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum AssignedValue {
     StaticTable { has_fields: bool },
+    String,
 }
 
 #[derive(Debug)]
@@ -264,6 +281,10 @@ fn get_assigned_value(expression: &ast::Expression) -> Option<AssignedValue> {
         });
     }
 
+    if let ast::Expression::String(_) = expression {
+        return Some(AssignedValue::String);
+    }
+
     None
 }
 
@@ -567,7 +588,7 @@ impl ScopeVisitor {
         self.define_name_full_with_variable(name, range, definition_range, Variable::default())
     }
 
-    fn try_hoist(&mut self) {
+    fn try_hoist(&mut self, kind: VariableKind) {
         let latest_reference_id = *self.current_scope().references.last().unwrap();
         let (name, identifier, write_expr) = {
             let reference = self
@@ -584,7 +605,15 @@ impl ScopeVisitor {
         };
 
         if self.find_variable(&name).is_none() {
-            let id = self.define_name_full(&name, identifier, write_expr);
+            let id = self.define_name_full_with_variable(
+                &name,
+                identifier,
+                write_expr,
+                Variable {
+                    kind,
+                    ..Variable::default()
+                },
+            );
 
             for (_, reference) in &mut self.scope_manager.references {
                 if reference.read && reference.name == name && reference.resolved.is_none() {
@@ -797,7 +826,7 @@ impl Visitor for ScopeVisitor {
 
             self.write_name(name, expression.map(range));
             if let ast::Var::Name(_) = var {
-                self.try_hoist();
+                self.try_hoist(VariableKind::Global);
             }
         }
     }
@@ -912,7 +941,15 @@ impl Visitor for ScopeVisitor {
 
         for parameter in body.parameters() {
             if let ast::Parameter::Ellipsis(token) | ast::Parameter::Name(token) = parameter {
-                self.define_name(token, range(token));
+                self.define_name_full_with_variable(
+                    &token.token().to_string(),
+                    range(token),
+                    range(token),
+                    Variable {
+                        kind: VariableKind::Parameter,
+                        ..Variable::default()
+                    },
+                );
             }
         }
     }
@@ -944,7 +981,7 @@ impl Visitor for ScopeVisitor {
         self.read_name(base);
 
         if !is_longer_expression {
-            self.try_hoist();
+            self.try_hoist(VariableKind::GlobalFunction);
         }
 
         if let Some(name) = name.method_name() {
@@ -1013,7 +1050,15 @@ impl Visitor for ScopeVisitor {
     }
 
     fn visit_local_function(&mut self, local_function: &ast::LocalFunction) {
-        self.define_name(local_function.name(), range(local_function.name()));
+        self.define_name_full_with_variable(
+            &local_function.name().token().to_string(),
+            range(local_function.name()),
+            range(local_function.name()),
+            Variable {
+                kind: VariableKind::LocalFunction,
+                ..Variable::default()
+            },
+        );
         self.open_scope(local_function.body());
     }
 
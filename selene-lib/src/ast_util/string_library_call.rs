@@ -0,0 +1,133 @@
+use full_moon::{
+    ast::{self, FunctionCall},
+    tokenizer::TokenReference,
+};
+
+use super::{
+    scopes::{AssignedValue, ScopeManager},
+    strip_parentheses,
+};
+
+/// A call to a function in the `string` library, either through `string.name(...)`
+/// or as a method, such as `text:name(...)` or `("literal"):name(...)`.
+pub struct StringLibraryCall<'a> {
+    pub name: String,
+    /// The arguments passed to the function, not including the receiver of a method call.
+    pub arguments: Vec<&'a ast::Expression>,
+    pub method: bool,
+    /// For method calls directly on a string literal, the literal being called on.
+    pub literal_receiver: Option<&'a TokenReference>,
+    /// Whether the method call is known to be on a string, rather than any object with a method
+    /// of the same name. True for string literals, and for locals that are only ever assigned one.
+    pub string_receiver: bool,
+}
+
+/// Returns the string library call the function call represents, if any.
+/// Only calls with parenthesized arguments are matched, and `string.name` is ignored if
+/// `string` has been shadowed by a local variable.
+/// Method calls are matched on any receiver, so callers should check `string_receiver`.
+pub fn string_library_call<'a>(
+    call: &'a FunctionCall,
+    scope_manager: &ScopeManager,
+) -> Option<StringLibraryCall<'a>> {
+    let mut suffixes = call.suffixes().peekable();
+
+    if let ast::Prefix::Name(prefix_name) = call.prefix() {
+        if let Some(ast::Suffix::Index(ast::Index::Dot { name, .. })) = suffixes.peek() {
+            if prefix_name.token().to_string() != "string" {
+                return None;
+            }
+
+            if let Some(reference) =
+                scope_manager.reference_at_byte(prefix_name.token().start_position().bytes())
+            {
+                if reference.resolved.is_some() {
+                    return None;
+                }
+            }
+
+            suffixes.next();
+
+            return match suffixes.next() {
+                Some(ast::Suffix::Call(ast::Call::AnonymousCall(
+                    ast::FunctionArgs::Parentheses { arguments, .. },
+                ))) => Some(StringLibraryCall {
+                    name: name.token().to_string(),
+                    arguments: arguments.iter().collect(),
+                    method: false,
+                    literal_receiver: None,
+                    string_receiver: false,
+                }),
+
+                _ => None,
+            };
+        }
+    }
+
+    let mut indexed = false;
+
+    for suffix in suffixes {
+        match suffix {
+            ast::Suffix::Index(_) => indexed = true,
+
+            ast::Suffix::Call(ast::Call::MethodCall(method_call)) => {
+                let arguments = match method_call.args() {
+                    ast::FunctionArgs::Parentheses { arguments, .. } => arguments.iter().collect(),
+                    _ => return None,
+                };
+
+                let literal_receiver = match call.prefix() {
+                    ast::Prefix::Expression(expression) if !indexed => {
+                        match strip_parentheses(expression) {
+                            ast::Expression::String(token) => Some(token),
+                            _ => None,
+                        }
+                    }
+
+                    _ => None,
+                };
+
+                let string_receiver = literal_receiver.is_some()
+                    || match call.prefix() {
+                        ast::Prefix::Name(name) if !indexed => {
+                            local_holds_string(name, scope_manager)
+                        }
+
+                        _ => false,
+                    };
+
+                return Some(StringLibraryCall {
+                    name: method_call.name().token().to_string(),
+                    arguments,
+                    method: true,
+                    literal_receiver,
+                    string_receiver,
+                });
+            }
+
+            _ => return None,
+        }
+    }
+
+    None
+}
+
+// `local text = "..."`, where `text` is never assigned anything else
+fn local_holds_string(name: &TokenReference, scope_manager: &ScopeManager) -> bool {
+    let Some(variable) = scope_manager
+        .reference_at_byte(name.token().start_position().bytes())
+        .and_then(|reference| reference.resolved)
+        .and_then(|variable| scope_manager.variables.get(variable))
+    else {
+        return false;
+    };
+
+    variable.value == Some(AssignedValue::String)
+        && variable
+            .references
+            .iter()
+            .filter_map(|reference| scope_manager.references.get(*reference))
+            .filter(|reference| reference.write.is_some())
+            .count()
+            <= 1
+}
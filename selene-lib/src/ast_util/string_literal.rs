@@ -0,0 +1,214 @@
+use full_moon::tokenizer::{StringLiteralQuoteType, TokenReference, TokenType};
+
+/// A single character of a string literal's value, alongside the byte range in the source
+/// that produced it. Escape sequences such as `\n` or `\65` produce one character spanning
+/// the entire sequence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LiteralChar {
+    pub value: char,
+    pub range: (usize, usize),
+}
+
+/// Given a string literal token, returns the characters of its value with their source ranges.
+/// Decimal and hex escapes that produce bytes above 127 are treated as their Latin-1 character,
+/// which is good enough for anything that only cares about ASCII syntax (like format strings).
+/// Returns None if the token is not a string literal.
+pub fn string_literal_chars(token: &TokenReference) -> Option<Vec<LiteralChar>> {
+    let (literal, multi_line_depth, quote_type) = match token.token_type() {
+        TokenType::StringLiteral {
+            literal,
+            multi_line_depth,
+            quote_type,
+        } => (literal.as_str(), *multi_line_depth, *quote_type),
+        _ => return None,
+    };
+
+    let token_start = token.token().start_position().bytes();
+
+    if quote_type == StringLiteralQuoteType::Brackets {
+        let value_start = token_start + multi_line_depth + 2;
+
+        // Lua skips the first newline of long strings
+        let skip = if literal.starts_with("\r\n") {
+            2
+        } else if literal.starts_with('\n') {
+            1
+        } else {
+            0
+        };
+
+        return Some(
+            literal[skip..]
+                .char_indices()
+                .map(|(index, value)| LiteralChar {
+                    value,
+                    range: (
+                        value_start + skip + index,
+                        value_start + skip + index + value.len_utf8(),
+                    ),
+                })
+                .collect(),
+        );
+    }
+
+    let value_start = token_start + 1;
+    let mut chars = Vec::with_capacity(literal.len());
+    let mut iter = literal.char_indices().peekable();
+
+    while let Some((index, value)) = iter.next() {
+        let start = value_start + index;
+
+        if value != '\\' {
+            chars.push(LiteralChar {
+                value,
+                range: (start, start + value.len_utf8()),
+            });
+
+            continue;
+        }
+
+        let (escape_index, escape) = match iter.next() {
+            Some(next) => next,
+            None => break,
+        };
+
+        let mut end = value_start + escape_index + escape.len_utf8();
+
+        let value = match escape {
+            'a' => '\x07',
+            'b' => '\x08',
+            'f' => '\x0c',
+            'n' | '\n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'v' => '\x0b',
+
+            'z' => {
+                while let Some((_, next)) = iter.peek() {
+                    if !next.is_whitespace() {
+                        break;
+                    }
+
+                    iter.next();
+                }
+
+                continue;
+            }
+
+            'x' => {
+                let mut code = 0;
+
+                for _ in 0..2 {
+                    match iter.peek() {
+                        Some(&(digit_index, digit)) if digit.is_ascii_hexdigit() => {
+                            code = code * 16 + digit.to_digit(16).unwrap();
+                            end = value_start + digit_index + 1;
+                            iter.next();
+                        }
+
+                        _ => break,
+                    }
+                }
+
+                char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER)
+            }
+
+            'u' => {
+                let mut code = 0u32;
+
+                if let Some((_, '{')) = iter.peek() {
+                    iter.next();
+
+                    for (digit_index, digit) in iter.by_ref() {
+                        end = value_start + digit_index + 1;
+
+                        match digit.to_digit(16) {
+                            Some(digit) => code = code.saturating_mul(16).saturating_add(digit),
+                            None => break,
+                        }
+                    }
+                }
+
+                char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER)
+            }
+
+            '0'..='9' => {
+                let mut code = escape.to_digit(10).unwrap();
+
+                for _ in 0..2 {
+                    match iter.peek() {
+                        Some(&(digit_index, digit)) if digit.is_ascii_digit() => {
+                            code = code * 10 + digit.to_digit(10).unwrap();
+                            end = value_start + digit_index + 1;
+                            iter.next();
+                        }
+
+                        _ => break,
+                    }
+                }
+
+                char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER)
+            }
+
+            // \\, \", \', and invalid escapes (which bad_string_escape reports)
+            other => other,
+        };
+
+        chars.push(LiteralChar {
+            value,
+            range: (start, end),
+        });
+    }
+
+    Some(chars)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use full_moon::{ast, visitors::Visitor};
+
+    fn chars_of(code: &str) -> Vec<LiteralChar> {
+        struct StringVisitor(Option<Vec<LiteralChar>>);
+
+        impl Visitor for StringVisitor {
+            fn visit_expression(&mut self, node: &ast::Expression) {
+                if let ast::Expression::String(token) = node {
+                    self.0 = string_literal_chars(token);
+                }
+            }
+        }
+
+        let mut visitor = StringVisitor(None);
+        visitor.visit_ast(&full_moon::parse(code).unwrap());
+        visitor.0.expect("no string literal found")
+    }
+
+    fn value_of(chars: &[LiteralChar]) -> String {
+        chars.iter().map(|char| char.value).collect()
+    }
+
+    #[test]
+    fn test_plain_string() {
+        let chars = chars_of(r#"local _ = "a%d""#);
+        assert_eq!(value_of(&chars), "a%d");
+        assert_eq!(chars[0].range, (11, 12));
+        assert_eq!(chars[2].range, (13, 14));
+    }
+
+    #[test]
+    fn test_escapes() {
+        let chars = chars_of(r#"local _ = "\37d\n\x41\z   b""#);
+        assert_eq!(value_of(&chars), "%d\nAb");
+        assert_eq!(chars[0].range, (11, 14));
+        assert_eq!(chars[2].range, (15, 17));
+        assert_eq!(chars[3].range, (17, 21));
+    }
+
+    #[test]
+    fn test_long_string() {
+        let chars = chars_of("local _ = [==[\n%s]==]");
+        assert_eq!(value_of(&chars), "%s");
+        assert_eq!(chars[0].range, (15, 16));
+    }
+}
@@ -3,7 +3,12 @@
     feature = "force_exhaustive_checks",
     feature(non_exhaustive_omitted_patterns_lint)
 )]
-use std::{collections::HashMap, error::Error, fmt};
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt,
+    path::{Path, PathBuf},
+};
 
 use full_moon::ast::Ast;
 use serde::{
@@ -14,7 +19,10 @@ use serde::{
 mod ast_util;
 mod lint_filtering;
 pub mod lints;
+pub mod metrics;
 mod possible_std;
+#[cfg(feature = "roblox")]
+mod rojo;
 pub mod standard_library;
 mod text;
 
@@ -66,11 +74,17 @@ pub struct CheckerConfig<V> {
     #[serde(alias = "rules")]
     pub lints: HashMap<String, LintVariation>,
     pub std: Option<String>,
+    pub std_paths: Vec<String>,
     pub exclude: Vec<String>,
 
     // Not locked behind Roblox feature so that selene.toml for Roblox will
     // run even without it.
     pub roblox_std_source: RobloxStdSource,
+
+    /// The directory of selene.toml, which paths in lint configs are relative to.
+    /// None is the current directory. This is never read from selene.toml itself.
+    #[serde(skip)]
+    pub config_directory: Option<PathBuf>,
 }
 
 impl<V> CheckerConfig<V> {
@@ -85,9 +99,11 @@ impl<V> Default for CheckerConfig<V> {
             config: HashMap::new(),
             lints: HashMap::new(),
             std: None,
+            std_paths: Vec::new(),
             exclude: Vec::new(),
 
             roblox_std_source: RobloxStdSource::default(),
+            config_directory: None,
         }
     }
 }
@@ -151,6 +167,25 @@ macro_rules! use_lints {
             ];
         }
 
+        /// Every lint with its default severity and config, sorted by name.
+        pub fn lint_defaults() -> Vec<LintDefaults> {
+            let mut lint_defaults = vec![
+                $(
+                    LintDefaults::new::<$lint_path>(stringify!($lint_name)),
+                )+
+
+                $(
+                    $(
+                        #[$meta]
+                        LintDefaults::new::<$meta_lint_path>(stringify!($meta_lint_name)),
+                    )+
+                )+
+            ];
+
+            lint_defaults.sort_by_key(|lint_defaults| lint_defaults.name);
+            lint_defaults
+        }
+
         pub struct Checker<V: 'static + DeserializeOwned> {
             config: CheckerConfig<V>,
             context: Context,
@@ -223,6 +258,7 @@ macro_rules! use_lints {
                         user_set_standard_library: config.std.as_ref().map(|std_text| {
                             std_text.split('+').map(ToOwned::to_owned).collect()
                         }),
+                        config_directory: config.config_directory.clone(),
                     },
 
                     config,
@@ -230,9 +266,16 @@ macro_rules! use_lints {
             }
 
             pub fn test_on(&self, ast: &Ast) -> Vec<CheckerDiagnostic> {
-                let mut diagnostics = Vec::new();
+                self.test_on_ast_context(ast, AstContext::from_ast(ast))
+            }
 
-                let ast_context = AstContext::from_ast(ast);
+            /// Like `test_on`, but for lints that depend on the path of the file, such as `roblox_member_security`.
+            pub fn test_on_file(&self, ast: &Ast, file_path: &Path) -> Vec<CheckerDiagnostic> {
+                self.test_on_ast_context(ast, AstContext::from_ast(ast).with_file_path(file_path))
+            }
+
+            fn test_on_ast_context(&self, ast: &Ast, ast_context: AstContext) -> Vec<CheckerDiagnostic> {
+                let mut diagnostics = Vec::new();
 
                 macro_rules! check_lint {
                     ($name:ident) => {
@@ -245,7 +288,7 @@ macro_rules! use_lints {
 
                         diagnostics.extend(&mut lint_pass.into_iter().map(|diagnostic| {
                             CheckerDiagnostic {
-                                diagnostic,
+                                diagnostic: with_default_luacheck_code(lint, diagnostic),
                                 severity: self.get_lint_severity(lint, stringify!($name)),
                             }
                         }));
@@ -290,6 +333,45 @@ pub struct CheckerDiagnostic {
     pub severity: Severity,
 }
 
+// Diagnostics that don't pick a luacheck code themselves use the first one of their lint
+fn with_default_luacheck_code<R: Lint>(_lint: &R, diagnostic: Diagnostic) -> Diagnostic {
+    match (diagnostic.luacheck_code, R::LUACHECK_CODES.first()) {
+        (None, Some(luacheck_code)) => diagnostic.with_luacheck_code(luacheck_code),
+        _ => diagnostic,
+    }
+}
+
+/// The severity and config a lint has when selene.toml doesn't change them.
+#[derive(Debug)]
+pub struct LintDefaults {
+    pub name: &'static str,
+    pub severity: Severity,
+    /// `None` for lints that have nothing to configure
+    pub config: Option<toml::value::Table>,
+    /// The luacheck warning codes closest to the lint, see `Lint::LUACHECK_CODES`
+    pub luacheck_codes: &'static [&'static str],
+}
+
+impl LintDefaults {
+    fn new<L: Lint>(name: &'static str) -> Self
+    where
+        L::Config: Default + serde::Serialize,
+    {
+        // Lints without a config use `()`, which can't be serialized to a table
+        let config = match toml::Value::try_from(L::Config::default()) {
+            Ok(toml::Value::Table(table)) if !table.is_empty() => Some(table),
+            _ => None,
+        };
+
+        LintDefaults {
+            name,
+            severity: L::SEVERITY,
+            config,
+            luacheck_codes: L::LUACHECK_CODES,
+        }
+    }
+}
+
 pub fn lint_exists(name: &str) -> bool {
     ALL_LINTS.contains(&name)
 }
@@ -297,8 +379,11 @@ pub fn lint_exists(name: &str) -> bool {
 use_lints! {
     almost_swapped: lints::almost_swapped::AlmostSwappedLint,
     bad_string_escape: lints::bad_string_escape::BadStringEscapeLint,
+    bad_string_format: lints::bad_string_format::BadStringFormatLint,
+    bad_string_pattern: lints::bad_string_pattern::BadStringPatternLint,
     compare_nan: lints::compare_nan::CompareNanLint,
     constant_table_comparison: lints::constant_table_comparison::ConstantTableComparisonLint,
+    deep_nesting: lints::deep_nesting::DeepNestingLint,
     deprecated: lints::deprecated::DeprecatedLint,
     divide_by_zero: lints::divide_by_zero::DivideByZeroLint,
     duplicate_keys: lints::duplicate_keys::DuplicateKeysLint,
@@ -310,14 +395,18 @@ use_lints! {
     ifs_same_cond: lints::ifs_same_cond::IfsSameCondLint,
     incorrect_standard_library_use: lints::standard_library::StandardLibraryLint,
     invalid_lint_filter: lints::invalid_lint_filter::InvalidLintFilterLint,
+    long_function: lints::long_function::LongFunctionLint,
     manual_table_clone: lints::manual_table_clone::ManualTableCloneLint,
     mismatched_arg_count: lints::mismatched_arg_count::MismatchedArgCountLint,
     mixed_table: lints::mixed_table::MixedTableLint,
     multiple_statements: lints::multiple_statements::MultipleStatementsLint,
     must_use: lints::must_use::MustUseLint,
+    naming_convention: lints::naming_convention::NamingConventionLint,
     parenthese_conditions: lints::parenthese_conditions::ParentheseConditionsLint,
     shadowing: lints::shadowing::ShadowingLint,
     suspicious_reverse_loop: lints::suspicious_reverse_loop::SuspiciousReverseLoopLint,
+    too_many_parameters: lints::too_many_parameters::TooManyParametersLint,
+    too_many_return_values: lints::too_many_return_values::TooManyReturnValuesLint,
     type_check_inside_call: lints::type_check_inside_call::TypeCheckInsideCallLint,
     unbalanced_assignments: lints::unbalanced_assignments::UnbalancedAssignmentsLint,
     undefined_variable: lints::undefined_variable::UndefinedVariableLint,
@@ -327,7 +416,11 @@ use_lints! {
     #[cfg(feature = "roblox")]
     {
         roblox_incorrect_color3_new_bounds: lints::roblox_incorrect_color3_new_bounds::Color3BoundsLint,
+        roblox_incorrect_property_assignment: lints::roblox_incorrect_property_assignment::IncorrectPropertyAssignmentLint,
         roblox_incorrect_roact_usage: lints::roblox_incorrect_roact_usage::IncorrectRoactUsageLint,
+        roblox_invalid_instance_path: lints::roblox_invalid_instance_path::InvalidInstancePathLint,
+        roblox_member_security: lints::roblox_member_security::MemberSecurityLint,
+        roblox_roact_hooks: lints::roblox_roact_hooks::RoactHooksLint,
         roblox_suspicious_udim2_new: lints::roblox_suspicious_udim2_new::SuspiciousUDim2NewLint,
     },
 }
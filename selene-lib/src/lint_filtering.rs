@@ -4,7 +4,7 @@ use crate::{
         visit_nodes::{NodeVisitor, VisitorType},
     },
     lint_exists,
-    lints::{Diagnostic, Label, Severity},
+    lints::{invalid_lint_filter::InvalidLintFilterLint, Diagnostic, Label, Lint, Severity},
     CheckerDiagnostic, LintVariation,
 };
 use full_moon::{ast::Ast, node::Node, tokenizer::TokenType};
@@ -37,7 +37,7 @@ struct Filter {
 #[derive(Default)]
 struct FilterVisitor {
     comments_checked: HashSet<(usize, usize)>,
-    ranges: Vec<Result<Filter, Diagnostic>>,
+    ranges: Vec<Result<Filter, Box<Diagnostic>>>,
 }
 
 pub fn parse_comment(comment_original: &str) -> Option<Vec<FilterConfiguration>> {
@@ -142,14 +142,14 @@ impl NodeVisitor for FilterVisitor {
                                 range: (range.0.bytes(), range.1.bytes()),
                             })
                         } else {
-                            Err(Diagnostic::new(
+                            Err(Box::new(Diagnostic::new(
                                 "invalid_lint_filter",
                                 format!("no lint named `{}` exists", configuration.lint),
                                 Label::new((
                                     trivia_start_position.bytes(),
                                     trivia_end_position.bytes(),
                                 )),
-                            ))
+                            )))
                         }
                     }));
             }
@@ -157,7 +157,7 @@ impl NodeVisitor for FilterVisitor {
     }
 }
 
-fn get_filter_ranges(ast: &Ast) -> Vec<Result<Filter, Diagnostic>> {
+fn get_filter_ranges(ast: &Ast) -> Vec<Result<Filter, Box<Diagnostic>>> {
     let mut filter_visitor = FilterVisitor::default();
     filter_visitor.visit_nodes(ast);
     filter_visitor.ranges
@@ -196,7 +196,7 @@ pub fn filter_diagnostics(
     for thing in filter_ranges {
         match thing {
             Ok(filter) => filters.push(filter),
-            Err(failure) => failures.push(failure),
+            Err(failure) => failures.push(*failure),
         }
     }
 
@@ -339,7 +339,7 @@ pub fn filter_diagnostics(
 
     new_diagnostics.extend(&mut failures.into_iter().map(|failure| CheckerDiagnostic {
         severity: invalid_lint_filter_severity,
-        diagnostic: failure,
+        diagnostic: failure.with_luacheck_code(InvalidLintFilterLint::LUACHECK_CODES[0]),
     }));
 
     new_diagnostics
@@ -1,5 +1,8 @@
 use crate::{ast_util::scopes::ScopeManager, standard_library::StandardLibrary};
-use std::convert::TryInto;
+use std::{
+    convert::TryInto,
+    path::{Path, PathBuf},
+};
 
 use codespan_reporting::diagnostic::{
     Diagnostic as CodespanDiagnostic, Label as CodespanLabel, Severity as CodespanSeverity,
@@ -9,8 +12,11 @@ use serde::de::DeserializeOwned;
 
 pub mod almost_swapped;
 pub mod bad_string_escape;
+pub mod bad_string_format;
+pub mod bad_string_pattern;
 pub mod compare_nan;
 pub mod constant_table_comparison;
+pub mod deep_nesting;
 pub mod deprecated;
 pub mod divide_by_zero;
 pub mod duplicate_keys;
@@ -21,15 +27,19 @@ pub mod high_cyclomatic_complexity;
 pub mod if_same_then_else;
 pub mod ifs_same_cond;
 pub mod invalid_lint_filter;
+pub mod long_function;
 pub mod manual_table_clone;
 pub mod mismatched_arg_count;
 pub mod mixed_table;
 pub mod multiple_statements;
 pub mod must_use;
+pub mod naming_convention;
 pub mod parenthese_conditions;
 pub mod shadowing;
 pub mod standard_library;
 pub mod suspicious_reverse_loop;
+pub mod too_many_parameters;
+pub mod too_many_return_values;
 pub mod type_check_inside_call;
 pub mod unbalanced_assignments;
 pub mod undefined_variable;
@@ -39,9 +49,21 @@ pub mod unused_variable;
 #[cfg(feature = "roblox")]
 pub mod roblox_incorrect_color3_new_bounds;
 
+#[cfg(feature = "roblox")]
+pub mod roblox_incorrect_property_assignment;
+
 #[cfg(feature = "roblox")]
 pub mod roblox_incorrect_roact_usage;
 
+#[cfg(feature = "roblox")]
+pub mod roblox_invalid_instance_path;
+
+#[cfg(feature = "roblox")]
+pub mod roblox_member_security;
+
+#[cfg(feature = "roblox")]
+pub mod roblox_roact_hooks;
+
 #[cfg(feature = "roblox")]
 pub mod roblox_suspicious_udim2_new;
 
@@ -55,6 +77,11 @@ pub trait Lint {
     const SEVERITY: Severity;
     const LINT_TYPE: LintType;
 
+    /// The luacheck warning codes closest to what the lint checks, such as `"113"` for accessing
+    /// an undefined variable. Used when pretending to be luacheck, and by `selene migrate-luacheck`.
+    /// Diagnostics use the first code unless they set their own with `Diagnostic::with_luacheck_code`.
+    const LUACHECK_CODES: &'static [&'static str] = &[];
+
     fn new(config: Self::Config) -> Result<Self, Self::Error>
     where
         Self: Sized;
@@ -96,6 +123,8 @@ pub struct Diagnostic {
     pub notes: Vec<String>,
     pub primary_label: Label,
     pub secondary_labels: Vec<Label>,
+    /// The luacheck warning code to report this as, such as `"212"` for an unused argument
+    pub luacheck_code: Option<&'static str>,
 }
 
 impl Diagnostic {
@@ -107,6 +136,7 @@ impl Diagnostic {
 
             notes: Vec::new(),
             secondary_labels: Vec::new(),
+            luacheck_code: None,
         }
     }
 
@@ -123,6 +153,16 @@ impl Diagnostic {
             notes,
             primary_label,
             secondary_labels,
+            luacheck_code: None,
+        }
+    }
+
+    /// Sets the luacheck code for lints where it depends on the diagnostic, such as unused arguments
+    /// and unused locals being different codes.
+    pub fn with_luacheck_code(self, luacheck_code: &'static str) -> Self {
+        Self {
+            luacheck_code: Some(luacheck_code),
+            ..self
         }
     }
 
@@ -217,6 +257,9 @@ impl Label {
 pub struct Context {
     pub standard_library: StandardLibrary,
     pub user_set_standard_library: Option<Vec<String>>,
+    /// The directory of selene.toml, which paths in lint configs are relative to.
+    /// None is the current directory.
+    pub config_directory: Option<PathBuf>,
 }
 
 impl Context {
@@ -234,12 +277,22 @@ impl Context {
 #[derive(Debug)]
 pub struct AstContext {
     pub scope_manager: ScopeManager,
+    /// The path of the file being linted, if known. Code read from stdin has no path.
+    pub file_path: Option<PathBuf>,
 }
 
 impl AstContext {
     pub fn from_ast(ast: &Ast) -> Self {
         Self {
             scope_manager: ScopeManager::new(ast),
+            file_path: None,
+        }
+    }
+
+    pub fn with_file_path(self, file_path: &Path) -> Self {
+        Self {
+            file_path: Some(file_path.to_owned()),
+            ..self
         }
     }
 }
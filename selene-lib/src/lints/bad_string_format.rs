@@ -0,0 +1,524 @@
+use super::*;
+use crate::{
+    ast_util::{
+        is_vararg, range, scopes::ScopeManager, string_library_call, string_literal_chars,
+        strip_parentheses, ConstantArgument, LiteralChar, StringLibraryCall,
+    },
+    text::plural,
+};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    visitors::Visitor,
+    LuaVersion,
+};
+
+pub struct BadStringFormatLint;
+
+impl Lint for BadStringFormatLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Error;
+    const LINT_TYPE: LintType = LintType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(BadStringFormatLint)
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = BadStringFormatVisitor {
+            diagnostics: Vec::new(),
+            lua_version: context.standard_library.lua_version().0,
+            scope_manager: &ast_context.scope_manager,
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Expects {
+    Any,
+    Integer,
+    Number,
+    String,
+}
+
+#[derive(Debug)]
+struct Specifier {
+    conversion: char,
+    text: String,
+    range: (usize, usize),
+    has_modifiers: bool,
+}
+
+#[derive(Debug)]
+enum FormatProblem {
+    Incomplete((usize, usize)),
+    RepeatedFlags(Specifier),
+    TooLong(Specifier),
+}
+
+fn parse_format_string(chars: &[LiteralChar]) -> (Vec<Specifier>, Vec<FormatProblem>) {
+    const FLAGS: &str = "-+ #0";
+
+    let mut specifiers = Vec::new();
+    let mut problems = Vec::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        if chars[index].value != '%' {
+            index += 1;
+            continue;
+        }
+
+        let start = chars[index].range.0;
+        let mut text = String::from('%');
+        index += 1;
+
+        match chars.get(index) {
+            Some(LiteralChar { value: '%', .. }) => {
+                index += 1;
+                continue;
+            }
+
+            Some(_) => {}
+
+            None => {
+                problems.push(FormatProblem::Incomplete((start, chars[index - 1].range.1)));
+                break;
+            }
+        }
+
+        let mut flags = 0;
+        while let Some(char) = chars.get(index).filter(|char| FLAGS.contains(char.value)) {
+            text.push(char.value);
+            flags += 1;
+            index += 1;
+        }
+
+        let mut too_long = false;
+        let mut read_digits = |text: &mut String, index: &mut usize| {
+            let mut digits = 0;
+            while let Some(char) = chars.get(*index).filter(|char| char.value.is_ascii_digit()) {
+                text.push(char.value);
+                digits += 1;
+                *index += 1;
+            }
+
+            if digits > 2 {
+                too_long = true;
+            }
+
+            digits
+        };
+
+        let mut has_modifiers = flags > 0;
+
+        if read_digits(&mut text, &mut index) > 0 {
+            has_modifiers = true;
+        }
+
+        if let Some(char) = chars.get(index).filter(|char| char.value == '.') {
+            text.push(char.value);
+            has_modifiers = true;
+            index += 1;
+            read_digits(&mut text, &mut index);
+        }
+
+        let conversion = match chars.get(index) {
+            Some(char) => {
+                text.push(char.value);
+                index += 1;
+                char.value
+            }
+
+            None => {
+                problems.push(FormatProblem::Incomplete((start, chars[index - 1].range.1)));
+                break;
+            }
+        };
+
+        let specifier = Specifier {
+            conversion,
+            text,
+            range: (start, chars[index - 1].range.1),
+            has_modifiers,
+        };
+
+        if flags > FLAGS.len() {
+            problems.push(FormatProblem::RepeatedFlags(specifier));
+        } else if too_long {
+            problems.push(FormatProblem::TooLong(specifier));
+        } else {
+            specifiers.push(specifier);
+        }
+    }
+
+    (specifiers, problems)
+}
+
+struct BadStringFormatVisitor<'a> {
+    diagnostics: Vec<Diagnostic>,
+    lua_version: LuaVersion,
+    scope_manager: &'a ScopeManager,
+}
+
+impl BadStringFormatVisitor<'_> {
+    fn expects(&self, specifier: &Specifier) -> Result<Expects, Option<&'static str>> {
+        let version = self.lua_version;
+
+        match specifier.conversion {
+            'c' | 'd' | 'i' | 'o' | 'u' | 'x' | 'X' => Ok(Expects::Integer),
+            'e' | 'E' | 'f' | 'g' | 'G' => Ok(Expects::Number),
+            'q' | 's' => Ok(Expects::String),
+
+            'a' | 'A' => {
+                if version.has_lua52() || version.has_luajit() {
+                    Ok(Expects::Number)
+                } else {
+                    Err(Some("`%a` is only available in Lua 5.2+ and LuaJIT"))
+                }
+            }
+
+            'p' => {
+                if version.has_lua54() {
+                    Ok(Expects::Any)
+                } else {
+                    Err(Some("`%p` is only available in Lua 5.4"))
+                }
+            }
+
+            '*' => {
+                if version.has_luau() {
+                    Ok(Expects::Any)
+                } else {
+                    Err(Some("`%*` is only available in Luau"))
+                }
+            }
+
+            _ => Err(None),
+        }
+    }
+
+    fn check_format(
+        &mut self,
+        call: &ast::FunctionCall,
+        format: &[LiteralChar],
+        arguments: &[&ast::Expression],
+        maybe_more_arguments: bool,
+    ) {
+        let (specifiers, problems) = parse_format_string(format);
+
+        if !problems.is_empty() {
+            for problem in problems {
+                self.diagnostics.push(match problem {
+                    FormatProblem::Incomplete(range) => Diagnostic::new_complete(
+                        "bad_string_format",
+                        "format string ends with an incomplete specifier".to_owned(),
+                        Label::new(range),
+                        vec!["help: use `%%` to write a literal `%`".to_owned()],
+                        Vec::new(),
+                    ),
+
+                    FormatProblem::RepeatedFlags(specifier) => Diagnostic::new(
+                        "bad_string_format",
+                        format!("invalid format specifier `{}`", specifier.text),
+                        Label::new_with_message(specifier.range, "too many flags".to_owned()),
+                    ),
+
+                    FormatProblem::TooLong(specifier) => Diagnostic::new(
+                        "bad_string_format",
+                        format!("invalid format specifier `{}`", specifier.text),
+                        Label::new_with_message(
+                            specifier.range,
+                            "width and precision can only be two digits long".to_owned(),
+                        ),
+                    ),
+                });
+            }
+
+            return;
+        }
+
+        let mut expectations = Vec::with_capacity(specifiers.len());
+        let mut invalid = false;
+
+        for specifier in &specifiers {
+            match self.expects(specifier) {
+                Ok(expects) => {
+                    if specifier.has_modifiers
+                        && (specifier.conversion == '*'
+                            || (specifier.conversion == 'q' && self.lua_version.has_lua54()))
+                    {
+                        self.diagnostics.push(Diagnostic::new(
+                            "bad_string_format",
+                            format!(
+                                "format specifier `%{}` cannot have modifiers",
+                                specifier.conversion
+                            ),
+                            Label::new(specifier.range),
+                        ));
+
+                        invalid = true;
+                    }
+
+                    expectations.push(expects);
+                }
+
+                Err(note) => {
+                    self.diagnostics.push(Diagnostic::new_complete(
+                        "bad_string_format",
+                        format!("invalid format specifier `{}`", specifier.text),
+                        Label::new(specifier.range),
+                        note.into_iter().map(ToOwned::to_owned).collect(),
+                        Vec::new(),
+                    ));
+
+                    invalid = true;
+                }
+            }
+        }
+
+        // The rest of the format string is never reached
+        if invalid {
+            return;
+        }
+
+        if arguments.len() < specifiers.len() && !maybe_more_arguments {
+            self.diagnostics.push(Diagnostic::new_complete(
+                "bad_string_format",
+                format!(
+                    "format string expects {} {}, but {} {} passed",
+                    specifiers.len(),
+                    plural(specifiers.len(), "argument", "arguments"),
+                    arguments.len(),
+                    plural(arguments.len(), "was", "were"),
+                ),
+                Label::from_node(call, None),
+                Vec::new(),
+                specifiers[arguments.len()..]
+                    .iter()
+                    .map(|specifier| {
+                        Label::new_with_message(
+                            specifier.range,
+                            "no argument is passed for this".to_owned(),
+                        )
+                    })
+                    .collect(),
+            ));
+        }
+
+        let fixed_arguments = if maybe_more_arguments {
+            arguments.len() - 1
+        } else {
+            arguments.len()
+        };
+
+        if fixed_arguments > specifiers.len() {
+            let extra_arguments = &arguments[specifiers.len()..fixed_arguments];
+
+            self.diagnostics.push(Diagnostic::new(
+                "bad_string_format",
+                format!(
+                    "format string only uses {} {}, but {} {} passed",
+                    specifiers.len(),
+                    plural(specifiers.len(), "argument", "arguments"),
+                    fixed_arguments,
+                    plural(fixed_arguments, "was", "were"),
+                ),
+                Label::new_with_message(
+                    (
+                        range::<_, usize>(extra_arguments[0]).0,
+                        range::<_, usize>(extra_arguments[extra_arguments.len() - 1]).1,
+                    ),
+                    format!(
+                        "{} unused",
+                        plural(
+                            extra_arguments.len(),
+                            "this argument is",
+                            "these arguments are"
+                        )
+                    ),
+                ),
+            ));
+        }
+
+        for ((specifier, expects), argument) in
+            specifiers.iter().zip(expectations).zip(arguments.iter())
+        {
+            let constant = match ConstantArgument::from_expression(argument) {
+                Some(constant) => constant,
+                None => continue,
+            };
+
+            let problem = match expects {
+                Expects::Any => None,
+
+                Expects::Integer | Expects::Number => match constant.as_number() {
+                    Some(number) => {
+                        if expects == Expects::Integer
+                            && self.lua_version.has_lua53()
+                            && number.fract() != 0.0
+                        {
+                            Some(format!(
+                                "`{}` expects an integer, but this number has no integer representation",
+                                specifier.text
+                            ))
+                        } else {
+                            None
+                        }
+                    }
+
+                    None => Some(format!(
+                        "`{}` expects a number, but received a {} value",
+                        specifier.text,
+                        constant.type_name()
+                    )),
+                },
+
+                Expects::String => {
+                    // `%s` only accepts strings and numbers in Lua 5.1, later versions call
+                    // tostring. `%q` only accepts strings and numbers, until Lua 5.4 also
+                    // accepts nil and booleans.
+                    let accepted = match constant {
+                        ConstantArgument::String(_) | ConstantArgument::Number(_) => true,
+                        ConstantArgument::Nil | ConstantArgument::Bool
+                            if specifier.conversion == 'q' =>
+                        {
+                            self.lua_version.has_lua54()
+                        }
+                        _ if specifier.conversion == 'q' => false,
+                        _ => {
+                            self.lua_version.has_lua52()
+                                || self.lua_version.has_luau()
+                                || self.lua_version.has_luajit()
+                        }
+                    };
+
+                    if accepted {
+                        None
+                    } else {
+                        Some(format!(
+                            "`{}` expects a string, but received a {} value",
+                            specifier.text,
+                            constant.type_name()
+                        ))
+                    }
+                }
+            };
+
+            if let Some(message) = problem {
+                self.diagnostics.push(Diagnostic::new_complete(
+                    "bad_string_format",
+                    message,
+                    Label::from_node(argument, None),
+                    Vec::new(),
+                    vec![Label::new_with_message(
+                        specifier.range,
+                        "format specifier used here".to_owned(),
+                    )],
+                ));
+            }
+        }
+    }
+}
+
+fn format_string_chars(expression: &ast::Expression) -> Option<Vec<LiteralChar>> {
+    match strip_parentheses(expression) {
+        ast::Expression::String(token) => string_literal_chars(token),
+        _ => None,
+    }
+}
+
+impl Visitor for BadStringFormatVisitor<'_> {
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        // `string.format(...)` and `("..."):format(...)`
+        let StringLibraryCall {
+            name,
+            mut arguments,
+            method,
+            literal_receiver,
+            ..
+        } = match string_library_call(call, self.scope_manager) {
+            Some(string_call) => string_call,
+            None => return,
+        };
+
+        if name != "format" {
+            return;
+        }
+
+        let format = if method {
+            literal_receiver.and_then(string_literal_chars)
+        } else if arguments.is_empty() {
+            None
+        } else {
+            format_string_chars(arguments.remove(0))
+        };
+
+        let format = match format {
+            Some(format) => format,
+            None => return,
+        };
+
+        // Parentheses truncate calls and varargs to one value, so `(f())` is only one argument
+        let maybe_more_arguments =
+            matches!(arguments.last(), Some(ast::Expression::FunctionCall(_)))
+                || arguments.last().is_some_and(|argument| is_vararg(argument));
+
+        self.check_format(call, &format, &arguments, maybe_more_arguments);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::*, *};
+
+    #[test]
+    fn test_bad_string_format() {
+        test_lint(
+            BadStringFormatLint::new(()).unwrap(),
+            "bad_string_format",
+            "bad_string_format",
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "lua53")]
+    fn test_lua53() {
+        test_lint(
+            BadStringFormatLint::new(()).unwrap(),
+            "bad_string_format",
+            "lua53",
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "lua54")]
+    fn test_lua54() {
+        test_lint(
+            BadStringFormatLint::new(()).unwrap(),
+            "bad_string_format",
+            "lua54",
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "roblox")]
+    fn test_luau() {
+        test_lint_config(
+            BadStringFormatLint::new(()).unwrap(),
+            "bad_string_format",
+            "luau",
+            TestUtilConfig {
+                standard_library: StandardLibrary::from_name("luau").unwrap(),
+                ..TestUtilConfig::default()
+            },
+        );
+    }
+}
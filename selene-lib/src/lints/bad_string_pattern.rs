@@ -0,0 +1,516 @@
+use super::*;
+use crate::{
+    ast_util::{
+        scopes::ScopeManager, string_library_call, string_literal_chars, strip_parentheses,
+        LiteralChar, StringLibraryCall,
+    },
+    text::plural,
+};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    tokenizer::{Symbol, TokenType},
+    visitors::Visitor,
+    LuaVersion,
+};
+
+// LUA_MAXCAPTURES
+const MAX_CAPTURES: usize = 32;
+
+pub struct BadStringPatternLint;
+
+impl Lint for BadStringPatternLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const LINT_TYPE: LintType = LintType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(BadStringPatternLint)
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = BadStringPatternVisitor {
+            diagnostics: Vec::new(),
+            lua_version: context.standard_library.lua_version().0,
+            scope_manager: &ast_context.scope_manager,
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum PatternProblem {
+    EndsWithPercent((usize, usize)),
+    InvalidBackReference((usize, usize)),
+    MissingBalanceArguments((usize, usize)),
+    MissingBracket((usize, usize)),
+    MissingFrontierSet((usize, usize)),
+    TooManyCaptures((usize, usize)),
+    UnfinishedCapture((usize, usize)),
+    UnopenedCapture((usize, usize)),
+}
+
+impl PatternProblem {
+    fn into_diagnostic(self) -> Diagnostic {
+        let (message, range, notes) = match self {
+            PatternProblem::EndsWithPercent(range) => (
+                "malformed pattern (ends with `%`)",
+                range,
+                vec!["help: use `%%` to match a literal `%`"],
+            ),
+            PatternProblem::InvalidBackReference(range) => (
+                "invalid capture index in pattern",
+                range,
+                vec!["note: back references can only refer to captures that have been closed"],
+            ),
+            PatternProblem::MissingBalanceArguments(range) => (
+                "missing arguments to `%b`",
+                range,
+                vec!["note: `%b` must be followed by two characters, such as `%b()`"],
+            ),
+            PatternProblem::MissingBracket(range) => {
+                ("malformed pattern (missing `]`)", range, Vec::new())
+            }
+            PatternProblem::MissingFrontierSet(range) => {
+                ("missing `[` after `%f` in pattern", range, Vec::new())
+            }
+            PatternProblem::TooManyCaptures(range) => (
+                "too many captures in pattern",
+                range,
+                vec!["note: patterns can have at most 32 captures"],
+            ),
+            PatternProblem::UnfinishedCapture(range) => {
+                ("capture is never closed", range, Vec::new())
+            }
+            PatternProblem::UnopenedCapture(range) => (
+                "pattern closes a capture that was never opened",
+                range,
+                vec!["help: use `%)` to match a literal `)`"],
+            ),
+        };
+
+        Diagnostic::new_complete(
+            "bad_string_pattern",
+            message.to_owned(),
+            Label::new(range),
+            notes.into_iter().map(ToOwned::to_owned).collect(),
+            Vec::new(),
+        )
+    }
+}
+
+struct ParsedPattern {
+    captures: usize,
+    problems: Vec<PatternProblem>,
+}
+
+fn parse_pattern(chars: &[LiteralChar]) -> ParsedPattern {
+    let mut problems = Vec::new();
+
+    // The start of each capture, and whether it's been closed yet
+    let mut captures: Vec<(usize, bool)> = Vec::new();
+
+    let range_of = |start: usize, end: usize| (chars[start].range.0, chars[end].range.1);
+
+    let mut index = 0;
+
+    if chars.first().map(|char| char.value) == Some('^') {
+        index += 1;
+    }
+
+    while index < chars.len() {
+        match chars[index].value {
+            '(' => {
+                if captures.len() == MAX_CAPTURES {
+                    problems.push(PatternProblem::TooManyCaptures(range_of(index, index)));
+                    break;
+                }
+
+                // Position captures, `()`, are closed immediately
+                if chars.get(index + 1).map(|char| char.value) == Some(')') {
+                    captures.push((index, true));
+                    index += 2;
+                } else {
+                    captures.push((index, false));
+                    index += 1;
+                }
+            }
+
+            ')' => {
+                match captures.iter_mut().rev().find(|(_, closed)| !closed) {
+                    Some((_, closed)) => *closed = true,
+                    None => {
+                        problems.push(PatternProblem::UnopenedCapture(range_of(index, index)));
+                        break;
+                    }
+                }
+
+                index += 1;
+            }
+
+            '%' => match chars.get(index + 1).map(|char| char.value) {
+                None => {
+                    problems.push(PatternProblem::EndsWithPercent(range_of(index, index)));
+                    break;
+                }
+
+                Some('b') => {
+                    if index + 3 >= chars.len() {
+                        problems.push(PatternProblem::MissingBalanceArguments(range_of(
+                            index,
+                            chars.len() - 1,
+                        )));
+                        break;
+                    }
+
+                    index += 4;
+                }
+
+                Some('f') => {
+                    if chars.get(index + 2).map(|char| char.value) != Some('[') {
+                        problems.push(PatternProblem::MissingFrontierSet(range_of(
+                            index,
+                            index + 1,
+                        )));
+                        break;
+                    }
+
+                    match set_end(chars, index + 2) {
+                        Ok(end) => index = end + 1,
+                        Err(problem) => {
+                            problems.push(problem);
+                            break;
+                        }
+                    }
+                }
+
+                Some(digit @ '0'..='9') => {
+                    let capture_index = digit.to_digit(10).unwrap() as usize;
+
+                    if capture_index == 0
+                        || !captures
+                            .get(capture_index - 1)
+                            .is_some_and(|(_, closed)| *closed)
+                    {
+                        problems.push(PatternProblem::InvalidBackReference(range_of(
+                            index,
+                            index + 1,
+                        )));
+                        break;
+                    }
+
+                    index += 2;
+                }
+
+                Some(_) => index += 2,
+            },
+
+            '[' => match set_end(chars, index) {
+                Ok(end) => index = end + 1,
+                Err(problem) => {
+                    problems.push(problem);
+                    break;
+                }
+            },
+
+            _ => index += 1,
+        }
+    }
+
+    if problems.is_empty() {
+        if let Some((start, _)) = captures.iter().find(|(_, closed)| !closed) {
+            problems.push(PatternProblem::UnfinishedCapture(range_of(*start, *start)));
+        }
+    }
+
+    ParsedPattern {
+        captures: captures.len(),
+        problems,
+    }
+}
+
+// Given the index of a `[`, returns the index of the `]` that closes the set
+fn set_end(chars: &[LiteralChar], start: usize) -> Result<usize, PatternProblem> {
+    let mut index = start + 1;
+
+    if chars.get(index).map(|char| char.value) == Some('^') {
+        index += 1;
+    }
+
+    // The first character of a set is always included, so `[]]` is a set of `]`
+    loop {
+        let char = match chars.get(index) {
+            Some(char) => char,
+            None => {
+                return Err(PatternProblem::MissingBracket((
+                    chars[start].range.0,
+                    chars[chars.len() - 1].range.1,
+                )))
+            }
+        };
+
+        index += 1;
+
+        if char.value == '%' && index < chars.len() {
+            index += 1;
+        }
+
+        match chars.get(index) {
+            Some(char) if char.value == ']' => return Ok(index),
+            Some(_) => {}
+            None => {
+                return Err(PatternProblem::MissingBracket((
+                    chars[start].range.0,
+                    chars[chars.len() - 1].range.1,
+                )))
+            }
+        }
+    }
+}
+
+// Finds `.` and `-` in patterns that otherwise look like they were meant to be plain text,
+// such as `"file.txt"` or `"my-name"`.
+fn suspicious_literal_characters(chars: &[LiteralChar]) -> Vec<&LiteralChar> {
+    const MAGIC_CHARACTERS: &str = "^$()%*+?[]";
+
+    if chars
+        .iter()
+        .any(|char| MAGIC_CHARACTERS.contains(char.value))
+    {
+        return Vec::new();
+    }
+
+    chars
+        .windows(3)
+        .filter(|window| {
+            matches!(window[1].value, '.' | '-')
+                && window[0].value.is_alphanumeric()
+                && window[2].value.is_alphanumeric()
+        })
+        .map(|window| &window[1])
+        .collect()
+}
+
+fn literal_chars(expression: &ast::Expression) -> Option<Vec<LiteralChar>> {
+    match strip_parentheses(expression) {
+        ast::Expression::String(token) => string_literal_chars(token),
+        _ => None,
+    }
+}
+
+enum Plain {
+    No,
+    Yes,
+    Unknown,
+}
+
+fn plain_argument(expression: Option<&&ast::Expression>) -> Plain {
+    let expression = match expression {
+        Some(expression) => strip_parentheses(expression),
+        None => return Plain::No,
+    };
+
+    match expression {
+        ast::Expression::Symbol(token) => match token.token_type() {
+            TokenType::Symbol {
+                symbol: Symbol::False | Symbol::Nil,
+            } => Plain::No,
+            TokenType::Symbol {
+                symbol: Symbol::True,
+            } => Plain::Yes,
+            _ => Plain::Unknown,
+        },
+
+        _ => Plain::Unknown,
+    }
+}
+
+struct BadStringPatternVisitor<'a> {
+    diagnostics: Vec<Diagnostic>,
+    lua_version: LuaVersion,
+    scope_manager: &'a ScopeManager,
+}
+
+impl BadStringPatternVisitor<'_> {
+    fn check_replacement(&mut self, replacement: &[LiteralChar], captures: Option<usize>) {
+        let strict_replacements = self.lua_version.has_lua52() || self.lua_version.has_luau();
+
+        let mut index = 0;
+
+        while index < replacement.len() {
+            if replacement[index].value != '%' {
+                index += 1;
+                continue;
+            }
+
+            let range = |end: usize| (replacement[index].range.0, replacement[end].range.1);
+
+            match replacement.get(index + 1).map(|char| char.value) {
+                Some('%') | Some('0') => {}
+
+                Some(digit @ '1'..='9') => {
+                    let capture_index = digit.to_digit(10).unwrap() as usize;
+
+                    if let Some(captures) = captures {
+                        // With no captures, `%1` refers to the whole match
+                        if capture_index > captures.max(1) {
+                            self.diagnostics.push(Diagnostic::new_complete(
+                                "bad_string_pattern",
+                                format!("invalid capture index `%{digit}` in replacement string"),
+                                Label::new(range(index + 1)),
+                                vec![format!(
+                                    "note: the pattern only has {} {}",
+                                    captures,
+                                    plural(captures, "capture", "captures")
+                                )],
+                                Vec::new(),
+                            ));
+                        }
+                    }
+                }
+
+                next => {
+                    if strict_replacements {
+                        self.diagnostics.push(Diagnostic::new_complete(
+                            "bad_string_pattern",
+                            "invalid use of `%` in replacement string".to_owned(),
+                            Label::new(range(if next.is_some() { index + 1 } else { index })),
+                            vec!["help: use `%%` to write a literal `%`".to_owned()],
+                            Vec::new(),
+                        ));
+                    }
+                }
+            }
+
+            index += 2;
+        }
+    }
+}
+
+impl Visitor for BadStringPatternVisitor<'_> {
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        let StringLibraryCall {
+            name,
+            arguments,
+            method,
+            string_receiver,
+            ..
+        } = match string_library_call(call, self.scope_manager) {
+            Some(string_call) => string_call,
+            None => return,
+        };
+
+        if !matches!(name.as_str(), "find" | "match" | "gmatch" | "gsub") {
+            return;
+        }
+
+        // `router:match("user-id")` is just as likely to be on something that isn't a string
+        if method && !string_receiver {
+            return;
+        }
+
+        // Arguments after the string being searched
+        let arguments = if method {
+            &arguments[..]
+        } else if arguments.is_empty() {
+            return;
+        } else {
+            &arguments[1..]
+        };
+
+        let is_find = name == "find";
+
+        if is_find {
+            match plain_argument(arguments.get(2)) {
+                Plain::No => {}
+                Plain::Yes | Plain::Unknown => return,
+            }
+        }
+
+        let pattern = arguments.first().and_then(|pattern| literal_chars(pattern));
+
+        let mut captures = None;
+
+        if let Some(pattern) = &pattern {
+            let parsed = parse_pattern(pattern);
+
+            if !parsed.problems.is_empty() {
+                self.diagnostics.extend(
+                    parsed
+                        .problems
+                        .into_iter()
+                        .map(PatternProblem::into_diagnostic),
+                );
+
+                return;
+            }
+
+            captures = Some(parsed.captures);
+
+            for char in suspicious_literal_characters(pattern) {
+                let mut notes = vec![format!(
+                    "help: if you meant to match a literal `{}`, escape it with `%{}`",
+                    char.value, char.value
+                )];
+
+                if is_find {
+                    notes.push(
+                        "help: or, search for plain text with `string.find(text, pattern, 1, true)`"
+                            .to_owned(),
+                    );
+                }
+
+                self.diagnostics.push(Diagnostic::new_complete(
+                    "bad_string_pattern",
+                    if char.value == '.' {
+                        "unescaped `.` in pattern matches any character".to_owned()
+                    } else {
+                        "unescaped `-` in pattern repeats the previous character".to_owned()
+                    },
+                    Label::new(char.range),
+                    notes,
+                    Vec::new(),
+                ));
+            }
+        }
+
+        if name == "gsub" {
+            if let Some(replacement) = arguments
+                .get(1)
+                .and_then(|argument| literal_chars(argument))
+            {
+                self.check_replacement(&replacement, captures);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_bad_string_pattern() {
+        test_lint(
+            BadStringPatternLint::new(()).unwrap(),
+            "bad_string_pattern",
+            "bad_string_pattern",
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "lua52")]
+    fn test_lua52_replacements() {
+        test_lint(
+            BadStringPatternLint::new(()).unwrap(),
+            "bad_string_pattern",
+            "lua52_replacements",
+        );
+    }
+}
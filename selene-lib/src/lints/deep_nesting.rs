@@ -0,0 +1,122 @@
+use super::*;
+use crate::ast_util::{
+    functions::{nested_blocks, visit_functions},
+    range,
+};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    node::Node,
+};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DeepNestingConfig {
+    maximum_depth: usize,
+}
+
+impl Default for DeepNestingConfig {
+    fn default() -> Self {
+        Self { maximum_depth: 6 }
+    }
+}
+
+#[derive(Default)]
+pub struct DeepNestingLint {
+    config: DeepNestingConfig,
+}
+
+impl Lint for DeepNestingLint {
+    type Config = DeepNestingConfig;
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Allow;
+    const LINT_TYPE: LintType = LintType::Style;
+
+    fn new(config: Self::Config) -> Result<Self, Self::Error> {
+        Ok(DeepNestingLint { config })
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, _: &AstContext) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        visit_functions(ast, |function| {
+            let Some((depth, deepest_stmt)) = deepest_stmt(function.body.block(), 0) else {
+                return;
+            };
+
+            if depth > self.config.maximum_depth {
+                diagnostics.push(Diagnostic::new_complete(
+                    "deep_nesting",
+                    format!(
+                        "{} is nested too deeply ({depth} > {})",
+                        function.description(),
+                        self.config.maximum_depth
+                    ),
+                    Label::new(function.signature),
+                    Vec::new(),
+                    // Only the keyword, such as `if` or `for`, since the statement itself spans the whole block
+                    deepest_stmt
+                        .tokens()
+                        .next()
+                        .map(|keyword| {
+                            Label::new_with_message(
+                                range::<_, usize>(keyword),
+                                format!("nested {depth} blocks deep"),
+                            )
+                        })
+                        .into_iter()
+                        .collect(),
+                ));
+            }
+        });
+
+        diagnostics
+    }
+}
+
+// Returns the depth of the deepest block, and the statement that opens it.
+// Blocks inside of nested functions are not counted, as those functions are measured separately.
+pub(crate) fn deepest_stmt(block: &ast::Block, depth: usize) -> Option<(usize, &ast::Stmt)> {
+    let mut deepest: Option<(usize, &ast::Stmt)> = None;
+
+    for stmt in block.stmts() {
+        let blocks = nested_blocks(stmt);
+        if blocks.is_empty() {
+            continue;
+        }
+
+        let mut stmt_deepest = (depth + 1, stmt);
+
+        for nested_block in blocks {
+            if let Some(nested_deepest) = deepest_stmt(nested_block, depth + 1) {
+                if nested_deepest.0 > stmt_deepest.0 {
+                    stmt_deepest = nested_deepest;
+                }
+            }
+        }
+
+        if !matches!(deepest, Some((deepest_depth, _)) if deepest_depth >= stmt_deepest.0) {
+            deepest = Some(stmt_deepest);
+        }
+    }
+
+    deepest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_deep_nesting() {
+        test_lint(
+            DeepNestingLint::new(DeepNestingConfig { maximum_depth: 2 }).unwrap(),
+            "deep_nesting",
+            "deep_nesting",
+        );
+    }
+}
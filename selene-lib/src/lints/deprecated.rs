@@ -1,13 +1,13 @@
 use std::convert::Infallible;
 
 use full_moon::{ast, visitors::Visitor};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::ast_util::{name_paths::*, range, scopes::ScopeManager};
 
 use super::{super::standard_library::*, *};
 
-#[derive(Clone, Default, Deserialize)]
+#[derive(Clone, Default, Deserialize, Serialize)]
 #[serde(default)]
 pub struct DeprecatedLintConfig {
     pub allow: Vec<String>,
@@ -141,6 +141,24 @@ impl<'a> DeprecatedVisitor<'a> {
             ));
         }
 
+        if let Some(field) = self.standard_library.find_global(name_path) {
+            // Fields that aren't available in any version are reported by incorrect_standard_library_use
+            if let FieldAvailability::Partial(reason) =
+                self.standard_library.field_availability(field)
+            {
+                self.diagnostics.push(Diagnostic::new_complete(
+                    "deprecated",
+                    format!(
+                        "standard library {what} `{}` is not available in every version of Lua",
+                        name_path.join(".")
+                    ),
+                    Label::from_node(node, None),
+                    vec![reason],
+                    Vec::new(),
+                ));
+            }
+        }
+
         if let Some(Field {
             field_kind: FieldKind::Function(function),
             ..
@@ -289,6 +307,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_lua_versions() {
+        let mut standard_library = StandardLibrary::from_name("lua54").unwrap();
+        standard_library.lua_versions = vec![LuaVersion::Lua51, LuaVersion::Lua54];
+
+        test_lint_config(
+            DeprecatedLint::new(DeprecatedLintConfig::default()).unwrap(),
+            "deprecated",
+            "lua_versions",
+            TestUtilConfig {
+                standard_library,
+                ..TestUtilConfig::default()
+            },
+        );
+    }
+
     #[test]
     fn test_specific_allow() {
         test_lint(
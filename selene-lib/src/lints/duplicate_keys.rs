@@ -17,6 +17,7 @@ impl Lint for DuplicateKeysLint {
 
     const SEVERITY: Severity = Severity::Error;
     const LINT_TYPE: LintType = LintType::Correctness;
+    const LUACHECK_CODES: &'static [&'static str] = &["314"];
 
     fn new(_: Self::Config) -> Result<Self, Self::Error> {
         Ok(DuplicateKeysLint)
@@ -7,9 +7,9 @@ use full_moon::{
     tokenizer::{Token, TokenKind},
     visitors::Visitor,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Default, Deserialize)]
+#[derive(Clone, Copy, Default, Deserialize, Serialize)]
 #[serde(default)]
 pub struct EmptyIfLintConfig {
     comments_count: bool,
@@ -25,6 +25,7 @@ impl Lint for EmptyIfLint {
 
     const SEVERITY: Severity = Severity::Warning;
     const LINT_TYPE: LintType = LintType::Style;
+    const LUACHECK_CODES: &'static [&'static str] = &["542"];
 
     fn new(config: Self::Config) -> Result<Self, Self::Error> {
         Ok(EmptyIfLint { config })
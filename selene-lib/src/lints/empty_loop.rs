@@ -7,9 +7,9 @@ use full_moon::{
     tokenizer::{Token, TokenKind},
     visitors::Visitor,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Default, Deserialize)]
+#[derive(Clone, Copy, Default, Deserialize, Serialize)]
 #[serde(default)]
 pub struct EmptyLoopLintConfig {
     comments_count: bool,
@@ -3,13 +3,13 @@ use std::collections::HashSet;
 
 use full_moon::ast::Ast;
 use regex::Regex;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 fn is_global(name: &str, roblox: bool) -> bool {
     (roblox && name == "shared") || name == "_G"
 }
 
-#[derive(Clone, Default, Deserialize)]
+#[derive(Clone, Default, Deserialize, Serialize)]
 #[serde(default)]
 pub struct GlobalConfig {
     ignore_pattern: Option<String>,
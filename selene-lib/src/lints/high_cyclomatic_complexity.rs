@@ -1,15 +1,12 @@
 use super::*;
-use crate::ast_util::range;
+use crate::ast_util::functions::visit_functions;
 use std::convert::Infallible;
 
-use full_moon::{
-    ast::{self, Ast, TableConstructor},
-    visitors::Visitor,
-};
+use full_moon::ast::{self, Ast, TableConstructor};
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Deserialize)]
+#[derive(Clone, Copy, Deserialize, Serialize)]
 pub struct HighCyclomaticComplexityConfig {
     maximum_complexity: u16,
 }
@@ -34,39 +31,32 @@ impl Lint for HighCyclomaticComplexityLint {
 
     const SEVERITY: Severity = Severity::Allow;
     const LINT_TYPE: LintType = LintType::Style;
+    const LUACHECK_CODES: &'static [&'static str] = &["561"];
 
     fn new(config: Self::Config) -> Result<Self, Self::Error> {
         Ok(HighCyclomaticComplexityLint { config })
     }
 
     fn pass(&self, ast: &Ast, _: &Context, _: &AstContext) -> Vec<Diagnostic> {
-        let mut visitor = HighCyclomaticComplexityVisitor {
-            positions: Vec::new(),
-            config: self.config,
-        };
-
-        visitor.visit_ast(ast);
-
-        visitor
-            .positions
-            .into_iter()
-            .map(|(position, complexity)| {
-                Diagnostic::new(
+        let mut diagnostics = Vec::new();
+
+        visit_functions(ast, |function| {
+            let complexity = count_block_complexity(function.body.block(), 1);
+            if complexity > self.config.maximum_complexity {
+                diagnostics.push(Diagnostic::new(
                     "high_cyclomatic_complexity",
                     format!(
-                        "cyclomatic complexity is too high ({complexity} > {})",
+                        "cyclomatic complexity of {} is too high ({complexity} > {})",
+                        function.description(),
                         self.config.maximum_complexity
                     ),
-                    Label::new(position),
-                )
-            })
-            .collect()
-    }
-}
+                    Label::new(function.signature),
+                ));
+            }
+        });
 
-struct HighCyclomaticComplexityVisitor {
-    positions: Vec<((u32, u32), u16)>,
-    config: HighCyclomaticComplexityConfig,
+        diagnostics
+    }
 }
 
 fn count_table_complexity(table: &TableConstructor, starting_complexity: u16) -> u16 {
@@ -242,7 +232,7 @@ fn count_expression_complexity(expression: &ast::Expression, starting_complexity
     }
 }
 
-fn count_block_complexity(block: &ast::Block, starting_complexity: u16) -> u16 {
+pub(crate) fn count_block_complexity(block: &ast::Block, starting_complexity: u16) -> u16 {
     let mut complexity = starting_complexity;
 
     // we don't immediately return from the matched blocks so that we can add in any complexity from the last statement
@@ -349,6 +339,11 @@ fn count_block_complexity(block: &ast::Block, starting_complexity: u16) -> u16 {
                 // doesn't contain branch points
             }
 
+            #[cfg(feature = "roblox")]
+            ast::Stmt::ExportedTypeFunction(_) | ast::Stmt::TypeFunction(_) => {
+                // only runs while type checking
+            }
+
             #[cfg(feature = "lua52")]
             ast::Stmt::Goto(_) => {
                 // not a dynamic branch point itself
@@ -372,50 +367,6 @@ fn count_block_complexity(block: &ast::Block, starting_complexity: u16) -> u16 {
     complexity
 }
 
-impl Visitor for HighCyclomaticComplexityVisitor {
-    fn visit_local_function(&mut self, local_function: &ast::LocalFunction) {
-        let complexity = count_block_complexity(local_function.body().block(), 1);
-        if complexity > self.config.maximum_complexity {
-            self.positions.push((
-                (
-                    range(local_function.function_token()).0,
-                    range(local_function.body().parameters_parentheses()).1,
-                ),
-                complexity,
-            ));
-        }
-    }
-
-    fn visit_function_declaration(&mut self, function_declaration: &ast::FunctionDeclaration) {
-        let complexity = count_block_complexity(function_declaration.body().block(), 1);
-        if complexity > self.config.maximum_complexity {
-            self.positions.push((
-                (
-                    range(function_declaration.function_token()).0,
-                    range(function_declaration.body().parameters_parentheses()).1,
-                ),
-                complexity,
-            ));
-        }
-    }
-
-    fn visit_expression(&mut self, expression: &ast::Expression) {
-        if let ast::Expression::Function(function_box) = expression {
-            let function_body = &function_box.1;
-            let complexity = count_block_complexity(function_body.block(), 1);
-            if complexity > self.config.maximum_complexity {
-                self.positions.push((
-                    (
-                        expression.start_position().unwrap().bytes() as u32,
-                        range(function_body.parameters_parentheses()).1,
-                    ),
-                    complexity,
-                ));
-            }
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::{super::test_util::test_lint, *};
@@ -12,6 +12,7 @@ impl Lint for InvalidLintFilterLint {
 
     const SEVERITY: Severity = Severity::Error;
     const LINT_TYPE: LintType = LintType::Correctness;
+    const LUACHECK_CODES: &'static [&'static str] = &["021"];
 
     fn new(_: Self::Config) -> Result<Self, Self::Error> {
         Ok(InvalidLintFilterLint)
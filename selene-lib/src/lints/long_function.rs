@@ -0,0 +1,111 @@
+use super::*;
+use crate::ast_util::functions::{nested_blocks, visit_functions};
+use std::convert::Infallible;
+
+use full_moon::ast::{self, Ast};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Deserialize, Serialize)]
+#[serde(default)]
+pub struct LongFunctionConfig {
+    maximum_statements: usize,
+    maximum_lines: usize,
+}
+
+impl Default for LongFunctionConfig {
+    fn default() -> Self {
+        Self {
+            maximum_statements: 100,
+            maximum_lines: 200,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct LongFunctionLint {
+    config: LongFunctionConfig,
+}
+
+impl Lint for LongFunctionLint {
+    type Config = LongFunctionConfig;
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Allow;
+    const LINT_TYPE: LintType = LintType::Style;
+
+    fn new(config: Self::Config) -> Result<Self, Self::Error> {
+        Ok(LongFunctionLint { config })
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, _: &AstContext) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        visit_functions(ast, |function| {
+            let statements = count_statements(function.body.block());
+            if statements > self.config.maximum_statements {
+                diagnostics.push(Diagnostic::new(
+                    "long_function",
+                    format!(
+                        "{} has too many statements ({statements} > {})",
+                        function.description(),
+                        self.config.maximum_statements
+                    ),
+                    Label::new(function.signature),
+                ));
+            }
+
+            // Includes the line with the signature and the line with `end`
+            let start = function.body.parameters_parentheses().tokens().0;
+            let end = function.body.end_token();
+            let lines =
+                end.token().end_position().line() - start.token().start_position().line() + 1;
+            if lines > self.config.maximum_lines {
+                diagnostics.push(Diagnostic::new(
+                    "long_function",
+                    format!(
+                        "{} has too many lines ({lines} > {})",
+                        function.description(),
+                        self.config.maximum_lines
+                    ),
+                    Label::new(function.signature),
+                ));
+            }
+        });
+
+        diagnostics
+    }
+}
+
+// Statements inside of nested functions are not counted, as those functions are measured separately
+pub(crate) fn count_statements(block: &ast::Block) -> usize {
+    let mut count = block.last_stmt().is_some() as usize;
+
+    for stmt in block.stmts() {
+        count += 1;
+
+        for nested_block in nested_blocks(stmt) {
+            count += count_statements(nested_block);
+        }
+    }
+
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_long_function() {
+        test_lint(
+            LongFunctionLint::new(LongFunctionConfig {
+                maximum_statements: 5,
+                maximum_lines: 10,
+            })
+            .unwrap(),
+            "long_function",
+            "long_function",
+        );
+    }
+}
@@ -6,9 +6,9 @@ use full_moon::{
     node::Node,
     visitors::Visitor,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Default, Deserialize)]
+#[derive(Clone, Copy, Default, Deserialize, Serialize)]
 pub struct MultipleStatementsConfig {
     one_line_if: OneLineIf,
 }
@@ -17,7 +17,7 @@ pub struct MultipleStatementsLint {
     config: MultipleStatementsConfig,
 }
 
-#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum OneLineIf {
     Allow,
@@ -0,0 +1,501 @@
+use super::*;
+use crate::ast_util::{
+    range,
+    scopes::{ReferenceWrite, VariableKind},
+};
+use std::{collections::HashSet, fmt};
+
+use full_moon::{
+    ast::{self, Ast},
+    tokenizer::{Symbol, TokenReference, TokenType},
+    visitors::Visitor,
+};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct NamingConventionConfig {
+    locals: Option<String>,
+    functions: Option<String>,
+    parameters: Option<String>,
+    globals: Option<String>,
+    methods: Option<String>,
+    constants: Option<String>,
+    ignore_pattern: String,
+}
+
+impl Default for NamingConventionConfig {
+    fn default() -> Self {
+        Self {
+            locals: None,
+            functions: None,
+            parameters: None,
+            globals: None,
+            methods: None,
+            constants: None,
+            ignore_pattern: "^_".to_owned(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CaseStyle {
+    Camel,
+    Pascal,
+    Snake,
+    ScreamingSnake,
+}
+
+impl CaseStyle {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "camelCase" => Some(CaseStyle::Camel),
+            "PascalCase" => Some(CaseStyle::Pascal),
+            "snake_case" => Some(CaseStyle::Snake),
+            "SCREAMING_SNAKE_CASE" => Some(CaseStyle::ScreamingSnake),
+            _ => None,
+        }
+    }
+
+    fn matches(self, name: &str) -> bool {
+        let name = name.trim_start_matches('_');
+        let Some(first) = name.chars().next() else {
+            return true;
+        };
+
+        match self {
+            CaseStyle::Camel | CaseStyle::Pascal => {
+                let first_matches = if self == CaseStyle::Camel {
+                    first.is_lowercase()
+                } else {
+                    first.is_uppercase()
+                };
+
+                first_matches && name.chars().all(char::is_alphanumeric)
+            }
+
+            CaseStyle::Snake | CaseStyle::ScreamingSnake => {
+                let is_cased = if self == CaseStyle::Snake {
+                    char::is_lowercase
+                } else {
+                    char::is_uppercase
+                };
+
+                is_cased(first)
+                    && name.split('_').all(|word| {
+                        !word.is_empty()
+                            && word.chars().all(|char| char.is_numeric() || is_cased(char))
+                    })
+            }
+        }
+    }
+
+    fn convert(self, name: &str) -> Option<String> {
+        let trimmed = name.trim_start_matches('_');
+        let prefix = &name[..name.len() - trimmed.len()];
+        let words = split_words(trimmed);
+
+        if words.is_empty() {
+            return None;
+        }
+
+        let converted = match self {
+            CaseStyle::Camel => words
+                .iter()
+                .enumerate()
+                .map(|(index, word)| {
+                    if index == 0 {
+                        word.to_lowercase()
+                    } else {
+                        capitalize(word)
+                    }
+                })
+                .collect::<String>(),
+
+            CaseStyle::Pascal => words.iter().map(|word| capitalize(word)).collect(),
+
+            CaseStyle::Snake => words
+                .iter()
+                .map(|word| word.to_lowercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+
+            CaseStyle::ScreamingSnake => words
+                .iter()
+                .map(|word| word.to_uppercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+        };
+
+        Some(format!("{prefix}{converted}"))
+    }
+}
+
+impl fmt::Display for CaseStyle {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(match self {
+            CaseStyle::Camel => "camelCase",
+            CaseStyle::Pascal => "PascalCase",
+            CaseStyle::Snake => "snake_case",
+            CaseStyle::ScreamingSnake => "SCREAMING_SNAKE_CASE",
+        })
+    }
+}
+
+// Splits a name into words on underscores and case changes.
+// `HTTPServer2Port` produces ["HTTP", "Server2", "Port"].
+fn split_words(name: &str) -> Vec<String> {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut words = Vec::new();
+    let mut current = String::new();
+
+    for (index, &char) in chars.iter().enumerate() {
+        if char == '_' {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+
+            continue;
+        }
+
+        if char.is_uppercase() && !current.is_empty() {
+            let previous = chars[index - 1];
+            let next_is_lowercase =
+                matches!(chars.get(index + 1), Some(next) if next.is_lowercase());
+
+            if previous.is_lowercase()
+                || previous.is_numeric()
+                || (previous.is_uppercase() && next_is_lowercase)
+            {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+
+        current.push(char);
+    }
+
+    if !current.is_empty() {
+        words.push(current);
+    }
+
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+
+    match chars.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect(),
+        None => String::new(),
+    }
+}
+
+enum NamingStyle {
+    Case(CaseStyle),
+    Pattern(Regex),
+}
+
+impl NamingStyle {
+    fn new(style: &str) -> Result<Self, regex::Error> {
+        match CaseStyle::from_name(style) {
+            Some(case_style) => Ok(NamingStyle::Case(case_style)),
+            None => Ok(NamingStyle::Pattern(Regex::new(style)?)),
+        }
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        match self {
+            NamingStyle::Case(case_style) => case_style.matches(name),
+            NamingStyle::Pattern(regex) => regex.is_match(name),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum NameKind {
+    Local,
+    Function,
+    Parameter,
+    Global,
+    Method,
+    Constant,
+}
+
+impl NameKind {
+    fn description(self) -> &'static str {
+        match self {
+            NameKind::Local => "local",
+            NameKind::Function => "function",
+            NameKind::Parameter => "parameter",
+            NameKind::Global => "global",
+            NameKind::Method => "method",
+            NameKind::Constant => "constant",
+        }
+    }
+}
+
+pub struct NamingConventionLint {
+    locals: Option<NamingStyle>,
+    functions: Option<NamingStyle>,
+    parameters: Option<NamingStyle>,
+    globals: Option<NamingStyle>,
+    methods: Option<NamingStyle>,
+    constants: Option<NamingStyle>,
+    ignore_pattern: Regex,
+}
+
+impl NamingConventionLint {
+    fn style_for(&self, kind: NameKind) -> Option<&NamingStyle> {
+        match kind {
+            NameKind::Local => self.locals.as_ref(),
+            NameKind::Function => self.functions.as_ref(),
+            NameKind::Parameter => self.parameters.as_ref(),
+            NameKind::Global => self.globals.as_ref(),
+            NameKind::Method => self.methods.as_ref(),
+            // Constants are still locals, so fall back to that style if no constant style is set
+            NameKind::Constant => self.constants.as_ref().or(self.locals.as_ref()),
+        }
+    }
+
+    fn check(&self, kind: NameKind, name: &str, position: (usize, usize)) -> Option<Diagnostic> {
+        if self.ignore_pattern.is_match(name) {
+            return None;
+        }
+
+        let style = self.style_for(kind)?;
+
+        if style.matches(name) {
+            return None;
+        }
+
+        let (message, notes) = match style {
+            NamingStyle::Case(case_style) => (
+                format!("{} `{name}` is not in {case_style}", kind.description()),
+                case_style
+                    .convert(name)
+                    .filter(|converted| converted != name && case_style.matches(converted))
+                    .map(|converted| vec![format!("try renaming it to `{converted}`")])
+                    .unwrap_or_default(),
+            ),
+
+            NamingStyle::Pattern(regex) => (
+                format!(
+                    "{} `{name}` does not match the pattern `{}`",
+                    kind.description(),
+                    regex.as_str()
+                ),
+                Vec::new(),
+            ),
+        };
+
+        Some(Diagnostic::new_complete(
+            "naming_convention",
+            message,
+            Label::new(position),
+            notes,
+            Vec::new(),
+        ))
+    }
+}
+
+impl Lint for NamingConventionLint {
+    type Config = NamingConventionConfig;
+    type Error = regex::Error;
+
+    const SEVERITY: Severity = Severity::Allow;
+    const LINT_TYPE: LintType = LintType::Style;
+
+    fn new(config: Self::Config) -> Result<Self, Self::Error> {
+        let style = |style: Option<String>| style.as_deref().map(NamingStyle::new).transpose();
+
+        Ok(NamingConventionLint {
+            locals: style(config.locals)?,
+            functions: style(config.functions)?,
+            parameters: style(config.parameters)?,
+            globals: style(config.globals)?,
+            methods: style(config.methods)?,
+            constants: style(config.constants)?,
+            ignore_pattern: Regex::new(&config.ignore_pattern)?,
+        })
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = NamingConventionVisitor::default();
+        visitor.collect_constants(ast);
+        visitor.visit_ast(ast);
+
+        let mut diagnostics = Vec::new();
+
+        for (_, variable) in ast_context.scope_manager.variables.iter() {
+            if variable.is_self || variable.name == "..." {
+                continue;
+            }
+
+            let Some(&identifier) = variable.identifiers.first() else {
+                continue;
+            };
+
+            let kind = match variable.kind {
+                VariableKind::Local => {
+                    let writes = variable
+                        .references
+                        .iter()
+                        .filter(|id| {
+                            ast_context.scope_manager.references[**id].write
+                                == Some(ReferenceWrite::Assign)
+                        })
+                        .count();
+
+                    if writes <= 1 && visitor.constants.contains(&identifier) {
+                        NameKind::Constant
+                    } else {
+                        NameKind::Local
+                    }
+                }
+
+                VariableKind::LocalFunction | VariableKind::GlobalFunction => NameKind::Function,
+                VariableKind::Parameter => NameKind::Parameter,
+
+                VariableKind::Global => {
+                    // Assigning to globals from the standard library, like `_VERSION`, isn't naming them
+                    if context
+                        .standard_library
+                        .find_global(&[variable.name.as_str()])
+                        .is_some()
+                    {
+                        continue;
+                    }
+
+                    NameKind::Global
+                }
+            };
+
+            diagnostics.extend(self.check(kind, &variable.name, identifier));
+        }
+
+        for (kind, name) in visitor.declared_fields {
+            diagnostics.extend(self.check(kind, &name.token().to_string(), range(&name)));
+        }
+
+        diagnostics
+    }
+}
+
+#[derive(Default)]
+struct NamingConventionVisitor {
+    // Identifiers of top level locals assigned a literal, like `local MAX_PLAYERS = 10`
+    constants: HashSet<(usize, usize)>,
+    // The `b` in `function a.b()` and `function a:b()`.
+    // Simple names like `function a()` are variables, and are handled by the scope manager.
+    declared_fields: Vec<(NameKind, TokenReference)>,
+}
+
+impl NamingConventionVisitor {
+    fn collect_constants(&mut self, ast: &Ast) {
+        for stmt in ast.nodes().stmts() {
+            let ast::Stmt::LocalAssignment(local_assignment) = stmt else {
+                continue;
+            };
+
+            for (name, expression) in local_assignment
+                .names()
+                .iter()
+                .zip(local_assignment.expressions().iter())
+            {
+                if is_literal(expression) {
+                    self.constants.insert(range(name));
+                }
+            }
+        }
+    }
+}
+
+fn is_literal(expression: &ast::Expression) -> bool {
+    match expression {
+        ast::Expression::Number(_) | ast::Expression::String(_) => true,
+
+        ast::Expression::Symbol(symbol) => matches!(
+            symbol.token_type(),
+            TokenType::Symbol {
+                symbol: Symbol::True | Symbol::False,
+            }
+        ),
+
+        ast::Expression::UnaryOperator { unop, expression } => {
+            matches!(unop, ast::UnOp::Minus(_)) && is_literal(expression)
+        }
+
+        _ => false,
+    }
+}
+
+impl Visitor for NamingConventionVisitor {
+    fn visit_function_declaration(&mut self, declaration: &ast::FunctionDeclaration) {
+        let name = declaration.name();
+
+        if let Some(method_name) = name.method_name() {
+            self.declared_fields
+                .push((NameKind::Method, method_name.clone()));
+        } else if name.names().len() > 1 {
+            if let Some(field) = name.names().iter().last() {
+                self.declared_fields
+                    .push((NameKind::Function, field.clone()));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_split_words() {
+        assert_eq!(
+            split_words("HTTPServer2Port"),
+            vec!["HTTP", "Server2", "Port"]
+        );
+        assert_eq!(
+            split_words("some_snake_case"),
+            vec!["some", "snake", "case"]
+        );
+        assert_eq!(split_words("camelCase"), vec!["camel", "Case"]);
+    }
+
+    #[test]
+    fn test_naming_convention() {
+        test_lint(
+            NamingConventionLint::new(NamingConventionConfig {
+                locals: Some("camelCase".to_owned()),
+                functions: Some("camelCase".to_owned()),
+                parameters: Some("camelCase".to_owned()),
+                globals: Some("PascalCase".to_owned()),
+                methods: Some("PascalCase".to_owned()),
+                constants: Some("SCREAMING_SNAKE_CASE".to_owned()),
+                ..NamingConventionConfig::default()
+            })
+            .unwrap(),
+            "naming_convention",
+            "naming_convention",
+        );
+    }
+
+    #[test]
+    fn test_patterns() {
+        test_lint(
+            NamingConventionLint::new(NamingConventionConfig {
+                locals: Some("^[a-z]+$".to_owned()),
+                parameters: Some("snake_case".to_owned()),
+                ignore_pattern: "^(_|i|j)$".to_owned(),
+                ..NamingConventionConfig::default()
+            })
+            .unwrap(),
+            "naming_convention",
+            "patterns",
+        );
+    }
+}
@@ -0,0 +1,331 @@
+use super::{roblox_incorrect_roact_usage::is_roact_or_react_create_element, *};
+use crate::{
+    ast_util::{
+        range, roblox_instances::InstanceClasses, scopes::ScopeManager, strip_parentheses,
+        ConstantArgument,
+    },
+    standard_library::{FieldKind, RobloxClass, RobloxProperty, RobloxValueType, StandardLibrary},
+};
+use std::{collections::BTreeMap, convert::Infallible};
+
+use full_moon::{
+    ast::{self, Ast},
+    node::Node,
+    visitors::Visitor,
+};
+use if_chain::if_chain;
+
+pub struct IncorrectPropertyAssignmentLint;
+
+impl Lint for IncorrectPropertyAssignmentLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Error;
+    const LINT_TYPE: LintType = LintType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(IncorrectPropertyAssignmentLint)
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        if !context.is_roblox() {
+            return Vec::new();
+        }
+
+        let roblox_classes = &context.standard_library.roblox_classes;
+
+        // Old roblox standard library
+        if roblox_classes.is_empty() {
+            return Vec::new();
+        }
+
+        let mut visitor = IncorrectPropertyAssignmentVisitor {
+            diagnostics: Vec::new(),
+            instance_classes: InstanceClasses::new(
+                &ast_context.scope_manager,
+                &context.standard_library,
+            ),
+
+            roblox_classes,
+            scope_manager: &ast_context.scope_manager,
+            standard_library: &context.standard_library,
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+// What is known about a value being assigned to a property
+enum AssignedValue {
+    Constant(ConstantArgument),
+    DataType(String),
+    Enum(String),
+    Class(String),
+}
+
+impl AssignedValue {
+    fn type_name(&self) -> String {
+        match self {
+            AssignedValue::Constant(constant) => constant.type_name().to_owned(),
+            AssignedValue::DataType(name) | AssignedValue::Class(name) => name.to_owned(),
+            AssignedValue::Enum(name) => format!("Enum.{name}"),
+        }
+    }
+}
+
+struct IncorrectPropertyAssignmentVisitor<'a> {
+    diagnostics: Vec<Diagnostic>,
+    instance_classes: InstanceClasses<'a>,
+
+    roblox_classes: &'a BTreeMap<String, RobloxClass>,
+    scope_manager: &'a ScopeManager,
+    standard_library: &'a StandardLibrary,
+}
+
+impl<'a> IncorrectPropertyAssignmentVisitor<'a> {
+    fn assigned_value(&self, expression: &ast::Expression) -> Option<AssignedValue> {
+        if let Some(constant) = ConstantArgument::from_expression(expression) {
+            return Some(AssignedValue::Constant(constant));
+        }
+
+        if let Some(class_name) = self.instance_classes.created_class(expression) {
+            return Some(AssignedValue::Class(class_name));
+        }
+
+        match strip_parentheses(expression) {
+            // Vector3.new(...), Color3.fromRGB(...)
+            ast::Expression::FunctionCall(call) => {
+                let ast::Prefix::Name(data_type) = call.prefix() else {
+                    return None;
+                };
+
+                let suffixes = call.suffixes().collect::<Vec<_>>();
+
+                if_chain! {
+                    if let [ast::Suffix::Index(ast::Index::Dot { name: constructor, .. }), ast::Suffix::Call(_)] = suffixes.as_slice();
+                    let data_type = data_type.token().to_string();
+                    let constructor = constructor.token().to_string();
+                    if constructor == "new" || constructor.starts_with("from") || constructor.starts_with("From");
+                    if self.scope_manager.reference_at_byte(call.prefix().start_position()?.bytes())?.resolved.is_none();
+                    if matches!(
+                        self.standard_library.find_global(&[data_type.as_str(), constructor.as_str()]),
+                        Some(field) if matches!(field.field_kind, FieldKind::Function(_))
+                    );
+                    then {
+                        Some(AssignedValue::DataType(data_type))
+                    } else {
+                        None
+                    }
+                }
+            }
+
+            // Enum.Material.Plastic
+            ast::Expression::Var(ast::Var::Expression(var_expression)) => {
+                let ast::Prefix::Name(prefix) = var_expression.prefix() else {
+                    return None;
+                };
+
+                let suffixes = var_expression.suffixes().collect::<Vec<_>>();
+
+                if_chain! {
+                    if prefix.token().to_string() == "Enum";
+                    if let [ast::Suffix::Index(ast::Index::Dot { name: enum_name, .. }), ast::Suffix::Index(ast::Index::Dot { .. })] = suffixes.as_slice();
+                    then {
+                        Some(AssignedValue::Enum(enum_name.token().to_string()))
+                    } else {
+                        None
+                    }
+                }
+            }
+
+            ast::Expression::Var(ast::Var::Name(name)) => self
+                .instance_classes
+                .class_of_name(name)
+                .map(AssignedValue::Class),
+
+            _ => None,
+        }
+    }
+
+    fn accepts(&self, property_type: &RobloxValueType, value: &AssignedValue) -> bool {
+        match (property_type, value) {
+            (RobloxValueType::Class(_), AssignedValue::Constant(ConstantArgument::Nil)) => true,
+
+            (RobloxValueType::Bool, AssignedValue::Constant(ConstantArgument::Bool)) => true,
+
+            (RobloxValueType::Number, AssignedValue::Constant(constant)) => {
+                constant.as_number().is_some()
+            }
+
+            // Numbers are converted to strings when assigned
+            (
+                RobloxValueType::String,
+                AssignedValue::Constant(ConstantArgument::Number(_) | ConstantArgument::String(_)),
+            ) => true,
+
+            (
+                RobloxValueType::DataType(data_type),
+                AssignedValue::Constant(ConstantArgument::String(_)),
+            ) => {
+                matches!(
+                    data_type.as_str(),
+                    "Content" | "ContentId" | "ProtectedString"
+                )
+            }
+
+            // Enums can be assigned their names and values
+            (
+                RobloxValueType::Enum(_),
+                AssignedValue::Constant(ConstantArgument::Number(_) | ConstantArgument::String(_)),
+            ) => true,
+
+            (RobloxValueType::DataType(expected), AssignedValue::DataType(received))
+            | (RobloxValueType::Enum(expected), AssignedValue::Enum(received)) => {
+                expected == received
+            }
+
+            (RobloxValueType::Class(expected), AssignedValue::Class(received)) => {
+                expected == received
+                    || self
+                        .roblox_classes
+                        .get(received)
+                        .is_some_and(|class| class.is_subclass_of(self.roblox_classes, expected))
+            }
+
+            _ => false,
+        }
+    }
+
+    fn check_assignment(
+        &mut self,
+        class_name: &str,
+        property_name: &str,
+        property_range: (usize, usize),
+        value: &ast::Expression,
+    ) {
+        let Some(property) = self
+            .roblox_classes
+            .get(class_name)
+            .and_then(|class| class.property(self.roblox_classes, property_name))
+        else {
+            return;
+        };
+
+        let RobloxProperty {
+            value_type,
+            read_only,
+            ..
+        } = property;
+
+        if *read_only {
+            self.diagnostics.push(Diagnostic::new(
+                "roblox_incorrect_property_assignment",
+                format!("`{class_name}.{property_name}` is read-only"),
+                Label::new(property_range),
+            ));
+
+            return;
+        }
+
+        let Some(assigned_value) = self.assigned_value(value) else {
+            return;
+        };
+
+        if !self.accepts(value_type, &assigned_value) {
+            self.diagnostics.push(Diagnostic::new(
+                "roblox_incorrect_property_assignment",
+                format!("incorrect value assigned to `{class_name}.{property_name}`"),
+                Label::new_with_message(
+                    range::<_, usize>(value),
+                    format!(
+                        "expected `{value_type}`, received `{}`",
+                        assigned_value.type_name()
+                    ),
+                ),
+            ));
+        }
+    }
+}
+
+impl Visitor for IncorrectPropertyAssignmentVisitor<'_> {
+    fn visit_local_assignment(&mut self, node: &ast::LocalAssignment) {
+        self.instance_classes.record_local_assignment(node);
+    }
+
+    fn visit_assignment(&mut self, node: &ast::Assignment) {
+        for (var, value) in node.variables().iter().zip(node.expressions().iter()) {
+            if_chain! {
+                if let ast::Var::Expression(var_expression) = var;
+                if let ast::Prefix::Name(instance_name) = var_expression.prefix();
+                let suffixes = var_expression.suffixes().collect::<Vec<_>>();
+                if let [ast::Suffix::Index(ast::Index::Dot { name: property_name, .. })] = suffixes.as_slice();
+                if let Some(class_name) = self.instance_classes.class_of_name(instance_name);
+                then {
+                    self.check_assignment(
+                        &class_name,
+                        &property_name.token().to_string(),
+                        range(var),
+                        value,
+                    );
+                }
+            }
+        }
+    }
+
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        let mut suffixes = call.suffixes().collect::<Vec<_>>();
+        let call_suffix = suffixes.pop();
+
+        if is_roact_or_react_create_element(call.prefix(), &suffixes).is_none() {
+            return;
+        }
+
+        if_chain! {
+            if let Some(ast::Suffix::Call(ast::Call::AnonymousCall(
+                ast::FunctionArgs::Parentheses { arguments, .. }
+            ))) = call_suffix;
+            let mut arguments = arguments.iter();
+            if let Some(ast::Expression::String(class_token)) = arguments.next();
+            if let Some(class_name) = self.instance_classes.string_class_name(class_token);
+            if let Some(ast::Expression::TableConstructor(props)) = arguments.next();
+            then {
+                for field in props.fields() {
+                    if let ast::Field::NameKey { key, value, .. } = field {
+                        self.check_assignment(
+                            &class_name,
+                            &key.token().to_string(),
+                            range(key),
+                            value,
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_roblox_incorrect_property_assignment() {
+        test_lint(
+            IncorrectPropertyAssignmentLint::new(()).unwrap(),
+            "roblox_incorrect_property_assignment",
+            "roblox_incorrect_property_assignment",
+        );
+    }
+
+    #[test]
+    fn test_roact_props() {
+        test_lint(
+            IncorrectPropertyAssignmentLint::new(()).unwrap(),
+            "roblox_incorrect_property_assignment",
+            "roact_props",
+        );
+    }
+}
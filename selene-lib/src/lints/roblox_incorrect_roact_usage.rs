@@ -136,7 +136,7 @@ impl Lint for IncorrectRoactUsageLint {
     }
 }
 
-fn is_roact_or_react_create_element(
+pub(crate) fn is_roact_or_react_create_element(
     prefix: &ast::Prefix,
     suffixes: &[&ast::Suffix],
 ) -> Option<LibraryName> {
@@ -159,7 +159,7 @@ fn is_roact_or_react_create_element(
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
-enum LibraryName {
+pub(crate) enum LibraryName {
     Roact,
     React,
 }
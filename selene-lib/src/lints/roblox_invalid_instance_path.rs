@@ -0,0 +1,520 @@
+use super::*;
+use crate::{
+    ast_util::{
+        range,
+        scopes::{ReferenceWrite, ScopeManager},
+        strip_parentheses,
+    },
+    rojo::{RojoError, RojoProject},
+    standard_library::{RobloxClass, StandardLibrary},
+    text::closest_match,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::Infallible,
+    path::Path,
+};
+
+use full_moon::{
+    ast::{self, Ast},
+    tokenizer::{TokenReference, TokenType},
+    visitors::Visitor,
+};
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_PROJECT: &str = "default.project.json";
+
+#[derive(Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct InvalidInstancePathConfig {
+    project: Option<String>,
+}
+
+pub struct InvalidInstancePathLint {
+    config: InvalidInstancePathConfig,
+    // Building the tree walks the whole project, so it is only done once a file is checked
+    // with the Roblox standard library
+    project: OnceCell<Result<Option<RojoProject>, RojoError>>,
+}
+
+impl InvalidInstancePathLint {
+    fn project(&self, context: &Context) -> &Result<Option<RojoProject>, RojoError> {
+        self.project.get_or_init(|| {
+            let directory = context.config_directory.as_deref().unwrap_or(Path::new(""));
+
+            match &self.config.project {
+                Some(project) => RojoProject::from_file(&directory.join(project)).map(Some),
+
+                // Without a configured project, only use the default one if it is a Rojo project.
+                // Other tools can have a default.project.json too, so problems with it are ignored.
+                None => Ok(RojoProject::from_file(&directory.join(DEFAULT_PROJECT)).ok()),
+            }
+        })
+    }
+}
+
+impl Lint for InvalidInstancePathLint {
+    type Config = InvalidInstancePathConfig;
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const LINT_TYPE: LintType = LintType::Correctness;
+
+    fn new(config: Self::Config) -> Result<Self, Self::Error> {
+        Ok(InvalidInstancePathLint {
+            config,
+            project: OnceCell::new(),
+        })
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        if !context.is_roblox() {
+            return Vec::new();
+        }
+
+        let project = match self.project(context) {
+            Ok(Some(project)) => project,
+            Ok(None) => return Vec::new(),
+
+            Err(error) => {
+                return vec![Diagnostic::new(
+                    "roblox_invalid_instance_path",
+                    format!(
+                        "couldn't load the Rojo project, so instance paths aren't checked: {error}"
+                    ),
+                    Label::new((0, 0)),
+                )];
+            }
+        };
+
+        let mut visitor = InvalidInstancePathVisitor {
+            diagnostics: Vec::new(),
+            local_instances: HashMap::new(),
+            project,
+            roblox_classes: &context.standard_library.roblox_classes,
+            scope_manager: &ast_context.scope_manager,
+            script_path: ast_context
+                .file_path
+                .as_deref()
+                .and_then(|file_path| project.script_instance_path(file_path))
+                .map(ToOwned::to_owned),
+            standard_library: &context.standard_library,
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+enum Resolved {
+    // The names of the instances leading to it from the root of the project
+    Instance(Vec<String>),
+    // A child that doesn't exist on an instance whose children are all known
+    Missing {
+        parent: Vec<String>,
+        name: String,
+        range: (usize, usize),
+    },
+    Unknown,
+}
+
+fn string_literal(expression: &ast::Expression) -> Option<(String, (usize, usize))> {
+    let ast::Expression::String(token) = strip_parentheses(expression) else {
+        return None;
+    };
+
+    match token.token_type() {
+        TokenType::StringLiteral { literal, .. } => Some((literal.to_string(), range(token))),
+        _ => None,
+    }
+}
+
+fn single_argument(call: &ast::Call) -> Option<&ast::Expression> {
+    let arguments = match call {
+        ast::Call::AnonymousCall(arguments) => arguments,
+        ast::Call::MethodCall(method_call) => method_call.args(),
+        _ => return None,
+    };
+
+    match arguments {
+        ast::FunctionArgs::Parentheses { arguments, .. } if arguments.len() == 1 => {
+            arguments.iter().next()
+        }
+
+        _ => None,
+    }
+}
+
+struct InvalidInstancePathVisitor<'a> {
+    diagnostics: Vec<Diagnostic>,
+    // Instances of locals defined by `local Shared = ReplicatedStorage.Shared`, keyed by the range of their identifier
+    local_instances: HashMap<(usize, usize), Vec<String>>,
+    project: &'a RojoProject,
+    roblox_classes: &'a BTreeMap<String, RobloxClass>,
+    scope_manager: &'a ScopeManager,
+    // The instance of the file being linted, if it is part of the project
+    script_path: Option<Vec<String>>,
+    standard_library: &'a StandardLibrary,
+}
+
+impl InvalidInstancePathVisitor<'_> {
+    fn display_path(&self, path: &[String]) -> String {
+        if self.project.is_place() {
+            if path.is_empty() {
+                "game".to_owned()
+            } else {
+                path.join(".")
+            }
+        } else {
+            std::iter::once(self.project.name.as_str())
+                .chain(path.iter().map(String::as_str))
+                .collect::<Vec<_>>()
+                .join(".")
+        }
+    }
+
+    // Whether the class has a property, event, or method with the name, which would be indexed instead of a child
+    fn has_member(&self, class_name: &str, name: &str) -> bool {
+        let mut class_name = class_name;
+
+        while let Some(class) = self.roblox_classes.get(class_name) {
+            if class.properties.iter().any(|property| property == name)
+                || class.events.iter().any(|event| event == name)
+                || class.security.contains_key(name)
+                || self
+                    .standard_library
+                    .structs
+                    .get(class_name)
+                    .is_some_and(|strukt| strukt.contains_key(name))
+            {
+                return true;
+            }
+
+            class_name = &class.superclass;
+        }
+
+        false
+    }
+
+    fn local_instance(&self, name: &TokenReference) -> Option<Vec<String>> {
+        let reference = self
+            .scope_manager
+            .reference_at_byte(name.token().start_position().bytes())?;
+
+        let Some(variable_id) = reference.resolved else {
+            return match name.token().to_string().as_str() {
+                "script" => self.script_path.clone(),
+                "game" if self.project.is_place() => Some(Vec::new()),
+                "workspace" if self.project.is_place() => Some(vec!["Workspace".to_owned()]),
+                _ => None,
+            };
+        };
+
+        let variable = self.scope_manager.variables.get(variable_id)?;
+        let instance_path = self.local_instances.get(variable.identifiers.first()?)?;
+
+        let assignments = variable
+            .references
+            .iter()
+            .filter(|reference_id| {
+                let reference = &self.scope_manager.references[**reference_id];
+                reference.write == Some(ReferenceWrite::Assign) && reference.indexing.is_none()
+            })
+            .count();
+
+        (assignments == 1).then(|| instance_path.to_owned())
+    }
+
+    fn resolve_expression(&self, expression: &ast::Expression) -> Resolved {
+        match strip_parentheses(expression) {
+            ast::Expression::Var(ast::Var::Name(name)) => match self.local_instance(name) {
+                Some(path) => Resolved::Instance(path),
+                None => Resolved::Unknown,
+            },
+
+            ast::Expression::Var(ast::Var::Expression(var_expression)) => {
+                self.resolve(var_expression.prefix(), var_expression.suffixes())
+            }
+
+            ast::Expression::FunctionCall(call) => self.resolve(call.prefix(), call.suffixes()),
+
+            _ => Resolved::Unknown,
+        }
+    }
+
+    // Follows a chain such as `script.Parent.Modules:WaitForChild("Util")` through the project
+    fn resolve<'b>(
+        &self,
+        prefix: &ast::Prefix,
+        suffixes: impl Iterator<Item = &'b ast::Suffix>,
+    ) -> Resolved {
+        let path = match prefix {
+            ast::Prefix::Name(name) => self.local_instance(name),
+            ast::Prefix::Expression(expression) => match self.resolve_expression(expression) {
+                Resolved::Instance(path) => Some(path),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        let Some(mut path) = path else {
+            return Resolved::Unknown;
+        };
+
+        for suffix in suffixes {
+            let Some(instance) = self.project.instance(&path) else {
+                return Resolved::Unknown;
+            };
+
+            let missing = |path: &[String], name: String, range| Resolved::Missing {
+                parent: path.to_owned(),
+                name,
+                range,
+            };
+
+            match suffix {
+                ast::Suffix::Index(ast::Index::Dot {
+                    name: name_token, ..
+                }) => {
+                    let name = name_token.token().to_string();
+
+                    if instance.children.contains_key(&name) {
+                        path.push(name);
+                    } else if name == "Parent" && !path.is_empty() {
+                        path.pop();
+                    } else if instance.complete
+                        && instance.class_name.as_deref().is_some_and(|class_name| {
+                            self.roblox_classes.contains_key(class_name)
+                                && !self.has_member(class_name, &name)
+                        })
+                    {
+                        return missing(&path, name, range(name_token));
+                    } else {
+                        return Resolved::Unknown;
+                    }
+                }
+
+                ast::Suffix::Index(ast::Index::Brackets { expression, .. }) => {
+                    let Some((name, name_range)) = string_literal(expression) else {
+                        return Resolved::Unknown;
+                    };
+
+                    if instance.children.contains_key(&name) {
+                        path.push(name);
+                    } else if instance.complete {
+                        return missing(&path, name, name_range);
+                    } else {
+                        return Resolved::Unknown;
+                    }
+                }
+
+                ast::Suffix::Call(call @ ast::Call::MethodCall(method_call)) => {
+                    let Some((name, _)) = single_argument(call).and_then(string_literal) else {
+                        return Resolved::Unknown;
+                    };
+
+                    // Missing children aren't reported, since FindFirstChild is used to check if
+                    // something exists, and WaitForChild to wait for children created at runtime
+                    match method_call.name().token().to_string().as_str() {
+                        "WaitForChild" | "FindFirstChild"
+                            if instance.children.contains_key(&name) =>
+                        {
+                            path.push(name);
+                        }
+
+                        "GetService"
+                            if path.is_empty() && instance.children.contains_key(&name) =>
+                        {
+                            path.push(name);
+                        }
+
+                        _ => return Resolved::Unknown,
+                    }
+                }
+
+                _ => return Resolved::Unknown,
+            }
+        }
+
+        Resolved::Instance(path)
+    }
+
+    fn lint_missing(&mut self, resolved: Resolved) {
+        let Resolved::Missing {
+            parent,
+            name,
+            range,
+        } = resolved
+        else {
+            return;
+        };
+
+        let children = self
+            .project
+            .instance(&parent)
+            .map(|instance| {
+                instance
+                    .children
+                    .keys()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        self.diagnostics.push(Diagnostic::new_complete(
+            "roblox_invalid_instance_path",
+            format!(
+                "`{}` does not have a child named `{name}`",
+                self.display_path(&parent)
+            ),
+            Label::new(range),
+            closest_match(&name, children)
+                .map(|suggestion| format!("did you mean `{suggestion}`?"))
+                .into_iter()
+                .collect(),
+            Vec::new(),
+        ));
+    }
+
+    fn lint_require(&mut self, call: &ast::FunctionCall) {
+        let ast::Prefix::Name(name) = call.prefix() else {
+            return;
+        };
+
+        if name.token().to_string() != "require"
+            || self
+                .scope_manager
+                .reference_at_byte(name.token().start_position().bytes())
+                .is_some_and(|reference| reference.resolved.is_some())
+        {
+            return;
+        }
+
+        let suffixes = call.suffixes().collect::<Vec<_>>();
+
+        let [ast::Suffix::Call(call_suffix)] = suffixes.as_slice() else {
+            return;
+        };
+
+        let Some(argument) = single_argument(call_suffix) else {
+            return;
+        };
+
+        let Resolved::Instance(path) = self.resolve_expression(argument) else {
+            return;
+        };
+
+        let Some(class_name) = self
+            .project
+            .instance(&path)
+            .and_then(|instance| instance.class_name.as_deref())
+        else {
+            return;
+        };
+
+        if class_name == "ModuleScript" {
+            return;
+        }
+
+        self.diagnostics.push(Diagnostic::new(
+            "roblox_invalid_instance_path",
+            format!(
+                "`require` expects a ModuleScript, but `{}` is a {class_name}",
+                self.display_path(&path)
+            ),
+            Label::new(range::<_, usize>(argument)),
+        ));
+    }
+}
+
+impl Visitor for InvalidInstancePathVisitor<'_> {
+    fn visit_local_assignment(&mut self, node: &ast::LocalAssignment) {
+        for (name, expression) in node.names().iter().zip(node.expressions().iter()) {
+            if let Resolved::Instance(path) = self.resolve_expression(expression) {
+                self.local_instances.insert(range(name), path);
+            }
+        }
+    }
+
+    fn visit_var_expression(&mut self, var_expression: &ast::VarExpression) {
+        let resolved = self.resolve(var_expression.prefix(), var_expression.suffixes());
+        self.lint_missing(resolved);
+    }
+
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        let resolved = self.resolve(call.prefix(), call.suffixes());
+        self.lint_missing(resolved);
+        self.lint_require(call);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    fn project_lint(project: &str) -> InvalidInstancePathLint {
+        InvalidInstancePathLint::new(InvalidInstancePathConfig {
+            project: Some(
+                Path::new(env!("CARGO_MANIFEST_DIR"))
+                    .join("tests/lints/roblox_invalid_instance_path")
+                    .join(project)
+                    .to_string_lossy()
+                    .into_owned(),
+            ),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_roblox_invalid_instance_path() {
+        test_lint(
+            project_lint("default.project.json"),
+            "roblox_invalid_instance_path",
+            "roblox_invalid_instance_path",
+        );
+    }
+
+    #[test]
+    fn test_requires() {
+        test_lint(
+            project_lint("default.project.json"),
+            "roblox_invalid_instance_path",
+            "requires",
+        );
+    }
+
+    #[test]
+    fn test_library() {
+        test_lint(
+            project_lint("library.project.json"),
+            "roblox_invalid_instance_path",
+            "library",
+        );
+    }
+
+    #[test]
+    fn test_invalid_project() {
+        // Relative, so that the path in the error is the same on every machine
+        test_lint(
+            InvalidInstancePathLint::new(InvalidInstancePathConfig {
+                project: Some(
+                    "tests/lints/roblox_invalid_instance_path/invalid.project.json".to_owned(),
+                ),
+            })
+            .unwrap(),
+            "roblox_invalid_instance_path",
+            "invalid_project",
+        );
+    }
+
+    #[test]
+    fn test_not_in_project() {
+        test_lint(
+            project_lint("default.project.json"),
+            "roblox_invalid_instance_path",
+            "not_in_project",
+        );
+    }
+}
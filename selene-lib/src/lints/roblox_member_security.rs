@@ -0,0 +1,314 @@
+use super::*;
+use crate::{
+    ast_util::{range, roblox_instances::InstanceClasses, strip_parentheses},
+    standard_library::{RobloxClass, RobloxValueType},
+};
+use std::{
+    collections::{BTreeMap, HashSet},
+    path::{Component, Path},
+};
+
+use full_moon::{
+    ast::{self, Ast},
+    tokenizer::TokenReference,
+    visitors::Visitor,
+};
+use globset::{Glob, GlobMatcher};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ScriptContext {
+    #[default]
+    Game,
+    Plugin,
+    CommandBar,
+}
+
+impl ScriptContext {
+    fn allows(self, security: &str) -> bool {
+        match security {
+            "None" => true,
+            "PluginSecurity" => self >= ScriptContext::Plugin,
+            "LocalUserSecurity" => self >= ScriptContext::CommandBar,
+            _ => false,
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            ScriptContext::Game => "game scripts",
+            ScriptContext::Plugin => "plugins",
+            ScriptContext::CommandBar => "the command bar",
+        }
+    }
+}
+
+#[derive(Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct MemberSecurityConfig {
+    default_context: ScriptContext,
+    contexts: BTreeMap<String, ScriptContext>,
+}
+
+pub struct MemberSecurityLint {
+    default_context: ScriptContext,
+    contexts: Vec<(GlobMatcher, ScriptContext)>,
+}
+
+impl Lint for MemberSecurityLint {
+    type Config = MemberSecurityConfig;
+    type Error = globset::Error;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const LINT_TYPE: LintType = LintType::Correctness;
+
+    fn new(config: Self::Config) -> Result<Self, Self::Error> {
+        Ok(MemberSecurityLint {
+            default_context: config.default_context,
+            contexts: config
+                .contexts
+                .into_iter()
+                .map(|(glob, context)| Ok((Glob::new(&glob)?.compile_matcher(), context)))
+                .collect::<Result<_, globset::Error>>()?,
+        })
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        if !context.is_roblox() {
+            return Vec::new();
+        }
+
+        let roblox_classes = &context.standard_library.roblox_classes;
+
+        // Old roblox standard library
+        if roblox_classes.is_empty() {
+            return Vec::new();
+        }
+
+        let mut visitor = MemberSecurityVisitor {
+            assignment_targets: HashSet::new(),
+            diagnostics: Vec::new(),
+            instance_classes: InstanceClasses::new(
+                &ast_context.scope_manager,
+                &context.standard_library,
+            ),
+            roblox_classes,
+            script_context: match &ast_context.file_path {
+                Some(file_path) => self.script_context(file_path),
+                None => self.default_context,
+            },
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+impl MemberSecurityLint {
+    // If several globs match, the most permissive context is used
+    fn script_context(&self, file_path: &Path) -> ScriptContext {
+        // Paths given as `./src/plugin.lua` should match `src/*.lua`
+        let file_path = file_path
+            .components()
+            .filter(|component| !matches!(component, Component::CurDir))
+            .collect::<std::path::PathBuf>();
+
+        self.contexts
+            .iter()
+            .filter(|(matcher, _)| matcher.is_match(&file_path))
+            .map(|(_, context)| *context)
+            .max()
+            .unwrap_or(self.default_context)
+    }
+}
+
+struct MemberSecurityVisitor<'a> {
+    // Vars being assigned to, which need write security rather than read security
+    assignment_targets: HashSet<(usize, usize)>,
+    diagnostics: Vec<Diagnostic>,
+    instance_classes: InstanceClasses<'a>,
+    roblox_classes: &'a BTreeMap<String, RobloxClass>,
+    script_context: ScriptContext,
+}
+
+impl MemberSecurityVisitor<'_> {
+    fn check_member(&mut self, class_name: &str, member: &TokenReference, write: bool) {
+        let Some(class) = self.roblox_classes.get(class_name) else {
+            return;
+        };
+
+        let member_name = member.token().to_string();
+
+        let security = if write {
+            class
+                .property(self.roblox_classes, &member_name)
+                .and_then(|property| property.security.as_deref())
+        } else {
+            class.member_security(self.roblox_classes, &member_name)
+        };
+
+        let Some(security) = security else {
+            return;
+        };
+
+        if self.script_context.allows(security) {
+            return;
+        }
+
+        let mut notes = Vec::new();
+        if self.script_context == ScriptContext::Game && security == "PluginSecurity" {
+            notes.push(
+                "if this file is part of a plugin, set its script context in the config for `roblox_member_security`"
+                    .to_owned(),
+            );
+        }
+
+        self.diagnostics.push(Diagnostic::new_complete(
+            "roblox_member_security",
+            format!(
+                "`{class_name}.{member_name}` requires `{security}`, which is not available to {}",
+                self.script_context.description()
+            ),
+            Label::new(range::<_, usize>(member)),
+            notes,
+            Vec::new(),
+        ));
+    }
+
+    // Follows the chain of indexes and calls, as in `game:GetService("Players").LocalPlayer:Kick()`,
+    // checking each member for as long as the class is known.
+    fn check_suffixes<'b>(
+        &mut self,
+        prefix: &ast::Prefix,
+        suffixes: impl Iterator<Item = &'b ast::Suffix>,
+        write: bool,
+    ) {
+        let mut class_name = match prefix {
+            ast::Prefix::Name(name) => self.instance_classes.class_of_name(name),
+            ast::Prefix::Expression(expression) => self
+                .instance_classes
+                .created_class(strip_parentheses(expression)),
+            _ => None,
+        };
+
+        let suffixes = suffixes.collect::<Vec<_>>();
+        let last_index = suffixes.len().saturating_sub(1);
+
+        for (index, suffix) in suffixes.into_iter().enumerate() {
+            let Some(current_class) = class_name.take() else {
+                return;
+            };
+
+            match suffix {
+                ast::Suffix::Index(ast::Index::Dot { name, .. }) => {
+                    self.check_member(&current_class, name, write && index == last_index);
+
+                    class_name = self
+                        .roblox_classes
+                        .get(&current_class)
+                        .and_then(|class| {
+                            class.property(self.roblox_classes, &name.token().to_string())
+                        })
+                        .and_then(|property| match &property.value_type {
+                            RobloxValueType::Class(property_class) => Some(property_class.clone()),
+                            _ => None,
+                        });
+                }
+
+                ast::Suffix::Call(call @ ast::Call::MethodCall(method_call)) => {
+                    self.check_member(&current_class, method_call.name(), false);
+
+                    if method_call.name().token().to_string() == "GetService" {
+                        class_name = self.instance_classes.single_string_argument(call);
+                    }
+                }
+
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Visitor for MemberSecurityVisitor<'_> {
+    fn visit_local_assignment(&mut self, node: &ast::LocalAssignment) {
+        self.instance_classes.record_local_assignment(node);
+    }
+
+    fn visit_assignment(&mut self, node: &ast::Assignment) {
+        for var in node.variables() {
+            if let ast::Var::Expression(var_expression) = var {
+                self.assignment_targets.insert(range(var_expression));
+                self.check_suffixes(var_expression.prefix(), var_expression.suffixes(), true);
+            }
+        }
+    }
+
+    fn visit_var_expression(&mut self, var_expression: &ast::VarExpression) {
+        if self.assignment_targets.contains(&range(var_expression)) {
+            return;
+        }
+
+        self.check_suffixes(var_expression.prefix(), var_expression.suffixes(), false);
+    }
+
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        self.check_suffixes(call.prefix(), call.suffixes(), false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_roblox_member_security() {
+        test_lint(
+            MemberSecurityLint::new(MemberSecurityConfig::default()).unwrap(),
+            "roblox_member_security",
+            "roblox_member_security",
+        );
+    }
+
+    #[test]
+    fn test_script_contexts() {
+        test_lint(
+            MemberSecurityLint::new(MemberSecurityConfig {
+                default_context: ScriptContext::Game,
+                contexts: BTreeMap::from([
+                    ("script_*.lua".to_owned(), ScriptContext::Plugin),
+                    ("*contexts.lua".to_owned(), ScriptContext::CommandBar),
+                ]),
+            })
+            .unwrap(),
+            "roblox_member_security",
+            "script_contexts",
+        );
+    }
+
+    #[test]
+    fn test_script_context_for_path() {
+        let lint = MemberSecurityLint::new(MemberSecurityConfig {
+            default_context: ScriptContext::Game,
+            contexts: BTreeMap::from([
+                ("plugin/**".to_owned(), ScriptContext::Plugin),
+                ("plugin/debug/**".to_owned(), ScriptContext::CommandBar),
+            ]),
+        })
+        .unwrap();
+
+        assert_eq!(
+            lint.script_context(Path::new("src/init.lua")),
+            ScriptContext::Game
+        );
+        assert_eq!(
+            lint.script_context(Path::new("./plugin/init.lua")),
+            ScriptContext::Plugin
+        );
+        assert_eq!(
+            lint.script_context(Path::new("plugin/debug/run.lua")),
+            ScriptContext::CommandBar
+        );
+    }
+}
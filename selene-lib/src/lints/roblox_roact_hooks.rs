@@ -0,0 +1,578 @@
+use super::*;
+use crate::{
+    ast_util::{
+        range,
+        scopes::{ScopeManager, VariableKind},
+        strip_parentheses,
+    },
+    text::{english_list, plural},
+};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::Infallible,
+};
+
+use full_moon::{
+    ast::{self, Ast},
+    visitors::Visitor,
+};
+
+pub struct RoactHooksLint;
+
+impl Lint for RoactHooksLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const LINT_TYPE: LintType = LintType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(RoactHooksLint)
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        if !context.is_roblox() {
+            return Vec::new();
+        }
+
+        let mut visitor = RoactHooksVisitor {
+            conditional_ranges: Vec::new(),
+            diagnostics: Vec::new(),
+            frames: Vec::new(),
+            function_kinds: HashMap::new(),
+            scope_manager: &ast_context.scope_manager,
+            stable_variables: HashSet::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+// `useState` and `useCustomHook`, but not `user` or `used`
+fn is_hook_name(name: &str) -> bool {
+    name.strip_prefix("use")
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(|c: char| c.is_ascii_uppercase()))
+}
+
+// Returns the name of the hook being called, such as `React.useState` or `useCustomHook`
+fn hook_name(call: &ast::FunctionCall) -> Option<String> {
+    let ast::Prefix::Name(prefix) = call.prefix() else {
+        return None;
+    };
+
+    let prefix = prefix.token().to_string();
+    let suffixes = call.suffixes().collect::<Vec<_>>();
+
+    match suffixes.as_slice() {
+        [ast::Suffix::Call(ast::Call::AnonymousCall(_))] if is_hook_name(&prefix) => Some(prefix),
+
+        [ast::Suffix::Index(ast::Index::Dot { name, .. }), ast::Suffix::Call(ast::Call::AnonymousCall(_))]
+            if matches!(prefix.as_str(), "React" | "Roact")
+                && is_hook_name(&name.token().to_string()) =>
+        {
+            Some(format!("{prefix}.{}", name.token()))
+        }
+
+        _ => None,
+    }
+}
+
+fn call_arguments(call: &ast::FunctionCall) -> Vec<&ast::Expression> {
+    match call.suffixes().last() {
+        Some(ast::Suffix::Call(ast::Call::AnonymousCall(ast::FunctionArgs::Parentheses {
+            arguments,
+            ..
+        }))) => arguments.iter().map(strip_parentheses).collect(),
+
+        _ => Vec::new(),
+    }
+}
+
+// `React.memo(function(props) ... end)` and `React.forwardRef(function(props, ref) ... end)`
+fn wrapped_component(call: &ast::FunctionCall) -> Option<&ast::FunctionBody> {
+    let ast::Prefix::Name(prefix) = call.prefix() else {
+        return None;
+    };
+
+    let suffixes = call.suffixes().collect::<Vec<_>>();
+
+    let [ast::Suffix::Index(ast::Index::Dot { name, .. }), ast::Suffix::Call(_)] =
+        suffixes.as_slice()
+    else {
+        return None;
+    };
+
+    if !matches!(prefix.token().to_string().as_str(), "React" | "Roact")
+        || !matches!(name.token().to_string().as_str(), "memo" | "forwardRef")
+    {
+        return None;
+    }
+
+    match call_arguments(call).first() {
+        Some(ast::Expression::Function(function)) => Some(&function.1),
+        _ => None,
+    }
+}
+
+// The indexes of the callback and dependency array for hooks that take them
+fn dependency_arguments(hook_name: &str) -> Option<(usize, usize)> {
+    match hook_name.rsplit('.').next()? {
+        "useCallback" | "useEffect" | "useInsertionEffect" | "useLayoutEffect" | "useMemo" => {
+            Some((0, 1))
+        }
+
+        "useImperativeHandle" => Some((1, 2)),
+
+        _ => None,
+    }
+}
+
+fn contains(outer: (usize, usize), inner: (usize, usize)) -> bool {
+    outer.0 <= inner.0 && inner.1 <= outer.1
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FunctionKind {
+    Component,
+    Hook,
+    Other,
+}
+
+impl FunctionKind {
+    fn from_name(name: &str) -> Self {
+        if is_hook_name(name) {
+            FunctionKind::Hook
+        } else if name.starts_with(|c: char| c.is_ascii_uppercase()) {
+            FunctionKind::Component
+        } else {
+            FunctionKind::Other
+        }
+    }
+}
+
+struct FunctionFrame {
+    kind: FunctionKind,
+    range: (usize, usize),
+
+    conditional_depth: usize,
+    loop_depth: usize,
+    after_early_return: bool,
+}
+
+struct RoactHooksVisitor<'a> {
+    // The right side of `and`/`or`, and the branches of if expressions
+    conditional_ranges: Vec<(usize, usize)>,
+    diagnostics: Vec<Diagnostic>,
+    frames: Vec<FunctionFrame>,
+    // Keyed by the range of the function body
+    function_kinds: HashMap<(usize, usize), FunctionKind>,
+    scope_manager: &'a ScopeManager,
+    // Identifiers of values that never change between renders, such as the setter from `useState`
+    stable_variables: HashSet<(usize, usize)>,
+}
+
+impl RoactHooksVisitor<'_> {
+    fn check_hook_call(&mut self, call: &ast::FunctionCall, hook_name: &str) {
+        let call_range = range(call);
+
+        let problem = match self.frames.last() {
+            Some(frame) if frame.kind != FunctionKind::Other => {
+                if frame.loop_depth > 0 {
+                    Some("inside a loop")
+                } else if frame.conditional_depth > 0
+                    || self.conditional_ranges.iter().any(|conditional_range| {
+                        contains(frame.range, *conditional_range)
+                            && contains(*conditional_range, call_range)
+                    })
+                {
+                    Some("conditionally")
+                } else if frame.after_early_return {
+                    Some("after an early return")
+                } else {
+                    None
+                }
+            }
+
+            Some(_)
+                if self
+                    .frames
+                    .iter()
+                    .any(|frame| frame.kind != FunctionKind::Other) =>
+            {
+                Some("inside a nested function")
+            }
+
+            _ => Some("outside of a component or custom hook"),
+        };
+
+        let Some(problem) = problem else {
+            self.check_dependencies(call, hook_name);
+            return;
+        };
+
+        let note = if problem == "outside of a component or custom hook" {
+            "components must start with an uppercase letter, and custom hooks with `use`"
+        } else {
+            "hooks must be called in the same order on every render"
+        };
+
+        self.diagnostics.push(Diagnostic::new_complete(
+            "roblox_roact_hooks",
+            format!("`{hook_name}` is called {problem}"),
+            Label::new(call_range),
+            vec![note.to_owned()],
+            Vec::new(),
+        ));
+    }
+
+    fn check_dependencies(&mut self, call: &ast::FunctionCall, hook_name: &str) {
+        let Some((callback_index, dependencies_index)) = dependency_arguments(hook_name) else {
+            return;
+        };
+
+        let arguments = call_arguments(call);
+
+        let Some(ast::Expression::Function(callback)) = arguments.get(callback_index) else {
+            return;
+        };
+
+        // Without a dependency array, the hook runs on every render
+        let Some(ast::Expression::TableConstructor(dependencies)) =
+            arguments.get(dependencies_index)
+        else {
+            return;
+        };
+
+        let Some(component_range) = self.frames.last().map(|frame| frame.range) else {
+            return;
+        };
+
+        let callback_range = range(&callback.1);
+
+        let listed_dependencies = dependencies
+            .fields()
+            .iter()
+            .filter_map(|field| match field {
+                ast::Field::NoKey(expression) => Some(expression),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        // `props.value` counts as a dependency on `props`
+        let listed_names = listed_dependencies
+            .iter()
+            .filter_map(|expression| match strip_parentheses(expression) {
+                ast::Expression::Var(ast::Var::Name(name)) => Some(name.token().to_string()),
+                ast::Expression::Var(ast::Var::Expression(var_expression)) => {
+                    match var_expression.prefix() {
+                        ast::Prefix::Name(name) => Some(name.token().to_string()),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+            .collect::<HashSet<_>>();
+
+        let mut references = self
+            .scope_manager
+            .references
+            .iter()
+            .map(|(_, reference)| reference)
+            .filter(|reference| reference.read && contains(callback_range, reference.identifier))
+            .collect::<Vec<_>>();
+
+        references.sort_by_key(|reference| reference.identifier);
+
+        let mut missing = Vec::new();
+
+        for reference in references {
+            let Some(variable) = reference
+                .resolved
+                .and_then(|variable_id| self.scope_manager.variables.get(variable_id))
+            else {
+                continue;
+            };
+
+            let Some(identifier) = variable.identifiers.first() else {
+                continue;
+            };
+
+            // Only values that can change between renders, which are the ones created by the component
+            if matches!(
+                variable.kind,
+                VariableKind::Global | VariableKind::GlobalFunction
+            ) || !contains(component_range, *identifier)
+                || contains(callback_range, *identifier)
+                || self.stable_variables.contains(identifier)
+                || listed_names.contains(&variable.name)
+                || missing.contains(&variable.name.as_str())
+            {
+                continue;
+            }
+
+            missing.push(variable.name.as_str());
+        }
+
+        if missing.is_empty() {
+            return;
+        }
+
+        let suggested_dependencies = listed_dependencies
+            .iter()
+            .map(|expression| expression.to_string().trim().to_owned())
+            .chain(missing.iter().map(ToString::to_string))
+            .collect::<Vec<_>>();
+
+        let missing_list = missing
+            .iter()
+            .map(|name| format!("`{name}`"))
+            .collect::<Vec<_>>();
+
+        self.diagnostics.push(Diagnostic::new_complete(
+            "roblox_roact_hooks",
+            format!(
+                "`{hook_name}` is missing the {} {}",
+                plural(missing.len(), "dependency", "dependencies"),
+                english_list(&missing_list.iter().map(String::as_str).collect::<Vec<_>>()),
+            ),
+            Label::new(range::<_, usize>(dependencies)),
+            vec![format!("try: {{ {} }}", suggested_dependencies.join(", "))],
+            Vec::new(),
+        ));
+    }
+
+    fn enter_block(&mut self, is_loop: bool) {
+        if let Some(frame) = self.frames.last_mut() {
+            if is_loop {
+                frame.loop_depth += 1;
+            } else {
+                frame.conditional_depth += 1;
+            }
+        }
+    }
+
+    fn exit_block(&mut self, is_loop: bool) {
+        if let Some(frame) = self.frames.last_mut() {
+            if is_loop {
+                frame.loop_depth -= 1;
+            } else {
+                frame.conditional_depth -= 1;
+            }
+        }
+    }
+}
+
+impl Visitor for RoactHooksVisitor<'_> {
+    fn visit_function_body(&mut self, body: &ast::FunctionBody) {
+        let body_range = range(body);
+
+        self.frames.push(FunctionFrame {
+            kind: self
+                .function_kinds
+                .get(&body_range)
+                .copied()
+                .unwrap_or(FunctionKind::Other),
+            range: body_range,
+
+            conditional_depth: 0,
+            loop_depth: 0,
+            after_early_return: false,
+        });
+    }
+
+    fn visit_function_body_end(&mut self, _: &ast::FunctionBody) {
+        self.frames.pop();
+    }
+
+    fn visit_local_function(&mut self, local_function: &ast::LocalFunction) {
+        self.function_kinds.insert(
+            range(local_function.body()),
+            FunctionKind::from_name(&local_function.name().token().to_string()),
+        );
+    }
+
+    fn visit_function_declaration(&mut self, declaration: &ast::FunctionDeclaration) {
+        let name = declaration.name();
+
+        if let Some(last_name) = name.method_name().or_else(|| name.names().iter().last()) {
+            self.function_kinds.insert(
+                range(declaration.body()),
+                FunctionKind::from_name(&last_name.token().to_string()),
+            );
+        }
+    }
+
+    fn visit_local_assignment(&mut self, local_assignment: &ast::LocalAssignment) {
+        let names = local_assignment.names().iter().collect::<Vec<_>>();
+        let expressions = local_assignment.expressions();
+
+        for (name, expression) in names.iter().zip(expressions.iter()) {
+            if let ast::Expression::Function(function) = strip_parentheses(expression) {
+                self.function_kinds.insert(
+                    range(&function.1),
+                    FunctionKind::from_name(&name.token().to_string()),
+                );
+            }
+        }
+
+        // `local count, setCount = React.useState(0)`
+        if let (1, Some(ast::Expression::FunctionCall(call))) =
+            (expressions.len(), expressions.iter().next())
+        {
+            let stable_names = match hook_name(call)
+                .as_deref()
+                .and_then(|hook_name| hook_name.rsplit('.').next())
+            {
+                Some("useRef") => &[0][..],
+                Some("useState" | "useReducer") => &[1],
+                Some("useBinding") => &[0, 1],
+                _ => &[],
+            };
+
+            for index in stable_names {
+                if let Some(name) = names.get(*index) {
+                    self.stable_variables.insert(range(*name));
+                }
+            }
+        }
+    }
+
+    fn visit_assignment(&mut self, assignment: &ast::Assignment) {
+        for (var, expression) in assignment
+            .variables()
+            .iter()
+            .zip(assignment.expressions().iter())
+        {
+            let ast::Expression::Function(function) = strip_parentheses(expression) else {
+                continue;
+            };
+
+            let name = match var {
+                ast::Var::Name(name) => name,
+                ast::Var::Expression(var_expression) => match var_expression.suffixes().last() {
+                    Some(ast::Suffix::Index(ast::Index::Dot { name, .. })) => name,
+                    _ => continue,
+                },
+                _ => continue,
+            };
+
+            self.function_kinds.insert(
+                range(&function.1),
+                FunctionKind::from_name(&name.token().to_string()),
+            );
+        }
+    }
+
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        if let Some(body) = wrapped_component(call) {
+            self.function_kinds
+                .insert(range(body), FunctionKind::Component);
+        }
+
+        if let Some(hook_name) = hook_name(call) {
+            self.check_hook_call(call, &hook_name);
+        }
+    }
+
+    fn visit_expression(&mut self, expression: &ast::Expression) {
+        #[cfg_attr(
+            feature = "force_exhaustive_checks",
+            allow(non_exhaustive_omitted_patterns)
+        )]
+        match expression {
+            ast::Expression::BinaryOperator {
+                binop: ast::BinOp::And(_) | ast::BinOp::Or(_),
+                rhs,
+                ..
+            } => {
+                self.conditional_ranges.push(range(rhs));
+            }
+
+            ast::Expression::IfExpression(if_expression) => {
+                self.conditional_ranges
+                    .push(range(if_expression.if_expression()));
+
+                for else_if_expression in if_expression.else_if_expressions().into_iter().flatten()
+                {
+                    self.conditional_ranges.push(range(else_if_expression));
+                }
+
+                self.conditional_ranges
+                    .push(range(if_expression.else_expression()));
+            }
+
+            _ => {}
+        }
+    }
+
+    fn visit_return_end(&mut self, _: &ast::Return) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.after_early_return = true;
+        }
+    }
+
+    fn visit_if(&mut self, _: &ast::If) {
+        self.enter_block(false);
+    }
+
+    fn visit_if_end(&mut self, _: &ast::If) {
+        self.exit_block(false);
+    }
+
+    fn visit_generic_for(&mut self, _: &ast::GenericFor) {
+        self.enter_block(true);
+    }
+
+    fn visit_generic_for_end(&mut self, _: &ast::GenericFor) {
+        self.exit_block(true);
+    }
+
+    fn visit_numeric_for(&mut self, _: &ast::NumericFor) {
+        self.enter_block(true);
+    }
+
+    fn visit_numeric_for_end(&mut self, _: &ast::NumericFor) {
+        self.exit_block(true);
+    }
+
+    fn visit_repeat(&mut self, _: &ast::Repeat) {
+        self.enter_block(true);
+    }
+
+    fn visit_repeat_end(&mut self, _: &ast::Repeat) {
+        self.exit_block(true);
+    }
+
+    fn visit_while(&mut self, _: &ast::While) {
+        self.enter_block(true);
+    }
+
+    fn visit_while_end(&mut self, _: &ast::While) {
+        self.exit_block(true);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_roblox_roact_hooks() {
+        test_lint(
+            RoactHooksLint::new(()).unwrap(),
+            "roblox_roact_hooks",
+            "roblox_roact_hooks",
+        );
+    }
+
+    #[test]
+    fn test_dependencies() {
+        test_lint(
+            RoactHooksLint::new(()).unwrap(),
+            "roblox_roact_hooks",
+            "dependencies",
+        );
+    }
+}
@@ -1,10 +1,11 @@
 use super::*;
+use crate::ast_util::scopes::VariableKind;
 
 use full_moon::ast::Ast;
 use regex::Regex;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(default)]
 pub struct ShadowingConfig {
     ignore_pattern: String,
@@ -28,6 +29,7 @@ impl Lint for ShadowingLint {
 
     const SEVERITY: Severity = Severity::Warning;
     const LINT_TYPE: LintType = LintType::Style;
+    const LUACHECK_CODES: &'static [&'static str] = &["421", "422", "423", "431", "432", "433"];
 
     fn new(config: Self::Config) -> Result<Self, Self::Error> {
         Ok(ShadowingLint {
@@ -53,6 +55,7 @@ impl Lint for ShadowingLint {
                     first_defined: (definition.0 as u32, definition.1 as u32),
                     name,
                     range: variable.identifiers[0],
+                    shadows_parameter: shadow.kind == VariableKind::Parameter,
                 });
             }
         }
@@ -70,6 +73,11 @@ impl Lint for ShadowingLint {
                         "previously defined here".to_owned(),
                     )],
                 )
+                .with_luacheck_code(if shadow.shadows_parameter {
+                    "422"
+                } else {
+                    "421"
+                })
             })
             .collect()
     }
@@ -79,6 +87,7 @@ struct Shadow {
     first_defined: (u32, u32),
     name: String,
     range: (usize, usize),
+    shadows_parameter: bool,
 }
 
 #[cfg(test)]
@@ -1,9 +1,16 @@
 use super::{super::standard_library::*, *};
 use crate::{
-    ast_util::{name_paths::*, scopes::ScopeManager},
+    ast_util::{
+        name_paths::*,
+        range,
+        scopes::{ReferenceWrite, ScopeManager, Variable},
+        strip_parentheses, ConstantArgument,
+    },
     possible_std::possible_standard_library_notes,
+    text::closest_match,
 };
-use std::convert::Infallible;
+use id_arena::Id;
+use std::{collections::HashMap, convert::Infallible};
 
 use full_moon::{
     ast::{self, Ast, Expression},
@@ -20,6 +27,7 @@ impl Lint for StandardLibraryLint {
 
     const SEVERITY: Severity = Severity::Error;
     const LINT_TYPE: LintType = LintType::Correctness;
+    const LUACHECK_CODES: &'static [&'static str] = &["143", "142", "121", "122"];
 
     fn new(_: Self::Config) -> Result<Self, Self::Error> {
         Ok(StandardLibraryLint)
@@ -28,6 +36,7 @@ impl Lint for StandardLibraryLint {
     fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
         let mut visitor = StandardLibraryVisitor {
             diagnostics: Vec::new(),
+            local_structs: HashMap::new(),
             scope_manager: &ast_context.scope_manager,
             standard_library: &context.standard_library,
             user_set_standard_library: &context.user_set_standard_library,
@@ -204,28 +213,160 @@ fn get_argument_type(expression: &ast::Expression) -> Option<PassedArgumentType>
 
 pub struct StandardLibraryVisitor<'std> {
     diagnostics: Vec<Diagnostic>,
+    // Structs of locals defined by `local Players = game:GetService("Players")`, keyed by the range of their identifier
+    local_structs: HashMap<(usize, usize), String>,
     scope_manager: &'std ScopeManager,
     standard_library: &'std StandardLibrary,
     user_set_standard_library: &'std Option<Vec<String>>,
 }
 
-impl StandardLibraryVisitor<'_> {
+impl<'std> StandardLibraryVisitor<'std> {
+    // When `in_struct` is true, the first name in the path is a struct rather than a global,
+    // as with locals assigned from `GetService`
+    fn find_field(&self, name_path: &[String], in_struct: bool) -> Option<&'std Field> {
+        if in_struct {
+            self.standard_library
+                .find_struct_field(&name_path[0], &name_path[1..])
+        } else {
+            self.standard_library.find_global(name_path)
+        }
+    }
+
+    // Returns the struct that a local was assigned from `GetService`, if it was never reassigned
+    fn local_struct(&self, variable_id: Id<Variable>) -> Option<String> {
+        let variable = self.scope_manager.variables.get(variable_id)?;
+        let struct_name = self.local_structs.get(variable.identifiers.first()?)?;
+
+        let assignments = variable
+            .references
+            .iter()
+            .filter(|reference_id| {
+                let reference = &self.scope_manager.references[**reference_id];
+                reference.write == Some(ReferenceWrite::Assign) && reference.indexing.is_none()
+            })
+            .count();
+
+        (assignments == 1).then(|| struct_name.to_owned())
+    }
+
+    // Returns the struct for `game:GetService("Players")`, if the standard library has one for the service
+    fn get_service_struct(&self, expression: &ast::Expression) -> Option<String> {
+        let ast::Expression::FunctionCall(call) = strip_parentheses(expression) else {
+            return None;
+        };
+
+        let mut suffixes = call.suffixes().collect::<Vec<_>>();
+
+        let Some(ast::Suffix::Call(ast::Call::MethodCall(method_call))) = suffixes.pop() else {
+            return None;
+        };
+
+        if method_call.name().token().to_string() != "GetService" {
+            return None;
+        }
+
+        if self
+            .scope_manager
+            .reference_at_byte(call.start_position()?.bytes())
+            .is_some_and(|reference| reference.resolved.is_some())
+        {
+            return None;
+        }
+
+        let mut name_path = name_path_from_prefix_suffix(call.prefix(), suffixes.into_iter())?;
+        name_path.push("GetService".to_owned());
+
+        if !matches!(
+            self.standard_library.find_global(&name_path),
+            Some(field) if matches!(field.field_kind, FieldKind::Function(_))
+        ) {
+            return None;
+        }
+
+        let service = match method_call.args() {
+            ast::FunctionArgs::Parentheses { arguments, .. } if arguments.len() == 1 => {
+                ConstantArgument::from_expression(arguments.iter().next()?)
+            }
+            ast::FunctionArgs::String(token) => ConstantArgument::from_string_literal(token),
+            _ => None,
+        };
+
+        match service {
+            Some(ConstantArgument::String(service))
+                if self.standard_library.structs.contains_key(&service) =>
+            {
+                Some(service)
+            }
+
+            _ => None,
+        }
+    }
+
+    // Suggests a field with a similar name to one that doesn't exist, such as `GetPlayers` for `GetPlayerz`
+    fn did_you_mean_field(
+        &self,
+        parent_path: &[String],
+        field: &str,
+        in_struct: bool,
+    ) -> Option<String> {
+        let parent_struct = if in_struct && parent_path.len() == 1 {
+            Some(parent_path[0].as_str())
+        } else {
+            match self.find_field(parent_path, in_struct) {
+                Some(Field {
+                    field_kind: FieldKind::Struct(struct_name),
+                    ..
+                }) => Some(struct_name.as_str()),
+                _ => None,
+            }
+        };
+
+        let (fields, prefix) = match parent_struct
+            .and_then(|struct_name| self.standard_library.structs.get(struct_name))
+        {
+            Some(strukt) => (strukt, String::new()),
+            None if in_struct => (
+                self.standard_library.structs.get(&parent_path[0])?,
+                format!("{}.", parent_path[1..].join(".")),
+            ),
+            None => (
+                &self.standard_library.globals,
+                format!("{}.", parent_path.join(".")),
+            ),
+        };
+
+        let field_names = fields
+            .keys()
+            .filter_map(|name| name.strip_prefix(&prefix))
+            .filter_map(|name| name.split('.').next())
+            .filter(|name| *name != "*")
+            .collect::<Vec<_>>();
+
+        // Fields that exist but are being misused, such as calling a property, don't need suggestions
+        if field_names.contains(&field) {
+            return None;
+        }
+
+        closest_match(field, field_names).map(|suggestion| format!("did you mean `{suggestion}`?"))
+    }
+
     fn lint_invalid_field_access(
         &mut self,
         mut name_path: Vec<String>,
         range: (Position, Position),
+        in_struct: bool,
     ) {
         // Make sure it's not just `bad()`, and that it's not a field access from a global outside of standard library
-        if self.standard_library.find_global(&name_path).is_none()
-            && self.standard_library.global_has_fields(&name_path[0])
+        if self.find_field(&name_path, in_struct).is_none()
+            && (in_struct || self.standard_library.global_has_fields(&name_path[0]))
         {
             let field = name_path.pop().unwrap();
             assert!(!name_path.is_empty(), "name_path is empty");
 
             // check if it's writable
-            for bound in 1..=name_path.len() {
+            for bound in (if in_struct { 2 } else { 1 })..=name_path.len() {
                 let path = &name_path[0..bound];
-                match self.standard_library.find_global(path) {
+                match self.find_field(path, in_struct) {
                     Some(field) => {
                         match field.field_kind {
                             FieldKind::Any => return,
@@ -246,25 +387,63 @@ impl StandardLibraryVisitor<'_> {
                 }
             }
 
-            let mut name_path_with_field = name_path.iter().map(String::as_str).collect::<Vec<_>>();
-            name_path_with_field.push(&field);
+            let mut notes = Vec::new();
+
+            if !in_struct {
+                let mut name_path_with_field =
+                    name_path.iter().map(String::as_str).collect::<Vec<_>>();
+                name_path_with_field.push(&field);
+
+                notes = possible_standard_library_notes(
+                    &name_path_with_field,
+                    self.user_set_standard_library,
+                );
+            }
+
+            notes.extend(self.did_you_mean_field(&name_path, &field, in_struct));
 
             self.diagnostics.push(Diagnostic::new_complete(
                 "incorrect_standard_library_use",
                 format!(
-                    "standard library global `{}` does not contain the field `{}`",
+                    "standard library {} `{}` does not contain the field `{}`",
+                    if in_struct { "struct" } else { "global" },
                     name_path.join("."),
                     field,
                 ),
                 Label::new((range.0.bytes(), range.1.bytes())),
-                possible_standard_library_notes(
-                    &name_path_with_field,
-                    self.user_set_standard_library,
-                ),
+                notes,
                 Vec::new(),
             ));
         }
     }
+
+    // Returns true if the field is not available in the Lua versions of the standard library
+    fn lint_unavailable_field<N: Node>(
+        &mut self,
+        name_path: &[String],
+        field: &Field,
+        what: &str,
+        node: &N,
+    ) -> bool {
+        let FieldAvailability::Unavailable(reason) =
+            self.standard_library.field_availability(field)
+        else {
+            return false;
+        };
+
+        self.diagnostics.push(Diagnostic::new_complete(
+            "incorrect_standard_library_use",
+            format!(
+                "standard library {what} `{}` is not available",
+                name_path.join(".")
+            ),
+            Label::from_node(node, None),
+            vec![reason],
+            Vec::new(),
+        ));
+
+        true
+    }
 }
 
 impl Visitor for StandardLibraryVisitor<'_> {
@@ -327,6 +506,7 @@ impl Visitor for StandardLibraryVisitor<'_> {
                                 self.lint_invalid_field_access(
                                     name_path,
                                     var_expr.range().unwrap(),
+                                    false,
                                 );
                             }
                         }
@@ -366,28 +546,64 @@ impl Visitor for StandardLibraryVisitor<'_> {
         }
     }
 
+    fn visit_local_assignment(&mut self, local_assignment: &ast::LocalAssignment) {
+        for (name, expression) in local_assignment
+            .names()
+            .iter()
+            .zip(local_assignment.expressions().iter())
+        {
+            if let Some(struct_name) = self.get_service_struct(expression) {
+                self.local_structs.insert(range(name), struct_name);
+            }
+        }
+    }
+
     fn visit_expression(&mut self, expression: &ast::Expression) {
+        let mut struct_name = None;
+
         if let Some(reference) = self
             .scope_manager
             .reference_at_byte(expression.start_position().unwrap().bytes())
         {
-            if reference.resolved.is_some() {
-                return;
+            if let Some(variable_id) = reference.resolved {
+                struct_name = match self.local_struct(variable_id) {
+                    Some(struct_name) => Some(struct_name),
+                    None => return,
+                };
             }
         }
 
-        if let Some(name_path) = name_path(expression) {
-            self.lint_invalid_field_access(name_path, expression.range().unwrap());
+        if let Some(mut name_path) = name_path(expression) {
+            let in_struct = match struct_name {
+                Some(struct_name) if name_path.len() > 1 => {
+                    name_path[0] = struct_name;
+                    true
+                }
+
+                Some(_) => return,
+                None => false,
+            };
+
+            if let Some(field) = self.find_field(&name_path, in_struct) {
+                self.lint_unavailable_field(&name_path, field, "field", expression);
+            }
+
+            self.lint_invalid_field_access(name_path, expression.range().unwrap(), in_struct);
         }
     }
 
     fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        let mut struct_name = None;
+
         if let Some(reference) = self
             .scope_manager
             .reference_at_byte(call.start_position().unwrap().bytes())
         {
-            if reference.resolved.is_some() {
-                return;
+            if let Some(variable_id) = reference.resolved {
+                struct_name = match self.local_struct(variable_id) {
+                    Some(struct_name) => Some(struct_name),
+                    None => return,
+                };
             }
         }
 
@@ -403,9 +619,20 @@ impl Visitor for StandardLibraryVisitor<'_> {
                 None => return,
             };
 
+        // `Players:GetPlayers()` where `Players` is a local assigned from `GetService`
+        let in_struct = match struct_name {
+            Some(struct_name) if name_path.len() > 1 => {
+                name_path[0] = struct_name;
+                true
+            }
+
+            Some(_) => return,
+            None => false,
+        };
+
         let call_suffix = suffixes.pop().unwrap();
 
-        let field = match self.standard_library.find_global(&name_path) {
+        let field = match self.find_field(&name_path, in_struct) {
             Some(field) => field,
             None => {
                 self.lint_invalid_field_access(
@@ -421,22 +648,37 @@ impl Visitor for StandardLibraryVisitor<'_> {
                                 .unwrap_or_else(|| call.prefix().end_position().unwrap())
                         },
                     ),
+                    in_struct,
                 );
                 return;
             }
         };
 
+        if self.lint_unavailable_field(&name_path, field, "function", call) {
+            return;
+        }
+
         let function = match &field.field_kind {
             FieldKind::Any => return,
             FieldKind::Function(function) => function,
             _ => {
-                self.diagnostics.push(Diagnostic::new(
+                let (field_name, parent_path) = name_path.split_last().unwrap();
+
+                self.diagnostics.push(Diagnostic::new_complete(
                     "incorrect_standard_library_use",
                     format!(
                         "standard library field `{}` is not a function",
                         name_path.join("."),
                     ),
                     Label::from_node(call, None),
+                    if parent_path.is_empty() {
+                        Vec::new()
+                    } else {
+                        self.did_you_mean_field(parent_path, field_name, in_struct)
+                            .into_iter()
+                            .collect()
+                    },
+                    Vec::new(),
                 ));
 
                 return;
@@ -496,7 +738,11 @@ impl Visitor for StandardLibraryVisitor<'_> {
         match function_args {
             ast::FunctionArgs::Parentheses { arguments, .. } => {
                 for argument in arguments {
-                    argument_types.push((argument.range().unwrap(), get_argument_type(argument)));
+                    argument_types.push((
+                        argument.range().unwrap(),
+                        get_argument_type(argument),
+                        ConstantArgument::from_expression(argument),
+                    ));
                 }
             }
 
@@ -504,11 +750,16 @@ impl Visitor for StandardLibraryVisitor<'_> {
                 argument_types.push((
                     token.range().unwrap(),
                     Some(PassedArgumentType::from_string(token.token().to_string())),
+                    ConstantArgument::from_string_literal(token),
                 ));
             }
 
             ast::FunctionArgs::TableConstructor(table) => {
-                argument_types.push((table.range().unwrap(), Some(ArgumentType::Table.into())));
+                argument_types.push((
+                    table.range().unwrap(),
+                    Some(ArgumentType::Table.into()),
+                    None,
+                ));
             }
 
             _ => {}
@@ -600,7 +851,8 @@ impl Visitor for StandardLibraryVisitor<'_> {
             ));
         }
 
-        for ((range, passed_type), expected) in argument_types.iter().zip(function.arguments.iter())
+        for ((range, passed_type, constant), expected) in
+            argument_types.iter().zip(function.arguments.iter())
         {
             if expected.argument_type == ArgumentType::Vararg {
                 continue;
@@ -617,7 +869,18 @@ impl Visitor for StandardLibraryVisitor<'_> {
                 let matches = passed_type.matches(&expected.argument_type);
 
                 if !matches {
-                    self.diagnostics.push(Diagnostic::new(
+                    let notes = match (passed_type, &expected.argument_type) {
+                        (PassedArgumentType::String(text), ArgumentType::Constant(constants)) => {
+                            closest_match(text, constants.iter().map(String::as_str))
+                                .map(|suggestion| format!("did you mean `{suggestion}`?"))
+                                .into_iter()
+                                .collect()
+                        }
+
+                        _ => Vec::new(),
+                    };
+
+                    self.diagnostics.push(Diagnostic::new_complete(
                         "incorrect_standard_library_use",
                         format!(
                             "use of standard_library function `{}` is incorrect",
@@ -631,10 +894,133 @@ impl Visitor for StandardLibraryVisitor<'_> {
                                 passed_type.type_name()
                             ),
                         ),
+                        notes,
+                        Vec::new(),
+                    ));
+
+                    continue;
+                }
+            }
+
+            if let Some(problem) = constant
+                .as_ref()
+                .and_then(|constant| constraint_problem(constant, &expected.constraints))
+            {
+                self.diagnostics.push(Diagnostic::new(
+                    "incorrect_standard_library_use",
+                    format!(
+                        "use of standard_library function `{}` is incorrect",
+                        name_path.join("."),
+                    ),
+                    Label::new_with_message(
+                        (range.0.bytes() as u32, range.1.bytes() as u32),
+                        problem,
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+// Returns what is wrong with a constant argument according to the constraints of the standard library
+fn constraint_problem(
+    constant: &ConstantArgument,
+    constraints: &ArgumentConstraints,
+) -> Option<String> {
+    let expected_values = |matches_kind: fn(&ConstraintValue) -> bool| {
+        constraints
+            .values
+            .iter()
+            .filter(|value| matches_kind(value))
+            .collect::<Vec<_>>()
+    };
+
+    let one_of = |values: &[&ConstraintValue]| {
+        values
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    match constant {
+        ConstantArgument::Number(number) => {
+            let number = *number;
+
+            if constraints.integer && number.fract() != 0.0 {
+                return Some(format!("expected an integer, received `{number}`"));
+            }
+
+            if constraints.non_zero && number == 0.0 {
+                return Some("expected a number other than 0".to_owned());
+            }
+
+            if let Some(min) = constraints.min {
+                if number < min.0 {
+                    return Some(format!(
+                        "expected a number of at least {min}, received `{number}`"
+                    ));
+                }
+            }
+
+            if let Some(max) = constraints.max {
+                if number > max.0 {
+                    return Some(format!(
+                        "expected a number of at most {max}, received `{number}`"
+                    ));
+                }
+            }
+
+            let values = expected_values(|value| matches!(value, ConstraintValue::Number(_)));
+            if !values.is_empty()
+                && !values.iter().any(
+                    |value| matches!(value, ConstraintValue::Number(value) if value.0 == number),
+                )
+            {
+                return Some(format!(
+                    "expected one of {}, received `{number}`",
+                    one_of(&values)
+                ));
+            }
+
+            None
+        }
+
+        ConstantArgument::String(string) => {
+            let length = string.chars().count();
+
+            if let Some(min_length) = constraints.min_length {
+                if length < min_length {
+                    return Some(format!(
+                        "expected a string of at least {min_length} characters, received {length}"
+                    ));
+                }
+            }
+
+            if let Some(max_length) = constraints.max_length {
+                if length > max_length {
+                    return Some(format!(
+                        "expected a string of at most {max_length} characters, received {length}"
                     ));
                 }
             }
+
+            let values = expected_values(|value| matches!(value, ConstraintValue::String(_)));
+            if !values.is_empty()
+                && !values
+                    .iter()
+                    .any(|value| matches!(value, ConstraintValue::String(value) if value == string))
+            {
+                return Some(format!(
+                    "expected one of {}, received `\"{string}\"`",
+                    one_of(&values)
+                ));
+            }
+
+            None
         }
+
+        _ => None,
     }
 }
 
@@ -782,6 +1168,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_constraints() {
+        test_lint(
+            StandardLibraryLint::new(()).unwrap(),
+            "standard_library",
+            "constraints",
+        );
+    }
+
+    #[test]
+    fn test_get_service() {
+        test_lint(
+            StandardLibraryLint::new(()).unwrap(),
+            "standard_library",
+            "get_service",
+        );
+    }
+
     #[test]
     fn test_lua52() {
         test_lint_config(
@@ -795,6 +1199,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_lua_versions() {
+        let mut standard_library = StandardLibrary::from_name("lua54").unwrap();
+        standard_library.lua_versions = vec![LuaVersion::Lua51];
+
+        test_lint_config(
+            StandardLibraryLint::new(()).unwrap(),
+            "standard_library",
+            "lua_versions",
+            TestUtilConfig {
+                standard_library,
+                ..TestUtilConfig::default()
+            },
+        );
+    }
+
     #[test]
     fn test_math_on_types() {
         test_lint(
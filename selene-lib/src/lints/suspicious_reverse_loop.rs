@@ -15,6 +15,7 @@ impl Lint for SuspiciousReverseLoopLint {
 
     const SEVERITY: Severity = Severity::Error;
     const LINT_TYPE: LintType = LintType::Correctness;
+    const LUACHECK_CODES: &'static [&'static str] = &["571"];
 
     fn new(_: Self::Config) -> Result<Self, Self::Error> {
         Ok(SuspiciousReverseLoopLint)
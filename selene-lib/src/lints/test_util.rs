@@ -68,8 +68,9 @@ pub fn test_lint_config_with_output<
             } else {
                 None
             },
+            config_directory: None,
         },
-        &AstContext::from_ast(&ast),
+        &AstContext::from_ast(&ast).with_file_path(&path_base.with_extension("lua")),
     );
 
     let mut files = codespan::Files::new();
@@ -0,0 +1,83 @@
+use super::*;
+use crate::ast_util::functions::visit_functions;
+use std::convert::Infallible;
+
+use full_moon::ast::{self, Ast};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TooManyParametersConfig {
+    maximum_parameters: usize,
+}
+
+impl Default for TooManyParametersConfig {
+    fn default() -> Self {
+        Self {
+            maximum_parameters: 8,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct TooManyParametersLint {
+    config: TooManyParametersConfig,
+}
+
+impl Lint for TooManyParametersLint {
+    type Config = TooManyParametersConfig;
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Allow;
+    const LINT_TYPE: LintType = LintType::Style;
+
+    fn new(config: Self::Config) -> Result<Self, Self::Error> {
+        Ok(TooManyParametersLint { config })
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, _: &AstContext) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        visit_functions(ast, |function| {
+            // `...` is not counted, since it doesn't add anything for callers to remember
+            let parameters = function
+                .body
+                .parameters()
+                .iter()
+                .filter(|parameter| matches!(parameter, ast::Parameter::Name(_)))
+                .count();
+
+            if parameters > self.config.maximum_parameters {
+                diagnostics.push(Diagnostic::new(
+                    "too_many_parameters",
+                    format!(
+                        "{} has too many parameters ({parameters} > {})",
+                        function.description(),
+                        self.config.maximum_parameters
+                    ),
+                    Label::new(function.signature),
+                ));
+            }
+        });
+
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_too_many_parameters() {
+        test_lint(
+            TooManyParametersLint::new(TooManyParametersConfig {
+                maximum_parameters: 3,
+            })
+            .unwrap(),
+            "too_many_parameters",
+            "too_many_parameters",
+        );
+    }
+}
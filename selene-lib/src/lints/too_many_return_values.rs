@@ -0,0 +1,111 @@
+use super::*;
+use crate::ast_util::{
+    functions::{nested_blocks, visit_functions},
+    range,
+};
+use std::convert::Infallible;
+
+use full_moon::ast::{self, Ast};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TooManyReturnValuesConfig {
+    maximum_return_values: usize,
+}
+
+impl Default for TooManyReturnValuesConfig {
+    fn default() -> Self {
+        Self {
+            maximum_return_values: 5,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct TooManyReturnValuesLint {
+    config: TooManyReturnValuesConfig,
+}
+
+impl Lint for TooManyReturnValuesLint {
+    type Config = TooManyReturnValuesConfig;
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Allow;
+    const LINT_TYPE: LintType = LintType::Style;
+
+    fn new(config: Self::Config) -> Result<Self, Self::Error> {
+        Ok(TooManyReturnValuesLint { config })
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, _: &AstContext) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        visit_functions(ast, |function| {
+            let mut returns = Vec::new();
+            collect_returns(function.body.block(), &mut returns);
+
+            let secondary_labels = returns
+                .into_iter()
+                .filter(|return_stmt| {
+                    return_stmt.returns().len() > self.config.maximum_return_values
+                })
+                .map(|return_stmt| {
+                    Label::new_with_message(
+                        range::<_, usize>(return_stmt),
+                        format!("returns {} values here", return_stmt.returns().len()),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            if secondary_labels.is_empty() {
+                return;
+            }
+
+            diagnostics.push(Diagnostic::new_complete(
+                "too_many_return_values",
+                format!(
+                    "{} returns more than {} values",
+                    function.description(),
+                    self.config.maximum_return_values
+                ),
+                Label::new(function.signature),
+                Vec::new(),
+                secondary_labels,
+            ));
+        });
+
+        diagnostics
+    }
+}
+
+// Returns inside of nested functions are not collected, as those functions are measured separately
+fn collect_returns<'a>(block: &'a ast::Block, returns: &mut Vec<&'a ast::Return>) {
+    for stmt in block.stmts() {
+        for nested_block in nested_blocks(stmt) {
+            collect_returns(nested_block, returns);
+        }
+    }
+
+    if let Some(ast::LastStmt::Return(return_stmt)) = block.last_stmt() {
+        returns.push(return_stmt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_too_many_return_values() {
+        test_lint(
+            TooManyReturnValuesLint::new(TooManyReturnValuesConfig {
+                maximum_return_values: 2,
+            })
+            .unwrap(),
+            "too_many_return_values",
+            "too_many_return_values",
+        );
+    }
+}
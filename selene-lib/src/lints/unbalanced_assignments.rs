@@ -16,6 +16,7 @@ impl Lint for UnbalancedAssignmentsLint {
 
     const SEVERITY: Severity = Severity::Warning;
     const LINT_TYPE: LintType = LintType::Complexity;
+    const LUACHECK_CODES: &'static [&'static str] = &["531", "532"];
 
     fn new(_: Self::Config) -> Result<Self, Self::Error> {
         Ok(UnbalancedAssignmentsLint)
@@ -57,6 +58,7 @@ impl Lint for UnbalancedAssignmentsLint {
                         Vec::new(),
                         secondary_labels,
                     )
+                    .with_luacheck_code("532")
                 }
             })
             .collect()
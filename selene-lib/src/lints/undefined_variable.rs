@@ -19,6 +19,7 @@ impl Lint for UndefinedVariableLint {
 
     const SEVERITY: Severity = Severity::Error;
     const LINT_TYPE: LintType = LintType::Correctness;
+    const LUACHECK_CODES: &'static [&'static str] = &["113"];
 
     fn new(_: Self::Config) -> Result<Self, Self::Error> {
         Ok(UndefinedVariableLint)
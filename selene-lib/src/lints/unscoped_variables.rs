@@ -5,9 +5,9 @@ use std::collections::HashSet;
 
 use full_moon::ast::Ast;
 use regex::Regex;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(default)]
 pub struct UnscopedVariablesConfig {
     ignore_pattern: String,
@@ -31,6 +31,7 @@ impl Lint for UnscopedVariablesLint {
 
     const SEVERITY: Severity = Severity::Warning;
     const LINT_TYPE: LintType = LintType::Complexity;
+    const LUACHECK_CODES: &'static [&'static str] = &["111"];
 
     fn new(config: Self::Config) -> Result<Self, Self::Error> {
         Ok(UnscopedVariablesLint {
@@ -1,5 +1,5 @@
 use crate::{
-    ast_util::scopes::AssignedValue,
+    ast_util::scopes::{AssignedValue, VariableKind},
     standard_library::{Field, FieldKind, Observes},
 };
 
@@ -7,9 +7,9 @@ use super::*;
 
 use full_moon::ast::Ast;
 use regex::Regex;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(default)]
 pub struct UnusedVariableConfig {
     allow_unused_self: bool,
@@ -43,6 +43,7 @@ impl Lint for UnusedVariableLint {
 
     const SEVERITY: Severity = Severity::Warning;
     const LINT_TYPE: LintType = LintType::Style;
+    const LUACHECK_CODES: &'static [&'static str] = &["211", "212", "213"];
 
     fn new(config: Self::Config) -> Result<Self, Self::Error> {
         Ok(Self {
@@ -169,7 +170,7 @@ impl Lint for UnusedVariableLint {
 
                 let write_only = !analyzed_references.is_empty();
 
-                diagnostics.push(Diagnostic::new_complete(
+                let diagnostic = Diagnostic::new_complete(
                     "unused_variable",
                     if write_only {
                         format!("{} is assigned a value, but never used", variable.name)
@@ -188,7 +189,14 @@ impl Lint for UnusedVariableLint {
                             }
                         })
                         .collect(),
-                ));
+                );
+
+                let is_parameter = variable.is_self || variable.kind == VariableKind::Parameter;
+                diagnostics.push(diagnostic.with_luacheck_code(if is_parameter {
+                    "212"
+                } else {
+                    "211"
+                }));
             };
         }
 
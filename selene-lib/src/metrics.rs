@@ -0,0 +1,204 @@
+//! Structural metrics for functions, for tracking complexity over time rather than failing on it.
+//! These are computed the same way as the lints that measure them, such as `high_cyclomatic_complexity`.
+use std::collections::BTreeSet;
+
+use full_moon::{
+    ast::{self, Ast},
+    node::Node,
+};
+
+use crate::{
+    ast_util::{
+        functions::visit_functions,
+        scopes::{ScopeManager, VariableKind},
+    },
+    lints::{
+        deep_nesting::deepest_stmt, high_cyclomatic_complexity::count_block_complexity,
+        long_function::count_statements,
+    },
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionMetrics {
+    /// The name the function was declared with, such as `Class:method`. None for anonymous functions.
+    pub name: Option<String>,
+    /// The byte range of the function's signature, such as `function Class:method(a, b)`.
+    pub signature: (usize, usize),
+    pub cyclomatic_complexity: u16,
+    pub statements: usize,
+    pub nesting_depth: usize,
+    pub parameters: usize,
+    /// The globals read or written directly inside this function, not including nested functions.
+    pub globals_used: BTreeSet<String>,
+}
+
+/// The metrics of a file: its main chunk, and every function in it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileMetrics {
+    /// The code outside of any function, measured as if it were a function of its own.
+    /// Its name is None, and its signature is empty.
+    pub main_chunk: FunctionMetrics,
+    /// Every function, in the order they appear.
+    pub functions: Vec<FunctionMetrics>,
+}
+
+fn block_metrics(
+    name: Option<String>,
+    signature: (usize, usize),
+    block: &ast::Block,
+    parameters: usize,
+) -> FunctionMetrics {
+    FunctionMetrics {
+        name,
+        signature,
+        cyclomatic_complexity: count_block_complexity(block, 1),
+        statements: count_statements(block),
+        nesting_depth: deepest_stmt(block, 0).map_or(0, |(depth, _)| depth),
+        parameters,
+        globals_used: BTreeSet::new(),
+    }
+}
+
+/// Computes the metrics of the main chunk and every function in the AST.
+pub fn file_metrics(ast: &Ast) -> FileMetrics {
+    let mut main_chunk = block_metrics(None, (0, 0), ast.nodes(), 0);
+    let mut metrics = Vec::new();
+    let mut body_ranges = Vec::new();
+
+    visit_functions(ast, |function| {
+        body_ranges.push(
+            function
+                .body
+                .range()
+                .map_or((0, 0), |(start, end)| (start.bytes(), end.bytes())),
+        );
+
+        metrics.push(block_metrics(
+            function.name.clone(),
+            function.signature,
+            function.body.block(),
+            function
+                .body
+                .parameters()
+                .iter()
+                .filter(|parameter| matches!(parameter, ast::Parameter::Name(_)))
+                .count(),
+        ));
+    });
+
+    let scope_manager = ScopeManager::new(ast);
+
+    for (_, reference) in &scope_manager.references {
+        let is_global = match reference.resolved {
+            Some(variable) => matches!(
+                scope_manager.variables[variable].kind,
+                VariableKind::Global | VariableKind::GlobalFunction
+            ),
+            None => true,
+        };
+
+        if !is_global {
+            continue;
+        }
+
+        // Attribute the global to the innermost function containing it
+        let innermost = body_ranges
+            .iter()
+            .enumerate()
+            .filter(|(_, (start, end))| {
+                *start <= reference.identifier.0 && reference.identifier.1 <= *end
+            })
+            .min_by_key(|(_, (start, end))| end - start);
+
+        let globals_used = match innermost {
+            Some((index, _)) => &mut metrics[index].globals_used,
+            None => &mut main_chunk.globals_used,
+        };
+
+        globals_used.insert(reference.name.to_owned());
+    }
+
+    FileMetrics {
+        main_chunk,
+        functions: metrics,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_function_metrics() {
+        let ast = full_moon::parse(
+            r#"
+            local function outer(a, b, ...)
+                if a then
+                    for _ = 1, 10 do
+                        print(b)
+                    end
+                end
+
+                return function()
+                    helper()
+                end
+            end
+            "#,
+        )
+        .unwrap();
+
+        let metrics = file_metrics(&ast).functions;
+        assert_eq!(metrics.len(), 2);
+
+        let outer = &metrics[0];
+        assert_eq!(outer.name.as_deref(), Some("outer"));
+        assert_eq!(outer.cyclomatic_complexity, 3);
+        assert_eq!(outer.statements, 4);
+        assert_eq!(outer.nesting_depth, 2);
+        assert_eq!(outer.parameters, 2);
+        assert_eq!(outer.globals_used, BTreeSet::from(["print".to_owned()]));
+
+        let inner = &metrics[1];
+        assert_eq!(inner.name, None);
+        assert_eq!(inner.statements, 1);
+        assert_eq!(inner.globals_used, BTreeSet::from(["helper".to_owned()]));
+    }
+
+    #[test]
+    fn test_main_chunk_metrics() {
+        let ast = full_moon::parse(
+            r#"
+            local config = loadConfig()
+
+            if config.verbose then
+                for _, item in ipairs(config.items) do
+                    print(item)
+                end
+            end
+
+            local function helper()
+                return format()
+            end
+            "#,
+        )
+        .unwrap();
+
+        let metrics = file_metrics(&ast);
+        assert_eq!(metrics.functions.len(), 1);
+
+        let main_chunk = &metrics.main_chunk;
+        assert_eq!(main_chunk.name, None);
+        assert_eq!(main_chunk.cyclomatic_complexity, 3);
+        assert_eq!(main_chunk.statements, 5);
+        assert_eq!(main_chunk.nesting_depth, 2);
+        assert_eq!(main_chunk.parameters, 0);
+        assert_eq!(
+            main_chunk.globals_used,
+            BTreeSet::from([
+                "ipairs".to_owned(),
+                "loadConfig".to_owned(),
+                "print".to_owned()
+            ])
+        );
+    }
+}
@@ -0,0 +1,412 @@
+//! Builds the instance tree of a Rojo project from its project file and the filesystem,
+//! following the same rules Rojo uses to turn files into instances.
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde_json::Value;
+
+#[derive(Debug)]
+pub enum RojoError {
+    Io {
+        path: PathBuf,
+        error: io::Error,
+    },
+    Json {
+        path: PathBuf,
+        error: serde_json::Error,
+    },
+    InvalidProject {
+        path: PathBuf,
+        message: String,
+    },
+}
+
+impl fmt::Display for RojoError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RojoError::Io { path, error } => {
+                write!(formatter, "couldn't read {}: {error}", path.display())
+            }
+
+            RojoError::Json { path, error } => {
+                write!(formatter, "couldn't parse {}: {error}", path.display())
+            }
+
+            RojoError::InvalidProject { path, message } => {
+                write!(
+                    formatter,
+                    "invalid Rojo project {}: {message}",
+                    path.display()
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for RojoError {}
+
+#[derive(Debug, Default)]
+pub struct InstanceNode {
+    /// The class of the instance, if known. Models and instances only described by name are unknown.
+    pub class_name: Option<String>,
+    pub children: BTreeMap<String, InstanceNode>,
+    /// Whether every child of the instance is known, which is only the case when Rojo
+    /// removes instances that it doesn't know about, such as for directories.
+    pub complete: bool,
+}
+
+#[derive(Debug)]
+pub struct RojoProject {
+    pub name: String,
+    pub root: InstanceNode,
+    // Canonicalized script paths to the names of the instances leading to them from the root
+    scripts: HashMap<PathBuf, Vec<String>>,
+}
+
+impl RojoProject {
+    pub fn from_file(project_path: &Path) -> Result<Self, RojoError> {
+        let mut builder = TreeBuilder {
+            scripts: HashMap::new(),
+        };
+
+        let (name, root) = builder.build_project(project_path, Vec::new())?;
+
+        Ok(RojoProject {
+            name,
+            root,
+            scripts: builder.scripts,
+        })
+    }
+
+    /// Whether the project is a place, rather than a model or library.
+    pub fn is_place(&self) -> bool {
+        self.root.class_name.as_deref() == Some("DataModel")
+    }
+
+    /// The path of the instance a script file turns into, such as `["ReplicatedStorage", "Shared", "Util"]`.
+    pub fn script_instance_path(&self, file_path: &Path) -> Option<&[String]> {
+        let file_path = fs::canonicalize(file_path).ok()?;
+        self.scripts.get(&file_path).map(Vec::as_slice)
+    }
+
+    pub fn instance<S: AsRef<str>>(&self, path: &[S]) -> Option<&InstanceNode> {
+        path.iter()
+            .try_fold(&self.root, |node, name| node.children.get(name.as_ref()))
+    }
+}
+
+struct TreeBuilder {
+    scripts: HashMap<PathBuf, Vec<String>>,
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|file_name| file_name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn read_json(path: &Path) -> Result<Value, RojoError> {
+    let contents = fs::read_to_string(path).map_err(|error| RojoError::Io {
+        path: path.to_owned(),
+        error,
+    })?;
+
+    serde_json::from_str(&contents).map_err(|error| RojoError::Json {
+        path: path.to_owned(),
+        error,
+    })
+}
+
+// Files that are turned into instances, and what they turn into
+#[derive(Clone, Copy)]
+enum FileKind {
+    Script(&'static str),
+    Instance(&'static str),
+    Model,
+    Project,
+}
+
+// Returns the name of the instance and what the file turns into, or None if Rojo ignores the file
+fn file_kind(file_name: &str) -> Option<(&str, FileKind)> {
+    const SUFFIXES: &[(&str, FileKind)] = &[
+        (".project.json", FileKind::Project),
+        (".model.json", FileKind::Model),
+        (".server.luau", FileKind::Script("Script")),
+        (".server.lua", FileKind::Script("Script")),
+        (".client.luau", FileKind::Script("LocalScript")),
+        (".client.lua", FileKind::Script("LocalScript")),
+        (".luau", FileKind::Script("ModuleScript")),
+        (".lua", FileKind::Script("ModuleScript")),
+        (".json", FileKind::Instance("ModuleScript")),
+        (".toml", FileKind::Instance("ModuleScript")),
+        (".txt", FileKind::Instance("StringValue")),
+        (".csv", FileKind::Instance("LocalizationTable")),
+        (".rbxm", FileKind::Model),
+        (".rbxmx", FileKind::Model),
+    ];
+
+    // Metadata files describe other instances rather than being their own
+    if file_name.ends_with(".meta.json") {
+        return None;
+    }
+
+    SUFFIXES
+        .iter()
+        .find_map(|(suffix, kind)| Some((file_name.strip_suffix(suffix)?, *kind)))
+}
+
+impl TreeBuilder {
+    fn build_project(
+        &mut self,
+        project_path: &Path,
+        instance_path: Vec<String>,
+    ) -> Result<(String, InstanceNode), RojoError> {
+        let project = read_json(project_path)?;
+        let invalid_project = |message: &str| RojoError::InvalidProject {
+            path: project_path.to_owned(),
+            message: message.to_owned(),
+        };
+
+        let name = project
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or_else(|| invalid_project("missing `name`"))?
+            .to_owned();
+
+        let tree = project
+            .get("tree")
+            .and_then(Value::as_object)
+            .ok_or_else(|| invalid_project("missing `tree`"))?;
+
+        let project_directory = project_path.parent().unwrap_or_else(|| Path::new("."));
+
+        let root = self
+            .build_project_node(tree, project_directory, instance_path)?
+            .ok_or_else(|| {
+                invalid_project("the root of the tree is an optional path that doesn't exist")
+            })?;
+
+        Ok((name, root))
+    }
+
+    // Returns None for optional paths that don't exist
+    fn build_project_node(
+        &mut self,
+        node: &serde_json::Map<String, Value>,
+        project_directory: &Path,
+        instance_path: Vec<String>,
+    ) -> Result<Option<InstanceNode>, RojoError> {
+        let mut instance = match node.get("$path") {
+            Some(path) => {
+                let (path, optional) = match path {
+                    Value::String(path) => (path.as_str(), false),
+                    Value::Object(object) => match object.get("optional").and_then(Value::as_str) {
+                        Some(path) => (path, true),
+                        None => return Ok(None),
+                    },
+                    _ => return Ok(None),
+                };
+
+                let path = project_directory.join(path);
+
+                // Paths such as `Packages` are often only created by other tools, so they aren't errors
+                if !path.exists() {
+                    return Ok((!optional).then(InstanceNode::default));
+                }
+
+                match self.build_path(&path, instance_path.clone())? {
+                    Some(instance) => instance,
+                    None => return Ok(None),
+                }
+            }
+
+            None => InstanceNode::default(),
+        };
+
+        if let Some(class_name) = node.get("$className").and_then(Value::as_str) {
+            instance.class_name = Some(class_name.to_owned());
+        }
+
+        if let Some(ignore_unknown_instances) =
+            node.get("$ignoreUnknownInstances").and_then(Value::as_bool)
+        {
+            instance.complete = !ignore_unknown_instances;
+        }
+
+        for (name, child) in node {
+            let Some(child) = child.as_object() else {
+                continue;
+            };
+
+            if name.starts_with('$') {
+                continue;
+            }
+
+            let mut child_path = instance_path.clone();
+            child_path.push(name.to_owned());
+
+            if let Some(child) = self.build_project_node(child, project_directory, child_path)? {
+                instance.children.insert(name.to_owned(), child);
+            }
+        }
+
+        Ok(Some(instance))
+    }
+
+    // Returns the instance a path turns into, if Rojo doesn't ignore it
+    fn build_path(
+        &mut self,
+        path: &Path,
+        instance_path: Vec<String>,
+    ) -> Result<Option<InstanceNode>, RojoError> {
+        if path.is_dir() {
+            let nested_project = path.join("default.project.json");
+            if nested_project.exists() {
+                return self
+                    .build_project(&nested_project, instance_path)
+                    .map(|(_, instance)| Some(instance));
+            }
+
+            return self.build_directory(path, instance_path).map(Some);
+        }
+
+        let Some((_, kind)) = file_kind(&file_name(path)) else {
+            return Ok(None);
+        };
+
+        let instance = match kind {
+            FileKind::Script(class_name) => {
+                self.scripts.insert(
+                    fs::canonicalize(path).map_err(|error| RojoError::Io {
+                        path: path.to_owned(),
+                        error,
+                    })?,
+                    instance_path,
+                );
+
+                InstanceNode {
+                    class_name: Some(class_name.to_owned()),
+                    children: BTreeMap::new(),
+                    complete: true,
+                }
+            }
+
+            FileKind::Instance(class_name) => InstanceNode {
+                class_name: Some(class_name.to_owned()),
+                children: BTreeMap::new(),
+                complete: true,
+            },
+
+            // The children of models aren't read
+            FileKind::Model => {
+                let class_name = if file_name(path).ends_with(".model.json") {
+                    let model = read_json(path)?;
+
+                    model
+                        .get("ClassName")
+                        .or_else(|| model.get("className"))
+                        .and_then(Value::as_str)
+                        .map(ToOwned::to_owned)
+                } else {
+                    None
+                };
+
+                InstanceNode {
+                    class_name,
+                    children: BTreeMap::new(),
+                    complete: false,
+                }
+            }
+
+            FileKind::Project => self.build_project(path, instance_path)?.1,
+        };
+
+        Ok(Some(instance))
+    }
+
+    fn build_directory(
+        &mut self,
+        directory: &Path,
+        instance_path: Vec<String>,
+    ) -> Result<InstanceNode, RojoError> {
+        let io_error = |error| RojoError::Io {
+            path: directory.to_owned(),
+            error,
+        };
+
+        let mut entries = fs::read_dir(directory)
+            .map_err(io_error)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(io_error)?;
+
+        entries.sort();
+
+        let mut instance = InstanceNode {
+            class_name: None,
+            children: BTreeMap::new(),
+            complete: true,
+        };
+
+        // The class name in init.meta.json is only used when there isn't an init script
+        let mut meta_class_name = None;
+
+        for entry in entries {
+            let file_name = file_name(&entry);
+
+            // init.lua and friends turn the directory into a script
+            if entry.is_file() {
+                if let Some(("init", FileKind::Script(class_name))) = file_kind(&file_name) {
+                    self.scripts.insert(
+                        fs::canonicalize(&entry).map_err(io_error)?,
+                        instance_path.clone(),
+                    );
+
+                    instance.class_name = Some(class_name.to_owned());
+                    continue;
+                }
+
+                if file_name == "init.meta.json" {
+                    let meta = read_json(&entry)?;
+
+                    if let Some(class_name) = meta.get("className").and_then(Value::as_str) {
+                        meta_class_name = Some(class_name.to_owned());
+                    }
+
+                    if let Some(ignore_unknown_instances) =
+                        meta.get("ignoreUnknownInstances").and_then(Value::as_bool)
+                    {
+                        instance.complete = !ignore_unknown_instances;
+                    }
+
+                    continue;
+                }
+            }
+
+            let name = if entry.is_dir() {
+                file_name
+            } else {
+                match file_kind(&file_name) {
+                    Some((name, _)) => name.to_owned(),
+                    None => continue,
+                }
+            };
+
+            let mut child_path = instance_path.clone();
+            child_path.push(name.clone());
+
+            if let Some(child) = self.build_path(&entry, child_path)? {
+                instance.children.insert(name, child);
+            }
+        }
+
+        if instance.class_name.is_none() {
+            instance.class_name = Some(meta_class_name.unwrap_or_else(|| "Folder".to_owned()));
+        }
+
+        Ok(instance)
+    }
+}
@@ -2,7 +2,7 @@ use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub enum LuaVersion {
     Lua51,
     Lua52,
@@ -27,6 +27,46 @@ impl LuaVersion {
         }
     }
 
+    pub fn display_name(&self) -> &str {
+        match self {
+            Self::Lua51 => "Lua 5.1",
+            Self::Lua52 => "Lua 5.2",
+            Self::Lua53 => "Lua 5.3",
+            Self::Lua54 => "Lua 5.4",
+            Self::Luau => "Luau",
+            Self::LuaJIT => "LuaJIT",
+            Self::Unknown(value) => value,
+        }
+    }
+
+    // LuaJIT and Luau are both based on Lua 5.1
+    fn lua5_minor(&self) -> Option<u8> {
+        match self {
+            Self::Lua51 | Self::Luau | Self::LuaJIT => Some(1),
+            Self::Lua52 => Some(2),
+            Self::Lua53 => Some(3),
+            Self::Lua54 => Some(4),
+            Self::Unknown(_) => None,
+        }
+    }
+
+    /// Whether this version has everything that was added in `other`.
+    /// Lua 5.x versions build on each other, while LuaJIT and Luau only have their own additions.
+    pub fn has_additions_of(&self, other: &LuaVersion) -> bool {
+        match other {
+            Self::Lua51 | Self::Lua52 | Self::Lua53 | Self::Lua54 => {
+                match (self.lua5_minor(), other.lua5_minor()) {
+                    (Some(minor), Some(other_minor)) => minor >= other_minor,
+                    _ => true,
+                }
+            }
+
+            Self::Luau | Self::LuaJIT => self == other,
+
+            Self::Unknown(_) => true,
+        }
+    }
+
     pub fn to_lua_version(&self) -> Result<full_moon::ast::LuaVersion, LuaVersionError> {
         match self {
             Self::Lua51 => Ok(full_moon::ast::LuaVersion::lua51()),
@@ -121,6 +121,7 @@ pub struct StandardLibrary {
     pub structs: BTreeMap<String, BTreeMap<String, Field>>,
 
     #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub lua_versions: Vec<lua_versions::LuaVersion>,
 
     /// Internal, used for the Roblox standard library
@@ -244,13 +245,33 @@ impl StandardLibrary {
             return Some(explicit_global);
         }
 
+        profiling::scope!("find_global: look through global tree cache");
+        self.find_in_tree(self.global_tree_cache(), &self.globals, names)
+    }
+
+    /// Like `find_global`, but starting from inside a struct, such as finding `GetPlayers` in `Players`.
+    pub fn find_struct_field<S: Borrow<str>>(
+        &self,
+        struct_name: &str,
+        names: &[S],
+    ) -> Option<&Field> {
+        assert!(!names.is_empty());
+
+        let strukt = self.structs.get(struct_name)?;
+        self.find_in_tree(&extract_into_tree(strukt), strukt, names)
+    }
+
+    fn find_in_tree<'a, S: Borrow<str>>(
+        &'a self,
+        tree: &BTreeMap<String, GlobalTreeNode>,
+        names_to_fields: &'a BTreeMap<String, Field>,
+        names: &[S],
+    ) -> Option<&'a Field> {
         // TODO: This is really stupid lol
         let mut last_extracted_struct;
 
-        let mut current = self.global_tree_cache();
-        let mut current_names_to_fields = &self.globals;
-
-        profiling::scope!("find_global: look through global tree cache");
+        let mut current = tree;
+        let mut current_names_to_fields = names_to_fields;
 
         for name in names.iter().take(names.len() - 1) {
             let found_segment = current.get(name.borrow()).or_else(|| current.get("*"))?;
@@ -328,6 +349,17 @@ impl StandardLibrary {
         self.globals = globals;
     }
 
+    /// Extends this library with the library it's based on. Unlike [`extend`](Self::extend),
+    /// the `lua_versions` of this library are kept if it specifies any.
+    pub fn extend_base(&mut self, base: StandardLibrary) {
+        let lua_versions = std::mem::take(&mut self.lua_versions);
+        self.extend(base);
+
+        if !lua_versions.is_empty() {
+            self.lua_versions = lua_versions;
+        }
+    }
+
     #[cfg(feature = "roblox")]
     pub fn roblox_base() -> StandardLibrary {
         StandardLibrary::from_builtin_name(
@@ -345,12 +377,47 @@ impl StandardLibrary {
         if let Some(base_name) = &std.base {
             let base = StandardLibrary::from_name(base_name);
 
-            std.extend(base.expect("built-in library based off of non-existent built-in"));
+            std.extend_base(base.expect("built-in library based off of non-existent built-in"));
         }
 
         Some(std)
     }
 
+    pub fn field_availability(&self, field: &Field) -> FieldAvailability {
+        if field.since.is_none() && field.removed_in.is_none() {
+            return FieldAvailability::Available;
+        }
+
+        // No versions specified means Lua 5.1
+        let lua_versions = if self.lua_versions.is_empty() {
+            std::slice::from_ref(&lua_versions::LuaVersion::Lua51)
+        } else {
+            self.lua_versions.as_slice()
+        };
+
+        let unavailable_in = lua_versions
+            .iter()
+            .filter(|lua_version| !field.is_available_in(lua_version))
+            .map(lua_versions::LuaVersion::display_name)
+            .collect::<Vec<_>>();
+
+        if unavailable_in.is_empty() {
+            FieldAvailability::Available
+        } else if unavailable_in.len() == lua_versions.len() {
+            FieldAvailability::Unavailable(format!(
+                "{}, but the standard library is for {}",
+                field.availability_reason(),
+                unavailable_in.join(" and ")
+            ))
+        } else {
+            FieldAvailability::Partial(format!(
+                "{}, but the standard library is also for {}",
+                field.availability_reason(),
+                unavailable_in.join(" and ")
+            ))
+        }
+    }
+
     pub fn lua_version(&self) -> (full_moon::LuaVersion, Vec<lua_versions::LuaVersionError>) {
         let mut errors = Vec::new();
 
@@ -406,6 +473,8 @@ names! {
     "lua51" => "../../default_std/lua51.yml",
     "lua52" => "../../default_std/lua52.yml",
     "lua53" => "../../default_std/lua53.yml",
+    "lua54" => "../../default_std/lua54.yml",
+    "luajit" => "../../default_std/luajit.yml",
     "luau" => "../../default_std/luau.yml",
 }
 
@@ -442,6 +511,15 @@ pub struct Field {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deprecated: Option<Deprecated>,
+
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<lua_versions::LuaVersion>,
+
+    #[serde(default)]
+    #[serde(rename = "removed-in")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub removed_in: Option<lua_versions::LuaVersion>,
 }
 
 impl Field {
@@ -449,12 +527,63 @@ impl Field {
         Self {
             field_kind,
             deprecated: None,
+            since: None,
+            removed_in: None,
         }
     }
 
     pub fn with_deprecated(self, deprecated: Option<Deprecated>) -> Self {
         Self { deprecated, ..self }
     }
+
+    pub fn is_available_in(&self, lua_version: &lua_versions::LuaVersion) -> bool {
+        if let lua_versions::LuaVersion::Unknown(_) = lua_version {
+            return true;
+        }
+
+        let added = match &self.since {
+            Some(since) => lua_version.has_additions_of(since),
+            None => true,
+        };
+
+        let removed = match &self.removed_in {
+            Some(removed_in) => lua_version.has_additions_of(removed_in),
+            None => false,
+        };
+
+        added && !removed
+    }
+
+    // Such as "available since Lua 5.3", or "only available in LuaJIT"
+    fn availability_reason(&self) -> String {
+        let mut reasons = Vec::new();
+
+        match &self.since {
+            Some(since @ (lua_versions::LuaVersion::Luau | lua_versions::LuaVersion::LuaJIT)) => {
+                reasons.push(format!("only available in {}", since.display_name()))
+            }
+
+            Some(since) => reasons.push(format!("available since {}", since.display_name())),
+
+            None => {}
+        }
+
+        if let Some(removed_in) = &self.removed_in {
+            reasons.push(format!("removed in {}", removed_in.display_name()));
+        }
+
+        reasons.join(" and ")
+    }
+}
+
+/// Whether a field can be used in the Lua versions of a standard library, based on its `since` and `removed-in`.
+/// The strings explain why not, such as "available since Lua 5.3, but the standard library is for Lua 5.1".
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FieldAvailability {
+    Available,
+    /// Available in some of the Lua versions, but not all of them.
+    Partial(String),
+    Unavailable(String),
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq, Deserialize, Serialize)]
@@ -633,6 +762,83 @@ pub struct Argument {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deprecated: Option<Deprecated>,
+
+    #[serde(flatten)]
+    pub constraints: ArgumentConstraints,
+}
+
+/// Constraints on the values of constant arguments, such as `min: 0` for `string.rep`.
+/// These are only checked when the argument is a literal.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ArgumentConstraints {
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<ConstraintNumber>,
+
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<ConstraintNumber>,
+
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_false")]
+    pub integer: bool,
+
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_false")]
+    pub non_zero: bool,
+
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>,
+
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<ConstraintValue>,
+}
+
+/// A number in an argument constraint. Compared by its bits so that arguments can be hashed.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct ConstraintNumber(pub f64);
+
+impl PartialEq for ConstraintNumber {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_bits() == other.0.to_bits()
+    }
+}
+
+impl Eq for ConstraintNumber {}
+
+impl std::hash::Hash for ConstraintNumber {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state);
+    }
+}
+
+impl fmt::Display for ConstraintNumber {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}", self.0)
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ConstraintValue {
+    Number(ConstraintNumber),
+    String(String),
+}
+
+impl fmt::Display for ConstraintValue {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConstraintValue::Number(number) => write!(formatter, "{number}"),
+            ConstraintValue::String(string) => write!(formatter, "\"{string}\""),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
@@ -833,6 +1039,14 @@ pub struct RobloxClass {
     pub superclass: String,
     pub events: Vec<String>,
     pub properties: Vec<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub property_types: BTreeMap<String, RobloxProperty>,
+    /// The security context needed to use a member, such as `PluginSecurity`, for members that need one.
+    /// For properties, this is the security needed to read them.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub security: BTreeMap<String, String>,
 }
 
 impl RobloxClass {
@@ -863,6 +1077,120 @@ impl RobloxClass {
             false
         }
     }
+
+    /// Returns the type of the property, searching superclasses as well.
+    /// Returns None if the property doesn't exist, or if the standard library predates property types.
+    pub fn property<'a>(
+        &'a self,
+        roblox_classes: &'a BTreeMap<String, RobloxClass>,
+        property: &str,
+    ) -> Option<&'a RobloxProperty> {
+        match self.property_types.get(property) {
+            Some(roblox_property) => Some(roblox_property),
+            None => roblox_classes
+                .get(&self.superclass)?
+                .property(roblox_classes, property),
+        }
+    }
+
+    /// Returns the security context needed to use a member, searching superclasses as well.
+    pub fn member_security<'a>(
+        &'a self,
+        roblox_classes: &'a BTreeMap<String, RobloxClass>,
+        member: &str,
+    ) -> Option<&'a str> {
+        match self.security.get(member) {
+            Some(security) => Some(security),
+            None => roblox_classes
+                .get(&self.superclass)?
+                .member_security(roblox_classes, member),
+        }
+    }
+
+    pub fn is_subclass_of(
+        &self,
+        roblox_classes: &BTreeMap<String, RobloxClass>,
+        class_name: &str,
+    ) -> bool {
+        if self.superclass == class_name {
+            true
+        } else if let Some(superclass) = roblox_classes.get(&self.superclass) {
+            superclass.is_subclass_of(roblox_classes, class_name)
+        } else {
+            false
+        }
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RobloxProperty {
+    #[serde(rename = "type")]
+    pub value_type: RobloxValueType,
+
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_false")]
+    pub read_only: bool,
+
+    /// The security context needed to write to the property, such as `PluginSecurity`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security: Option<String>,
+}
+
+/// The type of a Roblox property.
+/// Written in the standard library as `bool`, `number`, `string`, a data type such as `Vector3`,
+/// an enum such as `Enum.Material`, or a class such as `Class.BasePart`.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum RobloxValueType {
+    Bool,
+    Number,
+    String,
+    DataType(String),
+    Enum(String),
+    Class(String),
+}
+
+impl From<String> for RobloxValueType {
+    fn from(name: String) -> Self {
+        match name.as_str() {
+            "bool" => RobloxValueType::Bool,
+            "number" => RobloxValueType::Number,
+            "string" => RobloxValueType::String,
+            _ => {
+                if let Some(enum_name) = name.strip_prefix("Enum.") {
+                    RobloxValueType::Enum(enum_name.to_owned())
+                } else if let Some(class_name) = name.strip_prefix("Class.") {
+                    RobloxValueType::Class(class_name.to_owned())
+                } else {
+                    RobloxValueType::DataType(name)
+                }
+            }
+        }
+    }
+}
+
+impl From<RobloxValueType> for String {
+    fn from(value_type: RobloxValueType) -> Self {
+        match value_type {
+            RobloxValueType::Class(name) => format!("Class.{name}"),
+            other => other.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for RobloxValueType {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RobloxValueType::Bool => write!(formatter, "bool"),
+            RobloxValueType::Number => write!(formatter, "number"),
+            RobloxValueType::String => write!(formatter, "string"),
+            RobloxValueType::DataType(name) | RobloxValueType::Class(name) => {
+                write!(formatter, "{name}")
+            }
+            RobloxValueType::Enum(name) => write!(formatter, "Enum.{name}"),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -877,6 +1205,83 @@ mod tests {
     fn valid_serde() {
         StandardLibrary::from_name("lua51").expect("lua51.toml wasn't found");
         StandardLibrary::from_name("lua52").expect("lua52.toml wasn't found");
+        StandardLibrary::from_name("lua53").expect("lua53.toml wasn't found");
+        StandardLibrary::from_name("lua54").expect("lua54.toml wasn't found");
+        StandardLibrary::from_name("luajit").expect("luajit.toml wasn't found");
+    }
+
+    #[test]
+    fn lua_versions_from_base() {
+        assert_eq!(
+            StandardLibrary::from_name("lua53").unwrap().lua_versions,
+            vec![LuaVersion::Lua53]
+        );
+
+        assert_eq!(
+            StandardLibrary::from_name("luajit").unwrap().lua_versions,
+            vec![LuaVersion::LuaJIT]
+        );
+    }
+
+    #[test]
+    fn lua_versions_from_chain() {
+        // `std = "lua52+luajit"` uses the versions of the last library that specifies any
+        let mut standard_library = StandardLibrary::from_name("lua52").unwrap();
+        standard_library.extend(StandardLibrary::from_name("luajit").unwrap());
+        assert_eq!(standard_library.lua_versions, vec![LuaVersion::LuaJIT]);
+
+        standard_library.extend(StandardLibrary::default());
+        assert_eq!(standard_library.lua_versions, vec![LuaVersion::LuaJIT]);
+    }
+
+    #[test]
+    fn field_availability() {
+        let mut standard_library = StandardLibrary::from_name("lua54").unwrap();
+
+        let warn = standard_library.find_global(&["warn"]).unwrap().clone();
+        let tointeger = standard_library
+            .find_global(&["math", "tointeger"])
+            .unwrap()
+            .clone();
+
+        assert_eq!(
+            standard_library.field_availability(&warn),
+            FieldAvailability::Available
+        );
+
+        standard_library.lua_versions = vec![LuaVersion::Lua51, LuaVersion::Lua54];
+        assert_eq!(
+            standard_library.field_availability(&tointeger),
+            FieldAvailability::Partial(
+                "available since Lua 5.3, but the standard library is also for Lua 5.1".to_owned()
+            )
+        );
+
+        standard_library.lua_versions = vec![LuaVersion::LuaJIT];
+        assert_eq!(
+            standard_library.field_availability(&warn),
+            FieldAvailability::Unavailable(
+                "available since Lua 5.4, but the standard library is for LuaJIT".to_owned()
+            )
+        );
+
+        let removed = Field {
+            removed_in: Some(LuaVersion::Lua52),
+            ..Field::from_field_kind(FieldKind::Any)
+        };
+
+        assert_eq!(
+            standard_library.field_availability(&removed),
+            FieldAvailability::Available
+        );
+
+        standard_library.lua_versions = vec![LuaVersion::Lua53];
+        assert_eq!(
+            standard_library.field_availability(&removed),
+            FieldAvailability::Unavailable(
+                "removed in Lua 5.2, but the standard library is for Lua 5.3".to_owned()
+            )
+        );
     }
 
     #[test]
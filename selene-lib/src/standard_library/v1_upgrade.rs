@@ -42,6 +42,7 @@ impl From<v1::Argument> for Argument {
             argument_type: v1_argument.argument_type.into(),
             observes: Observes::ReadWrite,
             deprecated: None,
+            constraints: ArgumentConstraints::default(),
         }
     }
 }
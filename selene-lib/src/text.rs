@@ -29,3 +29,47 @@ pub fn english_list<'a>(list: &[&'a str]) -> Cow<'a, str> {
         }
     }
 }
+
+/// Finds the candidate closest to the given text, ignoring case, if any are close enough to likely be a typo
+pub fn closest_match<'a>(
+    text: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let text_lowercase = text.to_lowercase();
+    let max_distance = (text.chars().count() / 3).max(1);
+
+    candidates
+        .into_iter()
+        .filter(|candidate| *candidate != text)
+        .map(|candidate| {
+            (
+                edit_distance(&text_lowercase, &candidate.to_lowercase()),
+                candidate,
+            )
+        })
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+// Levenshtein distance, counted in characters
+fn edit_distance(lhs: &str, rhs: &str) -> usize {
+    let rhs = rhs.chars().collect::<Vec<_>>();
+    let mut previous_row = (0..=rhs.len()).collect::<Vec<_>>();
+
+    for (lhs_index, lhs_char) in lhs.chars().enumerate() {
+        let mut current_row = vec![lhs_index + 1];
+
+        for (rhs_index, rhs_char) in rhs.iter().enumerate() {
+            let substitution = previous_row[rhs_index] + usize::from(lhs_char != *rhs_char);
+            let deletion = previous_row[rhs_index + 1] + 1;
+            let insertion = current_row[rhs_index] + 1;
+
+            current_row.push(substitution.min(deletion).min(insertion));
+        }
+
+        previous_row = current_row;
+    }
+
+    previous_row[rhs.len()]
+}
@@ -0,0 +1,46 @@
+local count, name = 1, "foo"
+
+-- Fine
+print(string.format("%d items for %s", count, name))
+print(string.format("%5.2f%%", 1.5))
+print(string.format("%-5d|%+d|% d|%#x|%05d", 1, 2, 3, 4, 5))
+print(string.format("%i", count))
+print(string.format("%d", "10"))
+print(string.format("%s %s", ...))
+print(string.format("%s %s %s", name, unpack({})))
+print(string.format("no specifiers"))
+print(("%d items"):format(count))
+print(string.format([[%s]], name))
+print(string.format("\37d", count))
+
+-- Not enough arguments
+print(string.format("%d items for %s", count))
+print(("%s and %s"):format(name))
+print(string.format("%s"))
+print(string.format("%s %s", (unpack({}))))
+print(string.format("%s %s", (...)))
+
+-- Too many arguments
+print(string.format("%d items", count, name))
+print(string.format("no specifiers", 1, 2))
+
+-- Invalid specifiers
+print(string.format("%y", count))
+print(string.format("%a", 1.5))
+print(string.format("%*", count))
+print(string.format("100%", count))
+print(string.format("%123d", count))
+print(string.format("%------d", count))
+
+-- Wrong constant types
+print(string.format("%d", "hello"))
+print(string.format("%5.1f", true))
+print(string.format("%x", {}))
+print(string.format("%s", nil))
+print(string.format("%s", -1))
+print(string.format("%d", 1.5))
+print(string.format("%d", "1_000"))
+
+-- Shadowed string library
+local string = { format = function() end }
+print(string.format("%d"))
@@ -0,0 +1,8 @@
+print(string.format("%d", 1.5))
+print(string.format("%d", 3.0))
+print(string.format("%a", 1.5))
+print(string.format("%s", nil))
+print(string.format("%p", {}))
+print(string.format("%q", 1))
+print(string.format("%q", true))
+print(string.format("%q", nil))
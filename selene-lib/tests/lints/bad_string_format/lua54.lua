@@ -0,0 +1,7 @@
+print(string.format("%q", "quoted"))
+print(string.format("%q", 1))
+print(string.format("%q", 1.5))
+print(string.format("%q", true))
+print(string.format("%q", nil))
+print(string.format("%q", {}))
+print(string.format("%p", {}))
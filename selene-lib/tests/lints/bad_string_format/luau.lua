@@ -0,0 +1,7 @@
+local count = 1
+
+print(string.format("%* items", count))
+print(string.format("%5*", count))
+print(string.format("%a", 1.5))
+print(string.format("%q", "quoted"))
+print(string.format("%s", nil))
@@ -0,0 +1,51 @@
+local text = "hello world"
+
+-- Fine
+print(string.find(text, "%w+"))
+print(string.match(text, "^(%w+) (%w+)$"))
+print(text:gmatch("[%a_][%w_]*"))
+print(text:gsub("(%w+)", "<%1>"))
+print(text:gsub("%w+", "%0 %1"))
+print(text:match("[]]"))
+print(text:match("[^%]]"))
+print(text:match("%b()"))
+print(text:match("%f[%w]%w+"))
+print(text:match("(a)%1"))
+print(text:match("()a()"))
+print(text:find("file.txt", 1, true))
+print(text:find("a.b", 1, plain))
+print(text:find("%s*"))
+print(text:gsub("foo", "100%%"))
+print(("a.b"):format())
+print(router:match("user-id"))
+print(self.text:find("a.b-c"))
+local reassigned = "hello"
+reassigned = getText()
+print(reassigned:match("[a-z"))
+
+-- Malformed patterns
+print(string.find(text, "100%"))
+print(text:match("[a-z"))
+print(text:match("[%]"))
+print(text:match("%b("))
+print(text:match("%fa"))
+print(text:match("(a)%2"))
+print(text:match("(a%1)"))
+print(text:match("a)"))
+print(text:match("(a"))
+print(text:gsub("(((((((((((((((((((((((((((((((((a)))))))))))))))))))))))))))))))))", ""))
+
+-- Replacement strings
+print(text:gsub("(a)(b)", "%3"))
+print(text:gsub("ab", "%2"))
+print(string.gsub(text, "ab", "%1"))
+
+-- Looks like a literal match
+print(text:find("file.txt"))
+print(text:match("my-name"))
+print(string.gsub(text, "v1.2", "v1.3"))
+print(text:find("file.txt", 1, false))
+
+-- Shadowed string library
+local string = {}
+string.find(text, "100%")
@@ -0,0 +1,5 @@
+local text = "hello world"
+
+print(text:gsub("(a)", "%1%%"))
+print(text:gsub("(a)", "%a"))
+print(text:gsub("(a)", "100%"))
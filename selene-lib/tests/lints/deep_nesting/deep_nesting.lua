@@ -0,0 +1,33 @@
+local function shallow()
+    if true then
+        for _ = 1, 10 do
+            print(1)
+        end
+    end
+end
+
+local function deep()
+    if true then
+        while true do
+            print(1)
+        end
+    else
+        for _ = 1, 10 do
+            do
+                print(2)
+            end
+        end
+    end
+end
+
+function Module:resetsInNestedFunctions()
+    if true then
+        return function()
+            if true then
+                if true then
+                    print(1)
+                end
+            end
+        end
+    end
+end
@@ -0,0 +1,38 @@
+local function short()
+    print(1)
+    print(2)
+end
+
+local function manyStatements()
+    print(1)
+    if true then
+        print(2)
+        print(3)
+    end
+    return function()
+        print("nested functions are counted separately")
+    end
+end
+
+function Module.manyLines()
+    print(1)
+
+
+
+    print(2)
+
+
+
+
+    print(3)
+end
+
+local callback = function()
+    for _ = 1, 10 do
+        print(1)
+        print(2)
+        print(3)
+        print(4)
+        print(5)
+    end
+end
@@ -0,0 +1,53 @@
+local MAX_PLAYERS = 10
+local maxRetries = 3
+local DEBUG_MODE = false
+local OFFSET = -1
+
+local counter = 0
+counter = counter + 1
+
+local state = 0
+state = 1
+
+local goodLocal = {}
+local bad_local = {}
+local HTTPServer = nil
+
+local function goodFunction(goodParam, bad_param, ...)
+    local innerValue = goodParam
+    local Inner_Value = bad_param
+    return innerValue, Inner_Value, ...
+end
+
+local function bad_function()
+end
+
+GoodGlobal = 1
+badGlobal = 2
+
+function globalFunction()
+end
+
+function Global_Function()
+end
+
+local Module = {}
+
+function Module.goodField()
+end
+
+function Module.Bad_Field()
+end
+
+function Module:GoodMethod(_unused)
+    return self
+end
+
+function Module:bad_method()
+end
+
+for index, some_value in ipairs({}) do
+    print(index, some_value)
+end
+
+print(MAX_PLAYERS, maxRetries, DEBUG_MODE, OFFSET, goodLocal, bad_local, HTTPServer, bad_function, _VERSION)
@@ -0,0 +1,15 @@
+local good = 1
+local notGood = 2
+local MAX = 3
+
+for i = 1, 10 do
+    for j = 1, 10 do
+        print(i, j)
+    end
+end
+
+local function process(good_param, badParam, _)
+    return good_param, badParam
+end
+
+print(good, notGood, MAX, process)
@@ -0,0 +1,13 @@
+local Roact = require(Packages.Roact)
+
+Roact.createElement("Part", {
+	Anchored = true,
+	Size = Vector3.new(1, 1, 1),
+})
+
+Roact.createElement("Part", {
+	Anchored = "true",
+	Size = Vector2.new(1, 1),
+	ClassName = "Folder",
+	[Roact.Event.Touched] = function() end,
+})
@@ -0,0 +1,40 @@
+local part = Instance.new("Part")
+part.Anchored = true
+part.Anchored = "true"
+part.Size = Vector3.new(1, 1, 1)
+part.Size = Vector2.new(1, 1)
+part.Size = nil
+part.Material = Enum.Material.Plastic
+part.Material = "Plastic"
+part.Material = Enum.Font.Arial
+part.Transparency = 0.5
+part.Transparency = "0.5"
+part.Transparency = "half"
+part.Name = 5
+part.Name = {}
+part.ClassName = "Folder"
+part.Parent = workspace
+part.Parent = nil
+part.Parent = "workspace"
+
+local model = Instance.new("Model")
+model.PrimaryPart = part
+model.PrimaryPart = Instance.new("Folder")
+
+workspace.Gravity = "high"
+
+local Lighting = game:GetService("Lighting")
+Lighting.ClockTime = 12
+Lighting.ClockTime = "noon"
+
+local reassigned = Instance.new("Part")
+reassigned = Instance.new("Folder")
+reassigned.Anchored = "true"
+
+local function setup(part)
+	part.Anchored = "true"
+end
+
+local unknown = getPart()
+unknown.Anchored = "true"
+part.DoesntExist = "true"
@@ -0,0 +1,2 @@
+local ReplicatedStorage = game:GetService("ReplicatedStorage")
+print(ReplicatedStorage.Missing)
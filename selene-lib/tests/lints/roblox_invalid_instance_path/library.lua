@@ -0,0 +1,2 @@
+print(script.Parent.Util, script.Parent.Utill)
+print(game:GetService("ReplicatedStorage").Anything)
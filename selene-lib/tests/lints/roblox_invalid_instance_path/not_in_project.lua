@@ -0,0 +1,2 @@
+print(script.Parent.Anything)
+print(game:GetService("ReplicatedStorage").Shared.Utill)
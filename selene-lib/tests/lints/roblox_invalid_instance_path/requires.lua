@@ -0,0 +1,10 @@
+local ReplicatedStorage = game:GetService("ReplicatedStorage")
+
+require(ReplicatedStorage.Shared.Util)
+require(ReplicatedStorage.Shared.Config)
+require(script.Parent.Handlers)
+require(script.Parent.Bootstrap)
+require(ReplicatedStorage.Shared.Modules)
+require(ReplicatedStorage.Shared.Message)
+require(script.Parent.Main)
+require(ReplicatedStorage.Packages.Promise)
@@ -0,0 +1,27 @@
+local ReplicatedStorage = game:GetService("ReplicatedStorage")
+local Shared = ReplicatedStorage.Shared
+
+print(Shared.Util, Shared.Modules.Signal, Shared.Config, Shared.Message.Value)
+print(Shared.Utill)
+print(Shared.Modules.Signall)
+print(Shared:WaitForChild("Util"), Shared:WaitForChild("Missing"))
+print(Shared:FindFirstChild("Missing"))
+print(Shared["Util"], Shared["Missing"])
+print(Shared.Name, Shared.ChildAdded, Shared.Parent.Shared)
+
+-- Models and nodes without paths can have children that Rojo doesn't know about
+print(Shared.Assets.Anything, ReplicatedStorage.Anything, game.Anything)
+print(ReplicatedStorage.Packages.Promise)
+
+-- script is ServerScriptService.Server.Main
+print(script.Parent.Handlers.Chat, script.Parent.Bootstrap.Enabled)
+print(script.Parent.Handlerz)
+print(script.Parent.Parent.Server.Main)
+print(script.Parent.Handlers.Chat.Parent.Parent.Handlers.Missing)
+
+local Handlers = script.Parent.Handlers
+print(Handlers.Chat, Handlers.Missing)
+
+local reassigned = script.Parent.Handlers
+reassigned = Shared
+print(reassigned.Util)
@@ -0,0 +1,22 @@
+local part = Instance.new("Part")
+print(part.Name)
+print(part:GetDebugId())
+
+game:GetJobsInfo()
+game:SetPlaceId(1)
+
+local Players = game:GetService("Players")
+print(Players.LocalPlayer.Name)
+print(Players.LocalPlayer.OsPlatform)
+print(Players.LocalPlayer.CharacterAppearance)
+game:GetService("Players").LocalPlayer.CharacterAppearance = "appearance"
+
+local newScript = Instance.new("Script")
+newScript.Source = "print('hello')"
+print(newScript.Source)
+
+plugin:GetSetting("setting")
+
+local function unknown(instance)
+	instance:GetDebugId()
+end
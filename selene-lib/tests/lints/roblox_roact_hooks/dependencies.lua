@@ -0,0 +1,38 @@
+local React = require(Packages.React)
+
+local MAX = 10
+
+local function Counter(props)
+	local count, setCount = React.useState(0)
+	local ref = React.useRef()
+	local multiplier = props.multiplier * 2
+
+	React.useEffect(function()
+		setCount(count + multiplier)
+		ref.current = MAX
+	end, {})
+
+	React.useEffect(function()
+		print(props.value)
+	end, { props.value })
+
+	local doubled = React.useMemo(function()
+		return count * 2
+	end, { count })
+
+	local onClick = React.useCallback(function()
+		local local_value = 1
+		setCount(count + local_value + doubled)
+	end, { count })
+
+	-- No dependency array runs on every render
+	React.useEffect(function()
+		print(count)
+	end)
+
+	React.useEffect(function()
+		print(count, multiplier)
+	end, { props.other })
+
+	return onClick
+end
@@ -0,0 +1,72 @@
+local React = require(Packages.React)
+local Roact = require(Packages.Roact)
+
+local function Counter(props)
+	local count, setCount = React.useState(0)
+	local theme = useTheme()
+
+	if props.visible then
+		local hovered = React.useState(false)
+	end
+
+	for _ = 1, 3 do
+		Roact.useRef()
+	end
+
+	local label = props.label or React.useMemo(function()
+		return "Count"
+	end)
+
+	React.useEffect(function()
+		local value = React.useContext(props.context)
+	end)
+
+	if count > 10 then
+		return nil
+	end
+
+	local ref = React.useRef()
+
+	return React.createElement("TextLabel", {
+		Text = label .. count,
+	})
+end
+
+local function useCounter(initial)
+	local count, setCount = React.useState(initial)
+
+	while count < 0 do
+		useTheme()
+	end
+
+	return count, setCount
+end
+
+local function helper()
+	return React.useState(0)
+end
+
+React.useState(0)
+
+local Memoized = React.memo(function(props)
+	local value = React.useState(props.value)
+	return value
+end)
+
+local Forwarded = Roact.forwardRef(function(props, ref)
+	if props.x then
+		React.useImperativeHandle(ref, function()
+			return {}
+		end)
+	end
+end)
+
+function Components.Button(props)
+	local pressed = React.useState(false)
+end
+
+function Components:render()
+	local state = React.useState(0)
+end
+
+local user = useless()
@@ -0,0 +1,22 @@
+between(0)
+between(255)
+between(-1)
+between(256)
+between(x)
+
+integer(1)
+integer(1.5)
+
+divide(2)
+divide(0)
+
+name("a")
+name("")
+name("hello")
+
+option("left")
+option("up")
+option "up"
+
+size(4)
+size(3)
@@ -0,0 +1,24 @@
+local Players = game:GetService("Players")
+local Workspace = game:GetService("Workspace")
+
+game:GetService("Players ")
+game:GetService("replicatedStorage")
+game:GetService("Lighting")
+
+Players:GetPlayers()
+Players:GetPlayerz()
+Players.GetPlayers()
+Players:GetPlayers(1)
+Players:LocalPlayer()
+Players:FindFirstChild("Player1")
+
+-- Services without structs aren't typed
+Workspace:Anything()
+
+local reassigned = game:GetService("Players")
+reassigned = {}
+reassigned:GetPlayerz()
+
+local function shadowed(Players)
+	Players:GetPlayerz()
+end
@@ -0,0 +1,8 @@
+print(math.tointeger(1.0))
+print(utf8.charpattern)
+warn("@on")
+
+local move = table.move
+local close = coroutine.close
+
+print(math.floor(1.5))
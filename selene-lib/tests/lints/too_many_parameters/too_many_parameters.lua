@@ -0,0 +1,10 @@
+local function fine(a, b, c, ...)
+end
+
+function Module.tooMany(a, b, c, d)
+end
+
+function Module:method(a, b, c, d, e)
+end
+
+call(function(a, b, c, d) end)
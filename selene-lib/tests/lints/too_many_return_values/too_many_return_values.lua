@@ -0,0 +1,17 @@
+local function fine()
+    return 1, 2
+end
+
+local function tooMany(x)
+    if x then
+        return 1, 2, 3
+    end
+
+    return function()
+        return 1, 2, 3, 4
+    end
+end
+
+function Module.last()
+    return 1, 2, 3
+end
@@ -18,7 +18,12 @@ fn capabilities() -> serde_json::Value {
 
 pub fn print_capabilities(display_style: DisplayStyle) {
     match display_style {
-        DisplayStyle::Quiet | DisplayStyle::Rich => {
+        DisplayStyle::Quiet
+        | DisplayStyle::Rich
+        | DisplayStyle::Checkstyle
+        | DisplayStyle::Junit
+        | DisplayStyle::Gitlab
+        | DisplayStyle::Github => {
             println!("{}", serde_yaml::to_string(&capabilities()).unwrap());
         }
 
@@ -0,0 +1,257 @@
+//! `--diff-base`, which only reports diagnostics on lines that changed since a git revision,
+//! so that old code doesn't have to be fixed before new code can be checked.
+use std::{
+    collections::HashMap,
+    fs,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use selene_lib::lints::{Diagnostic, Label};
+
+pub struct ChangedLines {
+    // Keyed by canonicalized path, with 1-indexed line ranges
+    files: HashMap<PathBuf, Vec<RangeInclusive<usize>>>,
+    include_secondary_labels: bool,
+}
+
+impl ChangedLines {
+    /// Runs `git diff` against `base` to find the lines that changed in every file. Every line of
+    /// an untracked file counts as changed, unless git ignores the file.
+    pub fn from_git(base: &str, include_secondary_labels: bool) -> Result<Self, String> {
+        let root = git(&["rev-parse", "--show-toplevel"])?;
+        let root = Path::new(root.trim());
+
+        // The prefixes are set explicitly in case the user's config changes them
+        let diff = git(&[
+            "-c",
+            "core.quotePath=false",
+            "diff",
+            "--unified=0",
+            "--no-color",
+            "--no-ext-diff",
+            "--src-prefix=a/",
+            "--dst-prefix=b/",
+            base,
+            "--",
+        ])?;
+
+        // Listed from the root, since ls-files only lists files in the current directory otherwise
+        let untracked = git(&[
+            "-C",
+            &root.to_string_lossy(),
+            "ls-files",
+            "--others",
+            "--exclude-standard",
+            "-z",
+        ])?;
+
+        let untracked_files = untracked
+            .split('\0')
+            .filter(|path| !path.is_empty())
+            .map(|path| (PathBuf::from(path), vec![1..=usize::MAX]));
+
+        Ok(ChangedLines {
+            files: parse_unified_diff(&diff)
+                .into_iter()
+                .chain(untracked_files)
+                .filter_map(|(path, lines)| Some((fs::canonicalize(root.join(path)).ok()?, lines)))
+                .collect(),
+            include_secondary_labels,
+        })
+    }
+
+    pub fn contains_file(&self, path: &Path) -> bool {
+        fs::canonicalize(path).is_ok_and(|path| self.files.contains_key(&path))
+    }
+
+    /// Whether the diagnostic points to a changed line. `line_of` converts a byte offset to a 1-indexed line.
+    pub fn contains_diagnostic(
+        &self,
+        path: &Path,
+        diagnostic: &Diagnostic,
+        line_of: impl Fn(u32) -> usize,
+    ) -> bool {
+        let Some(changed_lines) = fs::canonicalize(path)
+            .ok()
+            .and_then(|path| self.files.get(&path))
+        else {
+            return false;
+        };
+
+        let intersects = |label: &Label| {
+            // The end of a range is exclusive, so a label ending at the start of a line isn't on it
+            let end = label.range.1.saturating_sub(1).max(label.range.0);
+            let (start, end) = (line_of(label.range.0), line_of(end));
+            changed_lines
+                .iter()
+                .any(|lines| start <= *lines.end() && end >= *lines.start())
+        };
+
+        intersects(&diagnostic.primary_label)
+            || (self.include_secondary_labels && diagnostic.secondary_labels.iter().any(intersects))
+    }
+}
+
+fn git(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .map_err(|error| format!("couldn't run git: {error}"))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "`git {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+// Parses `start,count` from a hunk header, where the count is left out when it's 1
+fn parse_hunk_range(range: &str) -> Option<(usize, usize)> {
+    match range.split_once(',') {
+        Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+        None => Some((range.parse().ok()?, 1)),
+    }
+}
+
+// Finds the lines added or changed in every file from the output of `git diff --unified=0`.
+// Files that only had lines removed are included, but have no lines.
+fn parse_unified_diff(diff: &str) -> HashMap<PathBuf, Vec<RangeInclusive<usize>>> {
+    let mut files = HashMap::new();
+    let mut current_file = None;
+    // The lines left in the current hunk, from the old and new file. Until these run out, lines
+    // are content, even if they look like headers, such as an added line starting with `++ `.
+    let (mut old_remaining, mut new_remaining): (usize, usize) = (0, 0);
+
+    for line in diff.lines() {
+        if old_remaining > 0 || new_remaining > 0 {
+            match line.chars().next() {
+                Some('+') => new_remaining = new_remaining.saturating_sub(1),
+                Some('-') => old_remaining = old_remaining.saturating_sub(1),
+                // "\ No newline at end of file"
+                Some('\\') => {}
+                _ => {
+                    old_remaining = old_remaining.saturating_sub(1);
+                    new_remaining = new_remaining.saturating_sub(1);
+                }
+            }
+
+            continue;
+        }
+
+        if let Some(path) = line.strip_prefix("+++ ") {
+            // Deleted files are `+++ /dev/null`
+            current_file = path.strip_prefix("b/").map(PathBuf::from);
+
+            if let Some(path) = &current_file {
+                files.entry(path.clone()).or_insert_with(Vec::new);
+            }
+
+            continue;
+        }
+
+        // @@ -start,count +start,count @@
+        let Some(hunk) = line.strip_prefix("@@ ") else {
+            continue;
+        };
+
+        let mut ranges = hunk.split_whitespace();
+        let (Some(removed), Some(added)) = (
+            ranges.next().and_then(|range| range.strip_prefix('-')),
+            ranges.next().and_then(|range| range.strip_prefix('+')),
+        ) else {
+            continue;
+        };
+
+        let (Some((_, removed_count)), Some((start, count))) =
+            (parse_hunk_range(removed), parse_hunk_range(added))
+        else {
+            continue;
+        };
+
+        old_remaining = removed_count;
+        new_remaining = count;
+
+        if let Some(path) = &current_file {
+            if count > 0 {
+                files
+                    .get_mut(path)
+                    .expect("file was added when its header was read")
+                    .push(start..=start + count - 1);
+            }
+        }
+    }
+
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_unified_diff() {
+        let diff = "\
+diff --git a/src/init.lua b/src/init.lua
+index 1234567..89abcde 100644
+--- a/src/init.lua
++++ b/src/init.lua
+@@ -3 +3 @@ local a = 1
+-local b = 2
++local b = 3
+@@ -10,0 +11,3 @@ end
++local c = 4
++local d = 5
++local e = 6
+@@ -20,2 +22,0 @@
+-print(c)
+-print(d)
+@@ -30 +30,2 @@
+-- removed comment
++++ counter
++--- decremented
+@@ -40,0 +42 @@
++local f = 7
+diff --git a/src/removed.lua b/src/removed.lua
+deleted file mode 100644
+--- a/src/removed.lua
++++ /dev/null
+@@ -1 +0,0 @@
+-return nil
+";
+
+        let files = parse_unified_diff(diff);
+
+        assert_eq!(files.len(), 1);
+        // `++ counter` and `-- removed comment` are content, not the headers of another file
+        assert_eq!(
+            files[Path::new("src/init.lua")],
+            vec![3..=3, 11..=13, 30..=31, 42..=42]
+        );
+    }
+
+    #[test]
+    fn test_contains_diagnostic() {
+        let path = Path::new("Cargo.toml");
+        let changed_lines = ChangedLines {
+            files: HashMap::from([(fs::canonicalize(path).unwrap(), vec![2..=2])]),
+            include_secondary_labels: false,
+        };
+
+        // Every line is 10 bytes long
+        let line_of = |byte: u32| byte as usize / 10 + 1;
+        let diagnostic =
+            |start: u32, end: u32| Diagnostic::new("test", String::new(), Label::new((start, end)));
+
+        assert!(changed_lines.contains_diagnostic(path, &diagnostic(5, 15), line_of));
+        assert!(changed_lines.contains_diagnostic(path, &diagnostic(10, 10), line_of));
+
+        // The end of a label is exclusive, so a whole line 1 doesn't touch line 2
+        assert!(!changed_lines.contains_diagnostic(path, &diagnostic(0, 10), line_of));
+    }
+}
@@ -0,0 +1,585 @@
+//! `selene init`, which writes a selene.toml for the project in the current directory,
+//! picking a standard library and files to exclude by looking at the project.
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use selene_lib::{
+    lint_defaults,
+    lints::Severity,
+    standard_library::{Field, FieldKind, StandardLibrary},
+    Checker, CheckerConfig,
+};
+
+use crate::{
+    lua_version,
+    migrate_luacheck::{self, LuaValue},
+    standard_library,
+};
+
+const CONFIG_FILE: &str = "selene.toml";
+
+// The name of the standard library written by --generate-std
+const GENERATED_STD_NAME: &str = "globals";
+
+// Directories that package managers install dependencies into, which aren't the project's own code
+const VENDORED_DIRECTORIES: &[&str] = &[
+    // Wally
+    "Packages",
+    "DevPackages",
+    "ServerPackages",
+    // LuaRocks
+    "lua_modules",
+    "vendor",
+];
+
+pub const BUSTED_GLOBALS: &[&str] = &[
+    "after_each",
+    "before_each",
+    "describe",
+    "finally",
+    "insulate",
+    "it",
+    "lazy_setup",
+    "lazy_teardown",
+    "mock",
+    "pending",
+    "setup",
+    "spy",
+    "strict_setup",
+    "strict_teardown",
+    "stub",
+    "teardown",
+];
+
+const TESTEZ_GLOBALS: &[&str] = &[
+    "afterAll",
+    "afterEach",
+    "beforeAll",
+    "beforeEach",
+    "describe",
+    "describeFOCUS",
+    "describeSKIP",
+    "expect",
+    "FIXME",
+    "FOCUS",
+    "it",
+    "itFIXME",
+    "itFOCUS",
+    "itSKIP",
+    "SKIP",
+];
+
+struct SourceFile {
+    // Relative to the project directory
+    path: PathBuf,
+    contents: String,
+}
+
+impl SourceFile {
+    fn is_vendored(&self) -> bool {
+        self.path.components().any(|component| {
+            VENDORED_DIRECTORIES
+                .iter()
+                .any(|directory| component.as_os_str() == *directory)
+        })
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Detected {
+    std: String,
+    exclude: Vec<String>,
+    uses_test_framework: bool,
+    // Why the std and exclude were picked, which is shown to the user and written into selene.toml
+    reasons: Vec<String>,
+}
+
+// The Lua version of an interpreter in a shebang, such as `#!/usr/bin/env lua5.3`
+fn shebang_std(contents: &str) -> Option<&'static str> {
+    let shebang = contents.lines().next()?.strip_prefix("#!")?;
+
+    if shebang.contains("luajit") {
+        return Some("luajit");
+    }
+
+    [
+        ("lua5.1", "lua51"),
+        ("lua5.2", "lua52"),
+        ("lua5.3", "lua53"),
+        ("lua5.4", "lua54"),
+        ("lua51", "lua51"),
+        ("lua52", "lua52"),
+        ("lua53", "lua53"),
+        ("lua54", "lua54"),
+    ]
+    .into_iter()
+    .find_map(|(interpreter, std)| shebang.contains(interpreter).then_some(std))
+}
+
+// Whether the code has `require("ffi")`, LuaJIT's foreign function interface, in any of the ways it can be written
+fn requires_ffi(contents: &str) -> bool {
+    contents.match_indices("require").any(|(index, _)| {
+        let rest = contents[index + "require".len()..].trim_start_matches([' ', '\t', '(']);
+
+        ['"', '\''].into_iter().any(|quote| {
+            rest.strip_prefix(quote)
+                .and_then(|rest| rest.strip_prefix("ffi"))
+                .is_some_and(|rest| rest.starts_with(quote))
+        })
+    })
+}
+
+// Whether `name` is called anywhere, such as `describe("thing", function()`
+fn calls_global(contents: &str, name: &str) -> bool {
+    contents.match_indices(name).any(|(index, _)| {
+        let is_identifier = |character: char| character.is_alphanumeric() || character == '_';
+
+        let before = contents[..index].chars().next_back();
+        let after = contents[index + name.len()..].trim_start().chars().next();
+
+        !before.is_some_and(|character| {
+            is_identifier(character) || character == '.' || character == ':'
+        }) && matches!(after, Some('(' | '"' | '\''))
+    })
+}
+
+// The std from a `.luacheckrc`, such as `std = "lua51+busted"`. Luacheck's stds that don't have
+// a selene equivalent are returned separately.
+fn luacheckrc_std(contents: &str) -> Option<(Option<&'static str>, Vec<String>)> {
+    let (options, _) = migrate_luacheck::read_luacheckrc(contents).ok()?;
+    let Some(LuaValue::String(value)) = options.fields.get("std") else {
+        return None;
+    };
+
+    let mut std = None;
+    let mut unknown = Vec::new();
+
+    // Test globals are handled separately
+    for segment in value.split('+').filter(|segment| *segment != "busted") {
+        match migrate_luacheck::selene_std(segment) {
+            Some(segment_std) => {
+                std.get_or_insert(segment_std);
+            }
+
+            None => unknown.push(segment.to_owned()),
+        }
+    }
+
+    Some((std, unknown))
+}
+
+fn detect(root_files: &[String], files: &[SourceFile]) -> Detected {
+    let mut detected = Detected::default();
+
+    let has_root_file = |name: &str| root_files.iter().any(|file| file == name);
+
+    let roblox_std = if cfg!(feature = "roblox") {
+        "roblox"
+    } else {
+        "luau"
+    };
+
+    let luacheckrc = files
+        .iter()
+        .find(|file| file.path == Path::new(".luacheckrc"));
+    let source_files = files
+        .iter()
+        .filter(|file| file.path != Path::new(".luacheckrc") && !file.is_vendored())
+        .collect::<Vec<_>>();
+
+    let project_file = root_files
+        .iter()
+        .find(|file| file.ends_with(".project.json"));
+    let luau_file = source_files.iter().find(|file| {
+        file.path
+            .extension()
+            .is_some_and(|extension| extension == "luau")
+    });
+    let ffi_file = source_files
+        .iter()
+        .find(|file| requires_ffi(&file.contents));
+    let shebang = source_files
+        .iter()
+        .find_map(|file| Some((file, shebang_std(&file.contents)?)));
+
+    if let Some(project_file) = project_file {
+        detected.std = roblox_std.to_owned();
+        detected.reasons.push(format!(
+            "{project_file} is a Rojo project, so std is {roblox_std}"
+        ));
+    } else if let Some(luau_file) = luau_file {
+        detected.std = roblox_std.to_owned();
+        detected.reasons.push(format!(
+            "{} is Luau, so std is {roblox_std}",
+            luau_file.path.display()
+        ));
+    } else if let Some((Some(std), _)) = luacheckrc.and_then(|file| luacheckrc_std(&file.contents))
+    {
+        detected.std = std.to_owned();
+        detected
+            .reasons
+            .push(format!(".luacheckrc uses the {std} std, so std is {std}"));
+    } else if let Some(ffi_file) = ffi_file {
+        detected.std = "luajit".to_owned();
+        detected.reasons.push(format!(
+            "{} requires LuaJIT's ffi, so std is luajit",
+            ffi_file.path.display()
+        ));
+    } else if let Some((file, std)) = shebang {
+        detected.std = std.to_owned();
+        detected.reasons.push(format!(
+            "{} has a shebang for {std}, so std is {std}",
+            file.path.display()
+        ));
+    } else {
+        detected.std = "lua51".to_owned();
+        detected
+            .reasons
+            .push("nothing specific to a Lua version was found, so std is lua51".to_owned());
+    }
+
+    if let Some((_, unknown)) = luacheckrc.and_then(|file| luacheckrc_std(&file.contents)) {
+        for std in unknown {
+            detected.reasons.push(format!(
+                ".luacheckrc uses the {std} std, which selene doesn't have, so its globals will need a standard library"
+            ));
+        }
+    }
+
+    if luacheckrc.is_some() {
+        detected.reasons.push(
+            "run `selene migrate-luacheck --force` to bring over the globals and ignored warnings of .luacheckrc".to_owned(),
+        );
+    }
+
+    if let Some(test_file) = source_files
+        .iter()
+        .find(|file| calls_global(&file.contents, "describe") && calls_global(&file.contents, "it"))
+    {
+        detected.uses_test_framework = true;
+        detected.reasons.push(format!(
+            "{} uses test framework globals such as describe and it",
+            test_file.path.display()
+        ));
+    }
+
+    for directory in VENDORED_DIRECTORIES {
+        let is_used = files.iter().any(|file| file.path.starts_with(directory));
+
+        if is_used || has_root_file(directory) {
+            detected.exclude.push(format!("**/{directory}/**"));
+            detected.reasons.push(format!(
+                "{directory} has installed packages, so it's excluded"
+            ));
+        }
+    }
+
+    detected
+}
+
+// Every Lua and Luau file in the project, skipping anything ignored by .gitignore and similar.
+// .luacheckrc is included, since it's Lua too.
+fn collect_files(directory: &Path) -> Vec<SourceFile> {
+    let mut files = Vec::new();
+
+    for entry in ignore::WalkBuilder::new(directory)
+        .hidden(false)
+        .require_git(false)
+        .add_custom_ignore_filename(".seleneignore")
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .build()
+        .flatten()
+    {
+        let path = entry.path();
+        let is_lua = path
+            .extension()
+            .is_some_and(|extension| extension == "lua" || extension == "luau")
+            || entry.file_name() == ".luacheckrc";
+
+        if !is_lua || !path.is_file() {
+            continue;
+        }
+
+        // Files that aren't UTF-8 can't be checked by selene either
+        let Ok(contents) = fs::read_to_string(path) else {
+            continue;
+        };
+
+        files.push(SourceFile {
+            path: path.strip_prefix(directory).unwrap_or(path).to_path_buf(),
+            contents,
+        });
+    }
+
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    files
+}
+
+// Globals that the project reads, but the std doesn't define
+fn undefined_globals(
+    std: &str,
+    directory: &Path,
+    files: &[SourceFile],
+) -> Result<BTreeSet<String>, String> {
+    let config = CheckerConfig::<toml::value::Value> {
+        std: Some(std.to_owned()),
+        ..CheckerConfig::default()
+    };
+
+    let standard_library =
+        standard_library::collect_standard_library(&config, std, directory, &None)
+            .map_err(|error| error.to_string())?
+            .unwrap_or_default();
+
+    let lua_version = lua_version(&standard_library);
+    let checker = Checker::new(config, standard_library).map_err(|error| error.to_string())?;
+
+    let mut globals = BTreeSet::new();
+
+    for file in files {
+        if file.is_vendored() || file.path == Path::new(".luacheckrc") {
+            continue;
+        }
+
+        // Files that don't parse are reported when selene is run
+        let Ok(ast) = full_moon::parse_fallible(&file.contents, lua_version).into_result() else {
+            continue;
+        };
+
+        for checker_diagnostic in checker.test_on(&ast) {
+            let diagnostic = checker_diagnostic.diagnostic;
+            if diagnostic.code != "undefined_variable" {
+                continue;
+            }
+
+            let (start, end) = diagnostic.primary_label.range;
+            if let Some(name) = file.contents.get(start as usize..end as usize) {
+                globals.insert(name.to_owned());
+            }
+        }
+    }
+
+    Ok(globals)
+}
+
+fn generated_std(std: &str, globals: BTreeSet<String>) -> String {
+    let mut library = StandardLibrary::default();
+    library.name = Some(GENERATED_STD_NAME.to_owned());
+    library.globals = globals
+        .into_iter()
+        .map(|global| (global, Field::from_field_kind(FieldKind::Any)))
+        .collect::<BTreeMap<_, _>>();
+
+    format!(
+        "# Globals the project uses that {std} doesn't define, generated by `selene init --generate-std`.\n\
+         # `any: true` allows any use of the global, see the standard library documentation to describe them further.\n\
+         {}",
+        serde_yaml::to_string(&library).expect("couldn't serialize standard library")
+    )
+}
+
+fn toml_string(text: &str) -> String {
+    toml::Value::String(text.to_owned()).to_string()
+}
+
+fn config_contents(detected: &Detected) -> String {
+    let mut contents = String::new();
+
+    writeln!(contents, "# Generated by `selene init`:").unwrap();
+    for reason in &detected.reasons {
+        writeln!(contents, "# - {reason}").unwrap();
+    }
+
+    writeln!(contents).unwrap();
+    writeln!(contents, "std = {}", toml_string(&detected.std)).unwrap();
+
+    let exclude = detected
+        .exclude
+        .iter()
+        .map(|pattern| toml_string(pattern))
+        .collect::<Vec<_>>();
+    writeln!(contents, "exclude = [{}]", exclude.join(", ")).unwrap();
+
+    let lint_defaults = lint_defaults();
+
+    writeln!(contents).unwrap();
+    writeln!(
+        contents,
+        "# Every lint with its default severity. Uncomment a line and change it to \"allow\", \"warn\", or \"deny\"."
+    )
+    .unwrap();
+    writeln!(contents, "[lints]").unwrap();
+    for lint in &lint_defaults {
+        let severity = match lint.severity {
+            Severity::Allow => "allow",
+            Severity::Error => "deny",
+            Severity::Warning => "warn",
+        };
+
+        writeln!(contents, "# {} = \"{severity}\"", lint.name).unwrap();
+    }
+
+    writeln!(contents).unwrap();
+    writeln!(
+        contents,
+        "# Every lint that can be configured, with its default config."
+    )
+    .unwrap();
+    writeln!(contents, "[config]").unwrap();
+    for lint in &lint_defaults {
+        if let Some(config) = &lint.config {
+            writeln!(
+                contents,
+                "# {} = {}",
+                lint.name,
+                toml::Value::Table(config.clone())
+            )
+            .unwrap();
+        }
+    }
+
+    contents
+}
+
+pub fn write_new_file(path: &Path, contents: &str, force: bool) -> Result<(), String> {
+    if !force && path.exists() {
+        return Err(format!(
+            "{} already exists, pass --force to overwrite it",
+            path.display()
+        ));
+    }
+
+    fs::write(path, contents).map_err(|error| format!("Couldn't write {}: {error}", path.display()))
+}
+
+/// Writes a selene.toml for the project in `directory`, and with `generate_std`,
+/// a standard library defining the globals the project uses that aren't defined anywhere.
+pub fn init(directory: &Path, generate_std: bool, force: bool) -> Result<(), String> {
+    let config_path = directory.join(CONFIG_FILE);
+    if !force && config_path.exists() {
+        return Err(format!(
+            "{CONFIG_FILE} already exists, pass --force to overwrite it"
+        ));
+    }
+
+    let root_files = fs::read_dir(directory)
+        .map_err(|error| format!("Couldn't read {}: {error}", directory.display()))?
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+
+    let files = collect_files(directory);
+    let mut detected = detect(&root_files, &files);
+
+    if generate_std {
+        let mut globals = undefined_globals(&detected.std, directory, &files)?;
+
+        if detected.uses_test_framework {
+            let test_globals = if detected.std == "roblox" {
+                TESTEZ_GLOBALS
+            } else {
+                BUSTED_GLOBALS
+            };
+
+            globals.extend(test_globals.iter().map(ToString::to_string));
+        }
+
+        let std_path = directory.join(format!("{GENERATED_STD_NAME}.yml"));
+        write_new_file(&std_path, &generated_std(&detected.std, globals), force)?;
+
+        detected.reasons.push(format!(
+            "{GENERATED_STD_NAME}.yml defines the globals that weren't defined anywhere"
+        ));
+        detected.std = format!("{}+{GENERATED_STD_NAME}", detected.std);
+    } else if detected.uses_test_framework {
+        detected.reasons.push(
+            "run `selene init --generate-std` to define the test framework's globals".to_owned(),
+        );
+    }
+
+    write_new_file(&config_path, &config_contents(&detected), force)?;
+
+    println!("Created {CONFIG_FILE}:");
+    for reason in &detected.reasons {
+        println!("- {reason}");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source_file(path: &str, contents: &str) -> SourceFile {
+        SourceFile {
+            path: PathBuf::from(path),
+            contents: contents.to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_detect() {
+        let detected = detect(
+            &["default.project.json".to_owned(), "Packages".to_owned()],
+            &[
+                source_file("Packages/_Index/ffi.lua", "local ffi = require('ffi')"),
+                source_file(
+                    "src/init.spec.lua",
+                    "describe(\"x\", function()\n\tit(\"y\", function() end)\nend)",
+                ),
+            ],
+        );
+
+        assert_eq!(
+            detected.std,
+            if cfg!(feature = "roblox") {
+                "roblox"
+            } else {
+                "luau"
+            }
+        );
+        assert_eq!(detected.exclude, vec!["**/Packages/**"]);
+        assert!(detected.uses_test_framework);
+
+        // Vendored code isn't used to pick the std
+        let detected = detect(
+            &[],
+            &[
+                source_file("vendor/ffi.lua", "local ffi = require \"ffi\""),
+                source_file("main.lua", "#!/usr/bin/env lua5.3\nprint(it)"),
+            ],
+        );
+
+        assert_eq!(detected.std, "lua53");
+        assert_eq!(detected.exclude, vec!["**/vendor/**"]);
+        assert!(!detected.uses_test_framework);
+
+        let detected = detect(
+            &[],
+            &[
+                source_file(".luacheckrc", "std = \"love+luajit\""),
+                source_file("main.lua", "#!/usr/bin/env lua5.3"),
+            ],
+        );
+
+        assert_eq!(detected.std, "luajit");
+        assert!(detected
+            .reasons
+            .iter()
+            .any(|reason| reason.contains("love")));
+    }
+
+    #[test]
+    fn test_generated_std() {
+        let std = generated_std("lua51", BTreeSet::from(["game".to_owned()]));
+
+        assert!(std.contains("name: globals"), "{std}");
+        assert!(std.contains("game:\n    any: true"), "{std}");
+        assert!(!std.contains("lua_versions"), "{std}");
+    }
+}
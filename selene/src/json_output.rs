@@ -1,4 +1,8 @@
-use std::io::{self, Write};
+use std::{
+    io::{self, Write},
+    path::Path,
+    sync::Mutex,
+};
 
 use codespan_reporting::diagnostic::{
     Diagnostic as CodespanDiagnostic, Label as CodespanLabel, LabelStyle, Severity,
@@ -20,6 +24,27 @@ pub struct JsonSummary {
     errors: usize,
     warnings: usize,
     parse_errors: usize,
+    skipped: usize,
+    files: Vec<JsonFileSummary>,
+}
+
+#[derive(Serialize)]
+pub struct JsonFileSummary {
+    filename: String,
+    errors: usize,
+    warnings: usize,
+    parse_errors: usize,
+}
+
+static FILE_SUMMARIES: Mutex<Vec<JsonFileSummary>> = Mutex::new(Vec::new());
+
+pub fn record_file_summary(filename: &Path, counts: crate::FileCounts) {
+    FILE_SUMMARIES.lock().unwrap().push(JsonFileSummary {
+        filename: filename.to_string_lossy().into_owned(),
+        errors: counts.errors,
+        warnings: counts.warnings,
+        parse_errors: counts.parse_errors,
+    });
 }
 
 #[derive(Serialize)]
@@ -96,19 +121,90 @@ pub fn diagnostic_to_json(
     }
 }
 
+// Data in workflow commands has to escape characters that GitHub would otherwise read as
+// the end of the command, and properties also can't contain the `,` and `:` separating them
+fn escape_github(text: &str, property: bool) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for character in text.chars() {
+        match character {
+            '%' => escaped.push_str("%25"),
+            '\r' => escaped.push_str("%0D"),
+            '\n' => escaped.push_str("%0A"),
+            ':' if property => escaped.push_str("%3A"),
+            ',' if property => escaped.push_str("%2C"),
+            _ => escaped.push(character),
+        }
+    }
+
+    escaped
+}
+
+/// Formats a diagnostic as a GitHub Actions workflow command, which is shown as an annotation on the line.
+pub fn diagnostic_to_github(
+    diagnostic: &CodespanDiagnostic<codespan::FileId>,
+    files: &codespan::Files<&str>,
+) -> String {
+    let JsonDiagnostic {
+        severity,
+        code,
+        mut message,
+        primary_label,
+        notes,
+        ..
+    } = diagnostic_to_json(diagnostic, files);
+
+    for note in notes {
+        message.push('\n');
+        message.push_str(&note);
+    }
+
+    let span = primary_label.span;
+
+    // Spans are 0-indexed, but GitHub expects 1-indexed positions with an inclusive end.
+    // Empty spans would end before they start, so they cover the column they're at instead.
+    let end_column = if span.end_line == span.start_line {
+        span.end_column.max(span.start_column + 1)
+    } else {
+        span.end_column
+    };
+
+    format!(
+        "::{} file={},line={},col={},endLine={},endColumn={},title={}::{}",
+        match severity {
+            Severity::Bug | Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note | Severity::Help => "notice",
+        },
+        escape_github(&crate::report::report_path(&primary_label.filename), true),
+        span.start_line + 1,
+        span.start_column + 1,
+        span.end_line + 1,
+        end_column,
+        escape_github(code.as_deref().unwrap_or("selene"), true),
+        escape_github(&message, false),
+    )
+}
+
 pub fn log_total_json(
     mut stdout: StandardStream,
     parse_errors: usize,
     lint_errors: usize,
     lint_warnings: usize,
+    skipped: usize,
 ) -> io::Result<()> {
+    let mut files = std::mem::take(&mut *FILE_SUMMARIES.lock().unwrap());
+    files.sort_by(|a, b| Path::new(&a.filename).cmp(Path::new(&b.filename)));
+
     writeln!(
         stdout,
         "{}",
         serde_json::to_string(&JsonOutput::Summary(JsonSummary {
             errors: lint_errors,
             warnings: lint_warnings,
-            parse_errors
+            parse_errors,
+            skipped,
+            files,
         }))?
     )?;
 
@@ -121,3 +217,41 @@ pub fn print_json(output: JsonOutput) {
         serde_json::to_string(&output).expect("unable to serialize json output")
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnostic_to_github() {
+        let mut files = codespan::Files::new();
+        let file_id = files.add("./src/a,b.lua", "local x = 1\nprint(1 / 0)\n");
+
+        let diagnostic = CodespanDiagnostic::warning()
+            .with_code("divide_by_zero")
+            .with_message("dividing by zero is not allowed, use math.huge instead")
+            .with_labels(vec![CodespanLabel::primary(file_id, 18..23)])
+            .with_notes(vec!["100% sure".to_owned()]);
+
+        assert_eq!(
+            diagnostic_to_github(&diagnostic, &files),
+            "::warning file=src/a%2Cb.lua,line=2,col=7,endLine=2,endColumn=11,title=divide_by_zero::dividing by zero is not allowed, use math.huge instead%0A100%25 sure"
+        );
+    }
+
+    #[test]
+    fn test_diagnostic_to_github_empty_span() {
+        let mut files = codespan::Files::new();
+        let file_id = files.add("a.lua", "print(\n");
+
+        let diagnostic = CodespanDiagnostic::error()
+            .with_code("parse_error")
+            .with_message("expected an expression")
+            .with_labels(vec![CodespanLabel::primary(file_id, 6..6)]);
+
+        assert_eq!(
+            diagnostic_to_github(&diagnostic, &files),
+            "::error file=a.lua,line=1,col=7,endLine=1,endColumn=7,title=parse_error::expected an expression"
+        );
+    }
+}
@@ -0,0 +1,446 @@
+//! Output that mimics luacheck's formatters, for editor plugins and other tools built for luacheck.
+//! Plain output is printed as each file is checked. The other formatters need every file,
+//! so files are collected here and written once at the end, like luacheck does.
+use std::{
+    io::{self, Write},
+    path::Path,
+    sync::Mutex,
+};
+
+use crate::{
+    opts::{LuacheckFormatter, Options},
+    report::escape_xml,
+};
+
+lazy_static::lazy_static! {
+    static ref FILES: Mutex<Vec<FileReport>> = Mutex::new(Vec::new());
+}
+
+struct FileReport {
+    path: String,
+    events: Vec<Event>,
+}
+
+pub struct Event {
+    // Such as `W113`, starting with `E` for syntax errors and `W` for everything else
+    code: String,
+    message: String,
+    // Only shown by the plain and default formatters, since they'd break the others
+    notes: Vec<String>,
+    // Luacheck events can only span one line, so an event is made up of every line its
+    // diagnostic spans, as (line, column, end column). These are 1-indexed, and inclusive.
+    lines: Vec<(usize, usize, usize)>,
+}
+
+impl Event {
+    pub fn new(
+        files: &codespan::Files<&str>,
+        source_id: codespan::FileId,
+        range: (u32, u32),
+        code: String,
+        message: String,
+    ) -> Self {
+        let start = files.location(source_id, range.0).unwrap();
+        let end = files.location(source_id, range.1).unwrap();
+        let source = files.source(source_id);
+
+        let lines = (start.line.to_usize()..=end.line.to_usize())
+            .map(|line| {
+                let column = if line == start.line.to_usize() {
+                    start.column.to_usize() + 1
+                } else {
+                    1
+                };
+
+                let end_column = if line == end.line.to_usize() {
+                    end.column.to_usize()
+                } else {
+                    // Report to the end of the line
+                    source
+                        .lines()
+                        .nth(line)
+                        .map_or(0, |line| line.chars().count())
+                };
+
+                (line + 1, column, end_column.max(column))
+            })
+            .collect();
+
+        Self {
+            code,
+            message,
+            notes: Vec::new(),
+            lines,
+        }
+    }
+
+    pub fn with_notes(self, notes: Vec<String>) -> Self {
+        Self { notes, ..self }
+    }
+
+    fn is_error(&self) -> bool {
+        self.code.starts_with('E')
+    }
+
+    fn format(
+        &self,
+        path: &str,
+        (line, column, end_column): (usize, usize, usize),
+        codes: bool,
+        ranges: bool,
+    ) -> String {
+        let mut formatted = format!("{path}:{line}:{column}");
+
+        if ranges {
+            formatted.push_str(&format!("-{end_column}"));
+        }
+
+        formatted.push_str(": ");
+
+        if codes {
+            formatted.push_str(&format!("({}) ", self.code));
+        }
+
+        formatted.push_str(&self.message);
+        formatted
+    }
+}
+
+/// Writes the events of a file with the plain formatter, or keeps them for [`write_report`]
+/// with any other formatter.
+pub fn write_file(
+    options: &Options,
+    writer: &mut impl Write,
+    path: &Path,
+    events: Vec<Event>,
+) -> io::Result<()> {
+    let path = path.display().to_string();
+
+    if options.formatter.is_report() {
+        FILES.lock().unwrap().push(FileReport { path, events });
+        return Ok(());
+    }
+
+    // Consumers (editors) show an event on every line as a native implementation would,
+    // even though this would be frustrating for a human to read.
+    for event in &events {
+        for &line in &event.lines {
+            writeln!(
+                writer,
+                "{}",
+                event.format(&path, line, options.codes, options.ranges)
+            )?;
+
+            for note in &event.notes {
+                writeln!(writer, "{note}")?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes every file kept by [`write_file`] with the default, TAP, or JUnit formatter.
+/// Files are sorted by path, since they're checked in parallel.
+pub fn write_report(options: &Options, writer: &mut impl Write) -> io::Result<()> {
+    let mut files = std::mem::take(&mut *FILES.lock().unwrap());
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    match options.formatter {
+        LuacheckFormatter::Default => write_default(&files, options, writer),
+        LuacheckFormatter::Tap => write_tap(&files, options, writer),
+        LuacheckFormatter::JUnit => write_junit(&files, writer),
+        LuacheckFormatter::Plain => unreachable!("plain output is written as files are checked"),
+    }
+}
+
+fn plural(count: usize) -> &'static str {
+    if count == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
+
+fn write_default(
+    files: &[FileReport],
+    options: &Options,
+    writer: &mut impl Write,
+) -> io::Result<()> {
+    let (mut total_warnings, mut total_errors) = (0, 0);
+
+    for file in files {
+        let errors = file.events.iter().filter(|event| event.is_error()).count();
+        let warnings = file.events.len() - errors;
+        total_errors += errors;
+        total_warnings += warnings;
+
+        let mut status = Vec::new();
+        if warnings > 0 {
+            status.push(format!("{warnings} warning{}", plural(warnings)));
+        }
+        if errors > 0 {
+            status.push(format!("{errors} error{}", plural(errors)));
+        }
+        if status.is_empty() {
+            status.push("OK".to_owned());
+        }
+
+        let label = format!("Checking {}", file.path);
+        writeln!(
+            writer,
+            "{label}{}{}",
+            " ".repeat(50usize.saturating_sub(label.len()).max(1)),
+            status.join(" / "),
+        )?;
+
+        if !file.events.is_empty() {
+            writeln!(writer)?;
+
+            for event in &file.events {
+                writeln!(
+                    writer,
+                    "    {}",
+                    event.format(&file.path, event.lines[0], options.codes, options.ranges)
+                )?;
+
+                for note in &event.notes {
+                    writeln!(writer, "    {note}")?;
+                }
+            }
+
+            writeln!(writer)?;
+        }
+    }
+
+    // There's always one blank line before the total
+    if files.last().is_some_and(|file| file.events.is_empty()) {
+        writeln!(writer)?;
+    }
+
+    writeln!(
+        writer,
+        "Total: {total_warnings} warning{} / {total_errors} error{} in {} file{}",
+        plural(total_warnings),
+        plural(total_errors),
+        files.len(),
+        plural(files.len()),
+    )
+}
+
+fn write_tap(files: &[FileReport], options: &Options, writer: &mut impl Write) -> io::Result<()> {
+    let mut tests = Vec::new();
+
+    for file in files {
+        if file.events.is_empty() {
+            tests.push(format!("ok {} {}", tests.len() + 1, file.path));
+        }
+
+        for event in &file.events {
+            tests.push(format!(
+                "not ok {} {}",
+                tests.len() + 1,
+                event.format(&file.path, event.lines[0], options.codes, options.ranges)
+            ));
+        }
+    }
+
+    writeln!(writer, "1..{}", tests.len())?;
+
+    for test in tests {
+        writeln!(writer, "{test}")?;
+    }
+
+    Ok(())
+}
+
+// Luacheck's JUnit formatter ignores --codes and --ranges
+fn write_junit(files: &[FileReport], writer: &mut impl Write) -> io::Result<()> {
+    let tests: usize = files.iter().map(|file| file.events.len().max(1)).sum();
+
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        writer,
+        r#"<testsuite name="Luacheck report" tests="{tests}">"#
+    )?;
+
+    for file in files {
+        let path = escape_xml(&file.path);
+
+        if file.events.is_empty() {
+            writeln!(
+                writer,
+                r#"    <testcase name="{path}" classname="{path}"/>"#
+            )?;
+        }
+
+        for (index, event) in file.events.iter().enumerate() {
+            writeln!(
+                writer,
+                r#"    <testcase name="{path}:{}" classname="{path}">"#,
+                index + 1
+            )?;
+            writeln!(
+                writer,
+                r#"        <failure type="{}" message="{}"/>"#,
+                escape_xml(&event.code),
+                escape_xml(&event.format(&file.path, event.lines[0], false, false)),
+            )?;
+            writeln!(writer, "    </testcase>")?;
+        }
+    }
+
+    writeln!(writer, "</testsuite>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use structopt::StructOpt;
+
+    fn options(formatter: &str) -> Options {
+        Options::from_iter([
+            "selene",
+            "--luacheck",
+            "--codes",
+            "--formatter",
+            formatter,
+            "-",
+        ])
+    }
+
+    fn files() -> Vec<FileReport> {
+        let mut codespan_files = codespan::Files::new();
+        let source_id = codespan_files.add("a.lua", "local x = 1\nif true then\nend\n");
+
+        vec![
+            FileReport {
+                path: "a.lua".to_owned(),
+                events: vec![
+                    Event::new(
+                        &codespan_files,
+                        source_id,
+                        (6, 7),
+                        "W211".to_owned(),
+                        "unused variable x".to_owned(),
+                    ),
+                    Event::new(
+                        &codespan_files,
+                        source_id,
+                        (12, 28),
+                        "W542".to_owned(),
+                        "empty if branch".to_owned(),
+                    ),
+                ],
+            },
+            FileReport {
+                path: "b.lua".to_owned(),
+                events: Vec::new(),
+            },
+        ]
+    }
+
+    fn output(formatter: &str) -> String {
+        let mut output = Vec::new();
+        let files = files();
+
+        match formatter {
+            "default" => write_default(&files, &options(formatter), &mut output),
+            "tap" => write_tap(&files, &options(formatter), &mut output),
+            _ => write_junit(&files, &mut output),
+        }
+        .unwrap();
+
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_event_lines() {
+        let files = files();
+        assert_eq!(files[0].events[0].lines, vec![(1, 7, 7)]);
+        assert_eq!(files[0].events[1].lines, vec![(2, 1, 12), (3, 1, 3)]);
+    }
+
+    #[test]
+    fn test_plain_notes() {
+        let mut codespan_files = codespan::Files::new();
+        let source_id = codespan_files.add("a.lua", "local x = 1\n");
+        let event = Event::new(
+            &codespan_files,
+            source_id,
+            (6, 7),
+            "W211".to_owned(),
+            "unused variable x".to_owned(),
+        )
+        .with_notes(vec!["try prefixing it with `_`".to_owned()]);
+
+        let mut output = Vec::new();
+        write_file(
+            &options("plain"),
+            &mut output,
+            Path::new("a.lua"),
+            vec![event],
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "a.lua:1:7: (W211) unused variable x\ntry prefixing it with `_`\n"
+        );
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(
+            output("default"),
+            "Checking a.lua                                    2 warnings\n\
+             \n    a.lua:1:7: (W211) unused variable x\
+             \n    a.lua:2:1: (W542) empty if branch\n\
+             \n\
+             Checking b.lua                                    OK\n\
+             \n\
+             Total: 2 warnings / 0 errors in 2 files\n"
+        );
+    }
+
+    #[test]
+    fn test_tap() {
+        assert_eq!(
+            output("tap"),
+            "1..3\n\
+             not ok 1 a.lua:1:7: (W211) unused variable x\n\
+             not ok 2 a.lua:2:1: (W542) empty if branch\n\
+             ok 3 b.lua\n"
+        );
+    }
+
+    #[test]
+    fn test_junit() {
+        let output = output("junit");
+        assert!(output.contains(r#"<testsuite name="Luacheck report" tests="3">"#));
+        assert!(output.contains(r#"<failure type="W211" message="a.lua:1:7: unused variable x"/>"#));
+        assert!(output.contains(r#"<testcase name="b.lua" classname="b.lua"/>"#));
+    }
+
+    #[test]
+    fn test_documented_luacheck_codes() {
+        let documentation = include_str!("../../docs/src/luacheck.md");
+
+        for lint in selene_lib::lint_defaults() {
+            if lint.luacheck_codes.is_empty() {
+                continue;
+            }
+
+            let row = format!("`{}`", lint.name);
+            let codes = lint.luacheck_codes.join(", ");
+
+            assert!(
+                documentation
+                    .lines()
+                    .any(|line| line.contains(&row) && line.ends_with(&format!("| {codes} |"))),
+                "docs/src/luacheck.md doesn't list {} as {codes}",
+                lint.name
+            );
+        }
+    }
+}
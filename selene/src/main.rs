@@ -1,11 +1,12 @@
 use std::{
+    collections::{HashMap, HashSet},
     ffi::OsString,
     fmt, fs,
     io::{self, Read, Write},
-    path::{Path, PathBuf},
+    path::{Component, Path, PathBuf},
     sync::{
         atomic::{AtomicUsize, Ordering},
-        Arc, RwLock,
+        Arc, Mutex, OnceLock, RwLock,
     },
 };
 
@@ -22,19 +23,28 @@ use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 use threadpool::ThreadPool;
 use upgrade_std::upgrade_std;
 
-#[cfg(feature = "roblox")]
 use selene_lib::standard_library::StandardLibrary;
 
-use crate::{json_output::log_total_json, opts::DisplayStyle};
+use crate::{json_output::log_total_json, opts::DisplayStyle, ordered_output::OrderedOutput};
 
 mod capabilities;
+mod diff;
+mod init;
 mod json_output;
+mod luacheck_output;
+mod metrics;
+mod migrate_luacheck;
 mod opts;
+mod ordered_output;
+mod report;
 #[cfg(feature = "roblox")]
 mod roblox;
 mod standard_library;
+mod std_commands;
+mod std_export;
 mod upgrade_std;
 mod validate_config;
+mod watch;
 
 macro_rules! error {
     ($fmt:expr) => {
@@ -55,6 +65,9 @@ static LINT_WARNINGS: AtomicUsize = AtomicUsize::new(0);
 static PARSE_ERRORS: AtomicUsize = AtomicUsize::new(0);
 static STANDARD_LIBRARY_ERRORS: AtomicUsize = AtomicUsize::new(0);
 
+// Only set with --diff-base
+static CHANGED_LINES: OnceLock<diff::ChangedLines> = OnceLock::new();
+
 fn get_color() -> ColorChoice {
     let lock = OPTIONS.read().unwrap();
     let opts = lock.as_ref().unwrap();
@@ -82,7 +95,12 @@ pub fn error(text: &str) {
     writeln!(&mut stderr, "{text}").unwrap();
 }
 
-fn log_total(parse_errors: usize, lint_errors: usize, lint_warnings: usize) -> io::Result<()> {
+fn log_total(
+    parse_errors: usize,
+    lint_errors: usize,
+    lint_warnings: usize,
+    skipped: usize,
+) -> io::Result<()> {
     let lock = OPTIONS.read().unwrap();
     let opts = lock.as_ref().unwrap();
 
@@ -91,9 +109,9 @@ fn log_total(parse_errors: usize, lint_errors: usize, lint_warnings: usize) -> i
 
     match opts.display_style {
         Some(DisplayStyle::Json2) => {
-            log_total_json(stdout, parse_errors, lint_errors, lint_warnings)
+            log_total_json(stdout, parse_errors, lint_errors, lint_warnings, skipped)
         }
-        _ => log_total_text(stdout, parse_errors, lint_errors, lint_warnings),
+        _ => log_total_text(stdout, parse_errors, lint_errors, lint_warnings, skipped),
     }
 }
 
@@ -102,6 +120,7 @@ fn log_total_text(
     parse_errors: usize,
     lint_errors: usize,
     lint_warnings: usize,
+    skipped: usize,
 ) -> io::Result<()> {
     writeln!(&mut stdout, "Results:")?;
 
@@ -121,6 +140,14 @@ fn log_total_text(
     stat(lint_warnings, "warnings")?;
     stat(parse_errors, "parse errors")?;
 
+    // Only worth mentioning when files might be missing unexpectedly
+    if skipped > 0 {
+        writeln!(
+            &mut stdout,
+            "{skipped} skipped by exclude or ignore files, use --no-exclude and --no-ignore to check them"
+        )?;
+    }
+
     Ok(())
 }
 
@@ -167,25 +194,46 @@ fn emit_codespan(
             codespan_reporting::term::emit(writer, config, files, diagnostic)
                 .expect("couldn't emit error to codespan");
         }
+
+        Some(opts::DisplayStyle::Github) => {
+            writeln!(
+                writer,
+                "{}",
+                json_output::diagnostic_to_github(diagnostic, files)
+            )
+            .unwrap();
+        }
+
+        Some(
+            opts::DisplayStyle::Checkstyle | opts::DisplayStyle::Junit | opts::DisplayStyle::Gitlab,
+        ) => {
+            report::record_diagnostic(diagnostic, files);
+
+            // The report is going to a file, so the terminal can still show diagnostics
+            if opts.output.is_some() {
+                codespan_reporting::term::emit(writer, config, files, diagnostic)
+                    .expect("couldn't emit error to codespan");
+            }
+        }
     }
 }
 
-fn emit_codespan_locked(
-    files: &codespan::Files<&str>,
-    diagnostic: &CodespanDiagnostic<codespan::FileId>,
-) {
-    let stdout = termcolor::StandardStream::stdout(get_color());
-    let mut stdout = stdout.lock();
-
-    emit_codespan(&mut stdout, files, diagnostic);
+/// The number of diagnostics of each kind in a single file.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FileCounts {
+    pub errors: usize,
+    pub warnings: usize,
+    pub parse_errors: usize,
 }
 
+// Returns None if the file couldn't be read
 fn read<R: Read>(
     checker: &Checker<toml::value::Value>,
     filename: &Path,
     lua_version: LuaVersion,
     mut reader: R,
-) {
+    stdout: &mut impl WriteColor,
+) -> Option<FileCounts> {
     let mut buffer = Vec::new();
     if let Err(error) = reader.read_to_end(&mut buffer) {
         error!(
@@ -195,7 +243,7 @@ fn read<R: Read>(
         );
 
         LINT_ERRORS.fetch_add(1, Ordering::SeqCst);
-        return;
+        return None;
     }
 
     let contents = String::from_utf8_lossy(&buffer);
@@ -212,82 +260,122 @@ fn read<R: Read>(
         match full_moon::parse_fallible(&contents, lua_version).into_result() {
             Ok(ast) => ast,
             Err(errors) => {
+                let counts = FileCounts {
+                    parse_errors: errors.len(),
+                    ..FileCounts::default()
+                };
+
+                let mut events = Vec::new();
+
                 for error in errors {
                     PARSE_ERRORS.fetch_add(1, Ordering::SeqCst);
-                    match error {
+                    let diagnostic = match error {
                         full_moon::Error::AstError(ast_error) => {
                             let token = ast_error.token();
 
-                            emit_codespan_locked(
-                                &files,
-                                &CodespanDiagnostic {
-                                    severity: CodespanSeverity::Error,
-                                    code: Some("parse_error".to_owned()),
-                                    message: format!("unexpected token `{token}`"),
-                                    labels: vec![CodespanLabel::primary(
-                                        source_id,
-                                        codespan::Span::new(
-                                            token.start_position().bytes() as u32,
-                                            token.end_position().bytes() as u32,
-                                        ),
-                                    )
-                                    .with_message(ast_error.error_message())],
-                                    notes: Vec::new(),
-                                },
-                            )
-                        }
-
-                        full_moon::Error::TokenizerError(error) => emit_codespan_locked(
-                            &files,
-                            &CodespanDiagnostic {
+                            CodespanDiagnostic {
                                 severity: CodespanSeverity::Error,
                                 code: Some("parse_error".to_owned()),
-                                message: match error.error() {
-                                    full_moon::tokenizer::TokenizerErrorType::UnclosedComment => {
-                                        "unclosed comment".to_string()
-                                    }
-
-                                    full_moon::tokenizer::TokenizerErrorType::UnclosedString => {
-                                        "unclosed string".to_string()
-                                    }
-
-                                    full_moon::tokenizer::TokenizerErrorType::UnexpectedToken(
-                                        character,
-                                    ) => {
-                                        format!("unexpected character {character}")
-                                    }
-
-                                    full_moon::tokenizer::TokenizerErrorType::InvalidNumber => {
-                                        "invalid number".to_string()
-                                    }
-
-                                    full_moon::tokenizer::TokenizerErrorType::InvalidSymbol(
-                                        symbol,
-                                    ) => {
-                                        format!("invalid symbol {symbol}")
-                                    }
-                                },
+                                message: format!("unexpected token `{token}`"),
                                 labels: vec![CodespanLabel::primary(
                                     source_id,
                                     codespan::Span::new(
-                                        error.position().bytes() as u32,
-                                        error.position().bytes() as u32,
+                                        token.start_position().bytes() as u32,
+                                        token.end_position().bytes() as u32,
                                     ),
-                                )],
+                                )
+                                .with_message(ast_error.error_message())],
                                 notes: Vec::new(),
+                            }
+                        }
+
+                        full_moon::Error::TokenizerError(error) => CodespanDiagnostic {
+                            severity: CodespanSeverity::Error,
+                            code: Some("parse_error".to_owned()),
+                            message: match error.error() {
+                                full_moon::tokenizer::TokenizerErrorType::UnclosedComment => {
+                                    "unclosed comment".to_string()
+                                }
+
+                                full_moon::tokenizer::TokenizerErrorType::UnclosedString => {
+                                    "unclosed string".to_string()
+                                }
+
+                                full_moon::tokenizer::TokenizerErrorType::UnexpectedToken(
+                                    character,
+                                ) => {
+                                    format!("unexpected character {character}")
+                                }
+
+                                full_moon::tokenizer::TokenizerErrorType::InvalidNumber => {
+                                    "invalid number".to_string()
+                                }
+
+                                full_moon::tokenizer::TokenizerErrorType::InvalidSymbol(symbol) => {
+                                    format!("invalid symbol {symbol}")
+                                }
                             },
-                        ),
+                            labels: vec![CodespanLabel::primary(
+                                source_id,
+                                codespan::Span::new(
+                                    error.position().bytes() as u32,
+                                    error.position().bytes() as u32,
+                                ),
+                            )],
+                            notes: Vec::new(),
+                        },
+                    };
+
+                    if opts.luacheck {
+                        // Luacheck reports syntax errors as E011
+                        let label = &diagnostic.labels[0];
+                        let message = if label.message.is_empty() {
+                            diagnostic.message
+                        } else {
+                            format!("{}: {}", diagnostic.message, label.message)
+                        };
+
+                        events.push(luacheck_output::Event::new(
+                            &files,
+                            source_id,
+                            (label.range.start as u32, label.range.end as u32),
+                            "E011".to_owned(),
+                            message,
+                        ));
+                    } else {
+                        emit_codespan(stdout, &files, &diagnostic);
                     }
                 }
 
-                return;
+                if opts.luacheck {
+                    luacheck_output::write_file(opts, stdout, filename, events).unwrap();
+                }
+
+                return Some(counts);
             }
         }
     };
 
-    let mut diagnostics = checker.test_on(&ast);
+    let mut diagnostics = if filename == Path::new("-") {
+        checker.test_on(&ast)
+    } else {
+        checker.test_on_file(&ast, filename)
+    };
     diagnostics.sort_by_key(|diagnostic| diagnostic.diagnostic.start_position());
 
+    // Code read from stdin without --stdin-filename can't be compared to anything, so it's all checked
+    if let Some(changed_lines) = CHANGED_LINES.get() {
+        if filename != Path::new("-") {
+            diagnostics.retain(|diagnostic| {
+                changed_lines.contains_diagnostic(filename, &diagnostic.diagnostic, |byte| {
+                    files
+                        .location(source_id, byte)
+                        .map_or(0, |location| location.line.to_usize() + 1)
+                })
+            });
+        }
+    }
+
     let (mut errors, mut warnings) = (0, 0);
     for diagnostic in &diagnostics {
         match diagnostic.severity {
@@ -300,116 +388,715 @@ fn read<R: Read>(
     LINT_ERRORS.fetch_add(errors, Ordering::SeqCst);
     LINT_WARNINGS.fetch_add(warnings, Ordering::SeqCst);
 
-    let stdout = termcolor::StandardStream::stdout(get_color());
-    let mut stdout = stdout.lock();
+    let mut events = Vec::new();
 
     for diagnostic in diagnostics {
+        let severity = match diagnostic.severity {
+            Severity::Allow => continue,
+            Severity::Error => CodespanSeverity::Error,
+            Severity::Warning => CodespanSeverity::Warning,
+        };
+
         if opts.luacheck {
-            // Existing Luacheck consumers presumably use --formatter plain
-            let primary_label = &diagnostic.diagnostic.primary_label;
-            let end = files.location(source_id, primary_label.range.1).unwrap();
-
-            // Closures in Rust cannot call themselves recursively, especially not mutable ones.
-            // Luacheck only allows one line ranges, so we just repeat the lint for every line it spans.
-            // This would be frustrating for a human to read, but consumers (editors) will instead show it
-            // as a native implementation would.
-            let mut stack = Vec::new();
-
-            let mut write = |stack: &mut Vec<_>, start: codespan::Location| -> io::Result<()> {
-                write!(stdout, "{}:", filename.display())?;
-                write!(stdout, "{}:{}", start.line.number(), start.column.number())?;
-
-                if opts.ranges {
-                    write!(
-                        stdout,
-                        "-{}",
-                        if start.line != end.line {
-                            // Report to the end of the line
-                            files
-                                .source(source_id)
-                                .lines()
-                                .nth(start.line.to_usize())
-                                .unwrap()
-                                .chars()
-                                .count()
-                        } else {
-                            end.column.to_usize()
-                        }
-                    )?;
-                }
+            // Luacheck only uses E for syntax and config errors, so every lint is a warning.
+            // Lints without a close luacheck warning keep the code they always had.
+            let code = format!("W{}", diagnostic.diagnostic.luacheck_code.unwrap_or("000"));
+
+            events.push(
+                luacheck_output::Event::new(
+                    &files,
+                    source_id,
+                    diagnostic.diagnostic.primary_label.range,
+                    code,
+                    format!(
+                        "[{}] {}",
+                        diagnostic.diagnostic.code, diagnostic.diagnostic.message
+                    ),
+                )
+                .with_notes(diagnostic.diagnostic.notes),
+            );
+        } else {
+            let diagnostic = diagnostic
+                .diagnostic
+                .into_codespan_diagnostic(source_id, severity);
 
-                // The next line will be displayed just like this one
-                if start.line != end.line {
-                    stack.push(codespan::Location::new(
-                        (start.line.to_usize() + 1) as u32,
-                        0,
-                    ));
+            emit_codespan(stdout, &files, &diagnostic);
+        }
+    }
+
+    if opts.luacheck {
+        luacheck_output::write_file(opts, stdout, filename, events).unwrap();
+    }
+
+    Some(FileCounts {
+        errors,
+        warnings,
+        parse_errors: 0,
+    })
+}
+
+fn read_file(
+    checker: &Checker<toml::value::Value>,
+    lua_version: LuaVersion,
+    filename: &Path,
+    stdout: &mut impl WriteColor,
+) -> Option<FileCounts> {
+    read(
+        checker,
+        filename,
+        lua_version,
+        match fs::File::open(filename) {
+            Ok(file) => file,
+            Err(error) => {
+                error!("Couldn't open file {}: {}", filename.display(), error);
+                LINT_ERRORS.fetch_add(1, Ordering::SeqCst);
+                return None;
+            }
+        },
+        stdout,
+    )
+}
+
+pub enum Input {
+    // The path is `-` unless --stdin-filename is passed
+    Stdin(PathBuf),
+    File(PathBuf),
+}
+
+impl Input {
+    pub fn path(&self) -> &Path {
+        match self {
+            Input::Stdin(path) | Input::File(path) => path,
+        }
+    }
+}
+
+// Expands the files and directories passed on the command line into the files to check,
+// using --pattern for directories and skipping anything matched by `exclude`.
+// Returns how many files and directories were skipped because of `exclude` or ignore files.
+pub fn collect_inputs(
+    options: &opts::Options,
+    files: &[OsString],
+    exclude_set: &globset::GlobSet,
+    mut callback: impl FnMut(Input),
+) -> usize {
+    let pattern_set = build_pattern_set(options);
+    let mut skipped = 0;
+
+    for filename in files {
+        if filename == "-" {
+            match &options.stdin_filename {
+                Some(stdin_filename) => {
+                    // Matched and displayed without a leading `./`, the same as walked files
+                    let stdin_filename = without_current_dir(stdin_filename);
+
+                    if !options.no_exclude && exclude_set.is_match(&stdin_filename) {
+                        skipped += 1;
+                        continue;
+                    }
+
+                    callback(Input::Stdin(stdin_filename));
                 }
 
-                write!(
-                    stdout,
-                    ": ({}000) ",
-                    match diagnostic.severity {
-                        Severity::Allow => return Ok(()),
-                        Severity::Error => "E",
-                        Severity::Warning => "W",
+                None => callback(Input::Stdin(PathBuf::from("-"))),
+            }
+
+            continue;
+        }
+
+        match fs::metadata(filename) {
+            Ok(metadata) => {
+                if metadata.is_file() {
+                    if !options.no_exclude && exclude_set.is_match(filename) {
+                        skipped += 1;
+                        continue;
                     }
-                )?;
 
-                write!(stdout, "[{}] ", diagnostic.diagnostic.code)?;
-                write!(stdout, "{}", diagnostic.diagnostic.message)?;
+                    callback(Input::File(PathBuf::from(filename)));
+                } else if metadata.is_dir() {
+                    let (paths, ignored) =
+                        walk_directory(options, Path::new(filename), &pattern_set);
+                    skipped += ignored;
+
+                    for path in paths {
+                        if !options.no_exclude && exclude_set.is_match(&path) {
+                            skipped += 1;
+                            continue;
+                        }
 
-                if !diagnostic.diagnostic.notes.is_empty() {
-                    write!(stdout, "\n{}", diagnostic.diagnostic.notes.join("\n"))?;
+                        callback(Input::File(path));
+                    }
+                } else {
+                    unreachable!("Somehow got a symlink from the files?");
                 }
+            }
+
+            Err(error) => {
+                error!(
+                    "Error getting metadata of {}: {}",
+                    filename.to_string_lossy(),
+                    error
+                );
+
+                LINT_ERRORS.fetch_add(1, Ordering::SeqCst);
+            }
+        };
+    }
+
+    skipped
+}
 
-                writeln!(stdout)?;
-                Ok(())
+// Finds the files to check among paths that changed since collect_inputs was called with the same
+// files, such as files that were made or edited and directories that were moved in, matching them
+// the same way. Changed paths are absolute, and files are given to the callback as collect_inputs
+// would name them.
+pub fn collect_changed_inputs(
+    options: &opts::Options,
+    files: &[OsString],
+    exclude_set: &globset::GlobSet,
+    changed: &HashSet<PathBuf>,
+    mut callback: impl FnMut(PathBuf),
+) {
+    let pattern_set = build_pattern_set(options);
+    let is_excluded = |path: &Path| !options.no_exclude && exclude_set.is_match(path);
+
+    for filename in files {
+        if filename == "-" {
+            continue;
+        }
+
+        let root = Path::new(filename);
+        let Ok(absolute_root) = fs::canonicalize(root) else {
+            continue;
+        };
+
+        if absolute_root.is_file() {
+            if changed.contains(&absolute_root) && !is_excluded(root) {
+                callback(root.to_path_buf());
+            }
+
+            continue;
+        }
+
+        let walk = DirectoryWalk::new(options, root, &pattern_set);
+        let mut directories = Vec::new();
+
+        for changed_path in changed {
+            let Ok(relative_path) = changed_path.strip_prefix(&absolute_root) else {
+                continue;
             };
 
-            write(
-                &mut stack,
-                files.location(source_id, primary_label.range.0).unwrap(),
-            )
-            .unwrap();
+            let path = root.join(relative_path);
+            if relative_path
+                .components()
+                .any(|component| component.as_os_str() == ".git")
+                || !path.exists()
+                || walk.is_ignored(&path)
+            {
+                continue;
+            }
 
-            while let Some(new_start) = stack.pop() {
-                write(&mut stack, new_start).unwrap();
+            if path.is_dir() {
+                directories.push(path);
+            } else if walk.matches_pattern(&path) {
+                let path = without_current_dir(&path);
+                if !is_excluded(&path) {
+                    callback(path);
+                }
             }
-        } else {
-            let diagnostic = diagnostic.diagnostic.into_codespan_diagnostic(
-                source_id,
-                match diagnostic.severity {
-                    Severity::Allow => continue,
-                    Severity::Error => CodespanSeverity::Error,
-                    Severity::Warning => CodespanSeverity::Warning,
-                },
-            );
+        }
 
-            emit_codespan(&mut stdout, &files, &diagnostic);
+        for directory in directories {
+            let (paths, _) = DirectoryWalk::new(options, root, &pattern_set).walk(&directory);
+
+            for path in paths {
+                if !is_excluded(&path) {
+                    callback(path);
+                }
+            }
         }
     }
 }
 
-fn read_file(checker: &Checker<toml::value::Value>, lua_version: LuaVersion, filename: &Path) {
-    read(
-        checker,
-        filename,
-        lua_version,
-        match fs::File::open(filename) {
-            Ok(file) => file,
+fn build_pattern_set(options: &opts::Options) -> globset::GlobSet {
+    let mut pattern_builder = globset::GlobSetBuilder::new();
+    for pattern in &options.pattern {
+        match globset::GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()
+        {
+            Ok(glob) => pattern_builder.add(glob),
             Err(error) => {
-                error!("Couldn't open file {}: {}", filename.display(), error);
+                error!("Invalid glob pattern: {}", error);
+                std::process::exit(1);
+            }
+        };
+    }
+
+    match pattern_builder.build() {
+        Ok(pattern_set) => pattern_set,
+        Err(error) => {
+            error!("Invalid glob pattern: {}", error);
+            std::process::exit(1);
+        }
+    }
+}
+
+// Walks a directory in parallel for files matching --pattern, skipping anything matched by
+// .gitignore, .ignore, or .seleneignore files unless --no-ignore is passed.
+// Returns the files sorted by path, and how many files and directories were ignored. An ignored
+// directory counts once, however many files are inside it.
+fn walk_directory(
+    options: &opts::Options,
+    directory: &Path,
+    pattern_set: &globset::GlobSet,
+) -> (Vec<PathBuf>, usize) {
+    DirectoryWalk::new(options, directory, pattern_set).walk(directory)
+}
+
+// What the threads walking a directory share
+struct DirectoryWalk<'a> {
+    options: &'a opts::Options,
+    directory: &'a Path,
+    pattern_set: &'a globset::GlobSet,
+    current_dir: PathBuf,
+    parent_ignore_files: HashMap<PathBuf, IgnoreFiles>,
+    global_ignore: ignore::gitignore::Gitignore,
+    // The files found and how many entries were ignored, added to by each thread when it's done
+    results: Mutex<(Vec<PathBuf>, usize)>,
+}
+
+impl<'a> DirectoryWalk<'a> {
+    fn new(
+        options: &'a opts::Options,
+        directory: &'a Path,
+        pattern_set: &'a globset::GlobSet,
+    ) -> Self {
+        let current_dir = std::env::current_dir().unwrap_or_default();
+
+        let mut parent_ignore_files = HashMap::new();
+        let mut global_ignore = ignore::gitignore::Gitignore::empty();
+
+        if !options.no_ignore {
+            let absolute_directory = without_current_dir(&current_dir.join(directory));
+            for parent in absolute_directory.ancestors().skip(1) {
+                parent_ignore_files.insert(parent.to_path_buf(), IgnoreFiles::read(parent, true));
+            }
+
+            let (global, error) = ignore::gitignore::Gitignore::global();
+            report_ignore_errors(error);
+            global_ignore = global;
+        }
+
+        DirectoryWalk {
+            options,
+            directory,
+            pattern_set,
+            current_dir,
+            parent_ignore_files,
+            global_ignore,
+            results: Mutex::new((Vec::new(), 0)),
+        }
+    }
+
+    // Walks `start`, which is either the directory or a directory inside it that isn't ignored
+    fn walk(self, start: &Path) -> (Vec<PathBuf>, usize) {
+        let mut builder = ignore::WalkBuilder::new(start);
+        builder
+            .threads(self.options.num_threads)
+            // Ignore files are matched by the visitors instead of the walker, so that what they
+            // skip can be counted. Hidden files were always checked before ignore files were
+            // supported.
+            .standard_filters(false)
+            .filter_entry(|entry| entry.file_name() != ".git");
+
+        builder.build_parallel().visit(&mut &self);
+
+        let (mut paths, ignored) = self.results.into_inner().unwrap();
+        paths.sort();
+
+        (paths, ignored)
+    }
+
+    // Whether a path inside the directory is skipped when walking it, because either it or one of
+    // the directories it's in is ignored
+    fn is_ignored(&self, path: &Path) -> bool {
+        if self.options.no_ignore {
+            return false;
+        }
+
+        let mut visitor = self.visitor();
+        path.ancestors()
+            .take_while(|ancestor| *ancestor != self.directory)
+            .any(|ancestor| visitor.is_ignored(ancestor, ancestor.is_dir()))
+    }
+
+    fn visitor(&'a self) -> DirectoryVisitor<'a> {
+        DirectoryVisitor {
+            walk: self,
+            ignore_files: self.parent_ignore_files.clone(),
+            paths: Vec::new(),
+            ignored: 0,
+        }
+    }
+
+    fn absolute(&self, path: &Path) -> PathBuf {
+        without_current_dir(&self.current_dir.join(path))
+    }
+
+    fn matches_pattern(&self, path: &Path) -> bool {
+        path.strip_prefix(self.directory)
+            .is_ok_and(|relative_path| self.pattern_set.is_match(relative_path))
+    }
+}
+
+impl<'a> ignore::ParallelVisitorBuilder<'a> for &'a DirectoryWalk<'a> {
+    fn build(&mut self) -> Box<dyn ignore::ParallelVisitor + 'a> {
+        Box::new(self.visitor())
+    }
+}
+
+// Walks a directory on one thread. Each thread reads the ignore files it needs itself, rather
+// than waiting on the other threads for them.
+struct DirectoryVisitor<'a> {
+    walk: &'a DirectoryWalk<'a>,
+    ignore_files: HashMap<PathBuf, IgnoreFiles>,
+    paths: Vec<PathBuf>,
+    ignored: usize,
+}
+
+impl DirectoryVisitor<'_> {
+    // Matches the same way as the walker would, where a .seleneignore file in any directory takes
+    // precedence over an .ignore file, which takes precedence over git's ignore files.
+    fn is_ignored(&mut self, path: &Path, is_dir: bool) -> bool {
+        let path = self.walk.absolute(path);
+        let directories = path.ancestors().skip(1).collect::<Vec<_>>();
+
+        for directory in &directories {
+            self.ignore_files
+                .entry(directory.to_path_buf())
+                .or_insert_with(|| IgnoreFiles::read(directory, false));
+        }
+
+        // Git's ignore files only apply inside the closest repository
+        let git_directories = match directories
+            .iter()
+            .position(|directory| self.ignore_files[*directory].is_git_repository)
+        {
+            Some(position) => &directories[..=position],
+            None => &directories[..],
+        };
+
+        let matched = (0..IgnoreFiles::NAMES.len()).find_map(|index| {
+            let directories = if IgnoreFiles::NAMES[index].starts_with(".git") {
+                git_directories
+            } else {
+                &directories[..]
+            };
+
+            directories.iter().find_map(|directory| {
+                let matched = self.ignore_files[*directory].matchers[index].matched(&path, is_dir);
+                (!matched.is_none()).then(|| matched.is_ignore())
+            })
+        });
+
+        matched.unwrap_or_else(|| self.walk.global_ignore.matched(&path, is_dir).is_ignore())
+    }
+}
+
+impl ignore::ParallelVisitor for DirectoryVisitor<'_> {
+    fn visit(&mut self, entry: Result<ignore::DirEntry, ignore::Error>) -> ignore::WalkState {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(error) => {
+                error!(
+                    "Couldn't open file {}: {}",
+                    self.walk.directory.display(),
+                    error
+                );
                 LINT_ERRORS.fetch_add(1, Ordering::SeqCst);
-                return;
+                return ignore::WalkState::Continue;
             }
-        },
-    );
+        };
+
+        let path = entry.path();
+        let is_dir = entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_dir());
+
+        if !self.walk.options.no_ignore {
+            if entry.depth() > 0 && self.is_ignored(path, is_dir) {
+                // Only directories and files matching --pattern would have been checked
+                if is_dir || self.walk.matches_pattern(path) {
+                    self.ignored += 1;
+                }
+
+                return ignore::WalkState::Skip;
+            }
+
+            // The thread that visits a directory is the one that reports its broken ignore files
+            if is_dir {
+                let directory = self.walk.absolute(path);
+                let ignore_files = IgnoreFiles::read(&directory, true);
+                self.ignore_files.insert(directory, ignore_files);
+            }
+        }
+
+        if !is_dir && self.walk.matches_pattern(path) {
+            // `selene .` names files `./src/a.lua`, but `exclude` and the output expect `src/a.lua`
+            self.paths
+                .push(path.strip_prefix(".").unwrap_or(path).to_path_buf());
+        }
+
+        ignore::WalkState::Continue
+    }
+}
+
+impl Drop for DirectoryVisitor<'_> {
+    fn drop(&mut self) {
+        let mut results = self.walk.results.lock().unwrap();
+        results.0.append(&mut self.paths);
+        results.1 += self.ignored;
+    }
+}
+
+// The ignore files in a directory, matching paths relative to it
+#[derive(Clone)]
+struct IgnoreFiles {
+    // In the same order as NAMES
+    matchers: Vec<ignore::gitignore::Gitignore>,
+    is_git_repository: bool,
+}
+
+impl IgnoreFiles {
+    // From highest to lowest precedence
+    const NAMES: [&'static str; 4] = [
+        ".seleneignore",
+        ".ignore",
+        ".gitignore",
+        ".git/info/exclude",
+    ];
+
+    fn read(directory: &Path, report_errors: bool) -> Self {
+        let read_file = |path: PathBuf| {
+            if !path.is_file() {
+                return ignore::gitignore::Gitignore::empty();
+            }
+
+            let mut builder = ignore::gitignore::GitignoreBuilder::new(directory);
+            let error = builder.add(path);
+
+            match builder.build() {
+                Ok(gitignore) => {
+                    if report_errors {
+                        report_ignore_errors(error);
+                    }
+
+                    gitignore
+                }
+
+                Err(build_error) => {
+                    if report_errors {
+                        report_ignore_errors(Some(error.unwrap_or(build_error)));
+                    }
+
+                    ignore::gitignore::Gitignore::empty()
+                }
+            }
+        };
+
+        IgnoreFiles {
+            matchers: Self::NAMES
+                .iter()
+                .map(|name| read_file(directory.join(name)))
+                .collect(),
+            is_git_repository: directory.join(".git").exists(),
+        }
+    }
+
+    fn is_ignore_file(path: &Path) -> bool {
+        Self::NAMES.iter().any(|name| path.ends_with(name))
+    }
+}
+
+fn report_ignore_errors(error: Option<ignore::Error>) {
+    if let Some(error) = error {
+        error!("Couldn't read ignore file: {error}");
+        LINT_ERRORS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn without_current_dir(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| component != &Component::CurDir)
+        .collect()
+}
+
+// Finds the closest selene.toml to the file passed with --stdin-filename, when only stdin
+// is being checked. Returns None when that's the selene.toml in the current directory.
+fn stdin_config_directory(options: &opts::Options) -> Option<PathBuf> {
+    if options.files.iter().any(|file| file != "-") {
+        return None;
+    }
+
+    let directory = options
+        .stdin_filename
+        .as_deref()?
+        .parent()?
+        .ancestors()
+        .find(|directory| directory.join("selene.toml").is_file())?;
+
+    if directory.as_os_str().is_empty() {
+        None
+    } else {
+        Some(directory.to_path_buf())
+    }
+}
+
+// Reads the config passed with --config, or selene.toml if it exists.
+// Returns the config, and the directory of the config if it isn't the current directory.
+fn load_config(
+    options: &opts::Options,
+) -> Result<(CheckerConfig<toml::value::Value>, Option<PathBuf>), String> {
+    match &options.config {
+        Some(config_file) => {
+            let config_contents = fs::read_to_string(config_file)
+                .map_err(|error| format!("Couldn't read config file: {error}"))?;
+
+            let mut config: CheckerConfig<toml::value::Value> = toml::from_str(&config_contents)
+                .map_err(|error| format!("Config file not in correct format: {error}"))?;
+
+            let config_directory = Path::new(&config_file).parent().map(Path::to_path_buf);
+            config.config_directory = config_directory.clone();
+
+            Ok((config, config_directory))
+        }
+
+        None => {
+            let config_directory = stdin_config_directory(options);
+            let config_file = match &config_directory {
+                Some(directory) => directory.join("selene.toml"),
+                None => PathBuf::from("selene.toml"),
+            };
+
+            match fs::read_to_string(config_file) {
+                Ok(config_contents) => {
+                    let mut config: CheckerConfig<toml::value::Value> =
+                        toml::from_str(&config_contents).map_err(|error| {
+                            format!("Config file not in correct format: {error}")
+                        })?;
+
+                    config.config_directory = config_directory.clone();
+
+                    Ok((config, config_directory))
+                }
+
+                Err(_) => Ok((CheckerConfig::default(), None)),
+            }
+        }
+    }
+}
+
+fn load_standard_library(
+    config: &CheckerConfig<toml::value::Value>,
+    current_dir: &Path,
+    config_directory: &Option<PathBuf>,
+) -> Result<StandardLibrary, String> {
+    match standard_library::collect_standard_library(
+        config,
+        config.std(),
+        current_dir,
+        config_directory,
+    ) {
+        Ok(Some(library)) => Ok(library),
+
+        Ok(None) => Err("Standard library was empty.".to_owned()),
+
+        Err(error) => {
+            let missing_files: Vec<_> = config
+                .std()
+                .split('+')
+                .filter(|name| {
+                    standard_library::resolve_standard_library(
+                        config,
+                        name,
+                        current_dir,
+                        config_directory,
+                    )
+                    .is_none()
+                })
+                .collect();
+
+            if !missing_files.is_empty() {
+                eprintln!(
+                    "`std = \"{}\"`, but some libraries could not be found:",
+                    config.std()
+                );
+
+                for library_name in missing_files {
+                    eprintln!("  `{library_name}`");
+                }
+
+                return Err("Could not find all standard library files".to_owned());
+            }
+
+            Err(format!("Could not collect standard library: {error}"))
+        }
+    }
+}
+
+fn build_exclude_set(
+    config: &CheckerConfig<toml::value::Value>,
+) -> Result<globset::GlobSet, String> {
+    let mut builder = globset::GlobSetBuilder::new();
+    for pattern in &config.exclude {
+        builder.add(
+            globset::Glob::new(pattern)
+                .map_err(|error| format!("Invalid glob pattern: {error}"))?,
+        );
+    }
+
+    builder.build().map_err(|error| error.to_string())
+}
+
+// Problems with the Lua version are reported, but don't stop selene from running
+fn lua_version(standard_library: &StandardLibrary) -> LuaVersion {
+    let (lua_version, problems) = standard_library.lua_version();
+
+    for problem in problems {
+        match problem {
+            LuaVersionError::FeatureNotEnabled(feature) => {
+                error!(
+                    "lua version {feature} in standard library, but feature for it is not enabled"
+                );
+            }
+
+            LuaVersionError::Unknown(version) => {
+                error!("unknown lua version {version} in standard library");
+            }
+        }
+    }
+
+    lua_version
 }
 
 fn start(mut options: opts::Options) {
+    // Annotations show up inline on pull requests, so they're the best default in GitHub Actions
+    if options.display_style.is_none()
+        && !options.quiet
+        && !options.luacheck
+        && std::env::var("GITHUB_ACTIONS").as_deref() == Ok("true")
+    {
+        options.display_style = Some(DisplayStyle::Github);
+    }
+
     *OPTIONS.write().unwrap() = Some(options.clone());
 
+    // `selene metrics --pattern` is passed after the subcommand, rather than before it
+    if let Some(opts::Command::Metrics { pattern, .. }) = &options.command {
+        options.pattern.extend(pattern.iter().cloned());
+    }
+
     if options.pattern.is_empty() {
         options.pattern.push(String::from("**/*.lua"));
         #[cfg(feature = "roblox")]
@@ -451,7 +1138,7 @@ fn start(mut options: opts::Options) {
                         json_output::print_json(json_output::JsonOutput::InvalidConfig(error));
                     }
 
-                    opts::DisplayStyle::Rich => {
+                    opts::DisplayStyle::Rich | opts::DisplayStyle::Github => {
                         let stdout = termcolor::StandardStream::stdout(get_color());
                         let mut stdout = stdout.lock();
                         error
@@ -459,7 +1146,11 @@ fn start(mut options: opts::Options) {
                             .expect("can't write to stdout");
                     }
 
-                    opts::DisplayStyle::Json | opts::DisplayStyle::Quiet => {}
+                    opts::DisplayStyle::Json
+                    | opts::DisplayStyle::Quiet
+                    | opts::DisplayStyle::Checkstyle
+                    | opts::DisplayStyle::Junit
+                    | opts::DisplayStyle::Gitlab => {}
                 }
 
                 std::process::exit(1);
@@ -507,123 +1198,146 @@ fn start(mut options: opts::Options) {
             return;
         }
 
-        None => {}
-    }
+        Some(opts::Command::Init {
+            generate_std,
+            force,
+        }) => {
+            if let Err(error) = init::init(&std::env::current_dir().unwrap(), *generate_std, *force)
+            {
+                error!("{error}");
+                std::process::exit(1);
+            }
 
-    let (config, config_directory): (CheckerConfig<toml::value::Value>, Option<PathBuf>) =
-        match options.config {
-            Some(config_file) => {
-                let config_contents = match fs::read_to_string(&config_file) {
-                    Ok(contents) => contents,
-                    Err(error) => {
-                        error!("Couldn't read config file: {}", error);
-                        std::process::exit(1);
-                    }
-                };
+            return;
+        }
 
-                match toml::from_str(&config_contents) {
-                    Ok(config) => (
-                        config,
-                        Path::new(&config_file).parent().map(Path::to_path_buf),
-                    ),
-                    Err(error) => {
-                        error!("Config file not in correct format: {}", error);
-                        std::process::exit(1);
-                    }
-                }
+        Some(opts::Command::MigrateLuacheck { path, force }) => {
+            if let Err(error) =
+                migrate_luacheck::migrate_luacheck(&std::env::current_dir().unwrap(), path, *force)
+            {
+                error!("{error}");
+                std::process::exit(1);
             }
 
-            None => match fs::read_to_string("selene.toml") {
-                Ok(config_contents) => match toml::from_str(&config_contents) {
-                    Ok(config) => (config, None),
-                    Err(error) => {
-                        error!("Config file not in correct format: {}", error);
-                        std::process::exit(1);
-                    }
-                },
+            return;
+        }
 
-                Err(_) => (CheckerConfig::default(), None),
-            },
-        };
+        // Needs the config and standard library, so is handled below
+        Some(opts::Command::Metrics { .. }) => {}
 
-    let current_dir = std::env::current_dir().unwrap();
+        // Needs the config, so is handled below
+        Some(opts::Command::Std(_)) => {}
 
-    let standard_library = match standard_library::collect_standard_library(
-        &config,
-        config.std(),
-        &current_dir,
-        &config_directory,
-    ) {
-        Ok(Some(library)) => library,
+        None => {}
+    }
 
-        Ok(None) => {
-            error!("Standard library was empty.");
+    if options.watch {
+        if options.files.iter().any(|file| file == "-") {
+            error!("--watch can't be used with stdin");
             std::process::exit(1);
         }
 
-        Err(error) => {
-            let missing_files: Vec<_> = config
-                .std()
-                .split('+')
-                .filter(|name| {
-                    !PathBuf::from(format!("{name}.yml")).exists()
-                        && !PathBuf::from(format!("{name}.yaml")).exists()
-                        && !PathBuf::from(format!("{name}.toml")).exists()
-                })
-                .filter(|name| !cfg!(feature = "roblox") || *name != "roblox")
-                .collect();
+        if !matches!(
+            options.display_style(),
+            opts::DisplayStyle::Rich | opts::DisplayStyle::Quiet
+        ) {
+            error!("--watch can only be used with the rich and quiet display styles");
+            std::process::exit(1);
+        }
 
-            if !missing_files.is_empty() {
-                eprintln!(
-                    "`std = \"{}\"`, but some libraries could not be found:",
-                    config.std()
-                );
+        watch::watch(&options);
+        return;
+    }
 
-                for library_name in missing_files {
-                    eprintln!("  `{library_name}`");
-                }
+    let (config, config_directory) = match load_config(&options) {
+        Ok(config) => config,
+        Err(error) => {
+            error!("{error}");
+            std::process::exit(1);
+        }
+    };
 
-                error!("Could not find all standard library files");
-                std::process::exit(1);
+    let current_dir = std::env::current_dir().unwrap();
+
+    if let Some(opts::Command::Std(std_command)) = &options.command {
+        let success = match std_command {
+            opts::StdCommand::Which { name } => std_commands::which(
+                &config,
+                name.as_deref().unwrap_or_else(|| config.std()),
+                &current_dir,
+                &config_directory,
+            ),
+
+            opts::StdCommand::Dump { name, output } => std_commands::dump(
+                &config,
+                name.as_deref().unwrap_or_else(|| config.std()),
+                &current_dir,
+                &config_directory,
+                output.as_deref(),
+            ),
+
+            opts::StdCommand::Show { path, std } => std_commands::show(
+                &config,
+                std.as_deref().unwrap_or_else(|| config.std()),
+                path,
+                &current_dir,
+                &config_directory,
+            ),
+
+            opts::StdCommand::Export {
+                format,
+                name,
+                output,
+            } => std_commands::export(
+                &config,
+                name.as_deref().unwrap_or_else(|| config.std()),
+                *format,
+                &current_dir,
+                &config_directory,
+                output.as_deref(),
+            ),
+
+            opts::StdCommand::Diff { a, b } => {
+                std_commands::diff(&config, a, b, &current_dir, &config_directory)
             }
+        };
 
-            error!("Could not collect standard library: {error}");
+        if !success {
             std::process::exit(1);
         }
-    };
 
-    let mut builder = globset::GlobSetBuilder::new();
-    for pattern in &config.exclude {
-        builder.add(match globset::Glob::new(pattern) {
-            Ok(glob) => glob,
-            Err(error) => {
-                error!("Invalid glob pattern: {error}");
-                std::process::exit(1);
-            }
-        });
+        return;
     }
 
-    let exclude_set = match builder.build() {
-        Ok(globset) => globset,
+    let standard_library = match load_standard_library(&config, &current_dir, &config_directory) {
+        Ok(library) => library,
         Err(error) => {
             error!("{error}");
             std::process::exit(1);
         }
     };
 
-    let (lua_version, problems) = standard_library.lua_version();
-    if !problems.is_empty() {
-        for problem in problems {
-            match problem {
-                LuaVersionError::FeatureNotEnabled(feature) => {
-                    error!("lua version {feature} in standard library, but feature for it is not enabled");
-                }
+    let exclude_set = match build_exclude_set(&config) {
+        Ok(exclude_set) => exclude_set,
+        Err(error) => {
+            error!("{error}");
+            std::process::exit(1);
+        }
+    };
 
-                LuaVersionError::Unknown(version) => {
-                    error!("unknown lua version {version} in standard library");
-                }
-            }
+    let lua_version = lua_version(&standard_library);
+
+    if let Some(opts::Command::Metrics { format, files, .. }) = &options.command {
+        if !metrics::print_metrics(&options, files, &exclude_set, lua_version, *format) {
+            std::process::exit(1);
         }
+
+        return;
+    }
+
+    if options.output.is_some() && !options.display_style().is_report() {
+        error!("--output can only be used with the checkstyle, junit, and gitlab display styles");
+        std::process::exit(1);
     }
 
     let checker = Arc::new(match Checker::new(config, standard_library) {
@@ -634,80 +1348,74 @@ fn start(mut options: opts::Options) {
         }
     });
 
-    let pool = ThreadPool::new(options.num_threads);
+    let sorted = match options.output_order {
+        opts::OutputOrder::Sorted => true,
+        opts::OutputOrder::Completion => false,
+        // Output that's being saved or diffed should be the same every run
+        opts::OutputOrder::Auto => !atty::is(atty::Stream::Stdout),
+    };
 
-    for filename in &options.files {
-        if filename == "-" {
-            let checker = Arc::clone(&checker);
-            pool.execute(move || read(&checker, Path::new("-"), lua_version, io::stdin().lock()));
-            continue;
+    if let Some(diff_base) = &options.diff_base {
+        match diff::ChangedLines::from_git(diff_base, options.diff_include_secondary) {
+            Ok(changed_lines) => {
+                CHANGED_LINES.set(changed_lines).ok();
+            }
+
+            Err(error) => {
+                error!("Couldn't find what changed since {diff_base}: {error}");
+                std::process::exit(1);
+            }
         }
+    }
 
-        match fs::metadata(filename) {
-            Ok(metadata) => {
-                if metadata.is_file() {
-                    let checker = Arc::clone(&checker);
-                    let filename = filename.to_owned();
+    let mut inputs = Vec::new();
+    let skipped = collect_inputs(&options, &options.files, &exclude_set, |input| {
+        // With --diff-base, files that haven't changed don't need to be checked at all
+        if let (Some(changed_lines), Input::File(path)) = (CHANGED_LINES.get(), &input) {
+            if !changed_lines.contains_file(path) {
+                return;
+            }
+        }
 
-                    if !options.no_exclude && exclude_set.is_match(&filename) {
-                        continue;
-                    }
+        inputs.push(input)
+    });
 
-                    pool.execute(move || read_file(&checker, lua_version, Path::new(&filename)));
-                } else if metadata.is_dir() {
-                    for pattern in &options.pattern {
-                        let glob = match glob::glob(&format!(
-                            "{}/{}",
-                            filename.to_string_lossy(),
-                            pattern
-                        )) {
-                            Ok(glob) => glob,
-                            Err(error) => {
-                                error!("Invalid glob pattern: {}", error);
-                                std::process::exit(1);
-                            }
-                        };
+    if sorted {
+        inputs.sort_by(|a, b| a.path().cmp(b.path()));
+    }
 
-                        for entry in glob {
-                            match entry {
-                                Ok(path) => {
-                                    if !options.no_exclude && exclude_set.is_match(&path) {
-                                        continue;
-                                    }
+    let pool = ThreadPool::new(options.num_threads);
+    let output = Arc::new(OrderedOutput::new(get_color(), sorted));
 
-                                    let checker = Arc::clone(&checker);
+    for (index, input) in inputs.into_iter().enumerate() {
+        let checker = Arc::clone(&checker);
+        let output = Arc::clone(&output);
 
-                                    pool.execute(move || read_file(&checker, lua_version, &path));
-                                }
+        pool.execute(move || {
+            let mut buffer = output.buffer();
 
-                                Err(error) => {
-                                    error!(
-                                        "Couldn't open file {}: {}",
-                                        filename.to_string_lossy(),
-                                        error
-                                    );
-                                }
-                            };
-                        }
-                    }
-                } else {
-                    unreachable!("Somehow got a symlink from the files?");
-                }
-            }
+            let counts = match &input {
+                Input::Stdin(filename) => read(
+                    &checker,
+                    filename,
+                    lua_version,
+                    io::stdin().lock(),
+                    &mut buffer,
+                ),
 
-            Err(error) => {
-                error!(
-                    "Error getting metadata of {}: {}",
-                    filename.to_string_lossy(),
-                    error
-                );
+                Input::File(path) => read_file(&checker, lua_version, path, &mut buffer),
+            };
 
-                LINT_ERRORS.fetch_add(1, Ordering::SeqCst);
+            if let Some(counts) = counts {
+                json_output::record_file_summary(input.path(), counts);
             }
-        };
+
+            output.finish(index, buffer);
+        });
     }
 
     pool.join();
+    output.flush();
 
     let (parse_errors, lint_errors, lint_warnings, standard_library_errors) = (
         PARSE_ERRORS.load(Ordering::SeqCst),
@@ -716,8 +1424,37 @@ fn start(mut options: opts::Options) {
         STANDARD_LIBRARY_ERRORS.load(Ordering::SeqCst),
     );
 
-    if !options.luacheck && !options.no_summary {
-        log_total(parse_errors, lint_errors, lint_warnings).ok();
+    let display_style = options.display_style();
+
+    if display_style.is_report() {
+        let result = match &options.output {
+            Some(output) => fs::File::create(output).and_then(|file| {
+                let mut writer = io::BufWriter::new(file);
+                report::write_report(display_style, &mut writer)?;
+                writer.flush()
+            }),
+
+            None => report::write_report(display_style, &mut io::stdout().lock()),
+        };
+
+        if let Err(error) = result {
+            error!("Couldn't write report: {error}");
+            std::process::exit(1);
+        }
+    }
+
+    // A summary would corrupt a report written to stdout
+    let report_to_stdout = display_style.is_report() && options.output.is_none();
+
+    if options.luacheck && options.formatter.is_report() {
+        if let Err(error) = luacheck_output::write_report(&options, &mut io::stdout().lock()) {
+            error!("Couldn't write report: {error}");
+            std::process::exit(1);
+        }
+    }
+
+    if !options.luacheck && !options.no_summary && !report_to_stdout {
+        log_total(parse_errors, lint_errors, lint_warnings, skipped).ok();
     }
 
     let error_count =
@@ -840,6 +1577,91 @@ mod tests {
 
         assert!(get_opts_safe(args(vec!["-", "--formatter=plain"]), true).is_ok());
 
+        match get_opts_safe(args(vec!["--formatter", "TAP", "--codes", "-"]), true) {
+            Ok(opts) => {
+                assert_eq!(opts.formatter, opts::LuacheckFormatter::Tap);
+                assert!(opts.codes);
+            }
+
+            Err(err) => {
+                panic!("luacheck --formatter TAP --codes - returned Err: {err:?}");
+            }
+        }
+
+        // Formatters selene doesn't support are plain, rather than an error
+        match get_opts_safe(args(vec!["--formatter", "visual_studio", "-"]), true) {
+            Ok(opts) => assert_eq!(opts.formatter, opts::LuacheckFormatter::Plain),
+            Err(err) => {
+                panic!("luacheck --formatter visual_studio - returned Err: {err:?}");
+            }
+        }
+
         assert!(get_opts_safe(args(vec!["--fail", "files"]), true).is_ok());
     }
+
+    #[test]
+    fn test_watch_opts() {
+        assert!(get_opts_safe(args(vec!["--watch", "src"]), false).is_ok());
+        assert!(get_opts_safe(args(vec!["--watch", "--diff-base", "main", "src"]), false).is_err());
+    }
+
+    #[test]
+    fn test_collect_inputs_exclude() {
+        // Directories passed as `./directory`, like `selene .`, are matched against `exclude`
+        // and displayed without the leading `./`
+        let options = get_opts_safe(
+            args(vec!["--pattern=**/*.lua", "./tests/walk_directory"]),
+            false,
+        )
+        .unwrap();
+        let config: CheckerConfig<toml::value::Value> =
+            toml::from_str(r#"exclude = ["tests/walk_directory/vendor/*"]"#).unwrap();
+        let exclude_set = build_exclude_set(&config).unwrap();
+
+        let mut paths = Vec::new();
+        let skipped = collect_inputs(&options, &options.files, &exclude_set, |input| {
+            if let Input::File(path) = input {
+                paths.push(path);
+            }
+        });
+
+        assert_eq!(paths, vec![PathBuf::from("tests/walk_directory/src/a.lua")]);
+
+        // vendor/b.lua is excluded, and ignored/ from .seleneignore counts once
+        assert_eq!(skipped, 2);
+    }
+
+    #[test]
+    fn test_collect_changed_inputs() {
+        let options = get_opts_safe(
+            args(vec!["--pattern=**/*.lua", "./tests/walk_directory"]),
+            false,
+        )
+        .unwrap();
+        let config: CheckerConfig<toml::value::Value> =
+            toml::from_str(r#"exclude = ["tests/walk_directory/vendor/*"]"#).unwrap();
+        let exclude_set = build_exclude_set(&config).unwrap();
+
+        let changed = [
+            "tests/walk_directory/src",
+            "tests/walk_directory/vendor/b.lua",
+            "tests/walk_directory/ignored/c.lua",
+            "tests/walk_directory/.seleneignore",
+        ]
+        .into_iter()
+        .map(|path| fs::canonicalize(path).unwrap())
+        .chain(std::iter::once(
+            fs::canonicalize("tests/walk_directory")
+                .unwrap()
+                .join("deleted.lua"),
+        ))
+        .collect();
+
+        let mut paths = Vec::new();
+        collect_changed_inputs(&options, &options.files, &exclude_set, &changed, |path| {
+            paths.push(path);
+        });
+
+        assert_eq!(paths, vec![PathBuf::from("tests/walk_directory/src/a.lua")]);
+    }
 }
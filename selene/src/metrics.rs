@@ -0,0 +1,306 @@
+use std::{
+    collections::BTreeSet,
+    ffi::OsString,
+    fs,
+    io::{self, Read, Write},
+    path::Path,
+    sync::mpsc,
+};
+
+use full_moon::LuaVersion;
+use serde::Serialize;
+use threadpool::ThreadPool;
+
+use crate::{
+    collect_inputs,
+    opts::{self, MetricsFormat},
+    Input,
+};
+
+#[derive(Serialize)]
+struct MetricsOutput {
+    files: Vec<FileMetrics>,
+}
+
+#[derive(Serialize)]
+struct FileMetrics {
+    path: String,
+    totals: FileTotals,
+    main_chunk: FunctionMetrics,
+    functions: Vec<FunctionMetrics>,
+}
+
+#[derive(Serialize)]
+struct FileTotals {
+    functions: usize,
+    cyclomatic_complexity: usize,
+    statements: usize,
+    max_nesting_depth: usize,
+    parameters: usize,
+    globals_used: usize,
+}
+
+#[derive(Serialize)]
+struct FunctionMetrics {
+    name: Option<String>,
+    line: usize,
+    column: usize,
+    cyclomatic_complexity: u16,
+    statements: usize,
+    nesting_depth: usize,
+    parameters: usize,
+    globals_used: usize,
+}
+
+fn file_metrics(path: &Path, contents: &str, lua_version: LuaVersion) -> Option<FileMetrics> {
+    let ast = match full_moon::parse_fallible(contents, lua_version).into_result() {
+        Ok(ast) => ast,
+        Err(errors) => {
+            for error in errors {
+                crate::error(&format!("Couldn't parse {}: {error}", path.display()));
+            }
+
+            return None;
+        }
+    };
+
+    let mut files = codespan::Files::new();
+    let source_id = files.add(path.as_os_str(), contents);
+
+    let metrics = selene_lib::metrics::file_metrics(&ast);
+
+    // The main chunk counts towards the totals as if it were a function, so that files with
+    // only top level code aren't reported as empty
+    let all_metrics = || std::iter::once(&metrics.main_chunk).chain(&metrics.functions);
+
+    let mut all_globals_used = BTreeSet::new();
+    for metrics in all_metrics() {
+        all_globals_used.extend(metrics.globals_used.iter());
+    }
+
+    let totals = FileTotals {
+        functions: metrics.functions.len(),
+        cyclomatic_complexity: all_metrics()
+            .map(|metrics| metrics.cyclomatic_complexity as usize)
+            .sum(),
+        statements: all_metrics().map(|metrics| metrics.statements).sum(),
+        max_nesting_depth: all_metrics()
+            .map(|metrics| metrics.nesting_depth)
+            .max()
+            .unwrap_or(0),
+        parameters: all_metrics().map(|metrics| metrics.parameters).sum(),
+        globals_used: all_globals_used.len(),
+    };
+
+    let output_metrics = |metrics: selene_lib::metrics::FunctionMetrics| {
+        let location = files
+            .location(source_id, metrics.signature.0 as u32)
+            .expect("function signature out of bounds");
+
+        FunctionMetrics {
+            name: metrics.name,
+            line: location.line.to_usize() + 1,
+            column: location.column.to_usize() + 1,
+            cyclomatic_complexity: metrics.cyclomatic_complexity,
+            statements: metrics.statements,
+            nesting_depth: metrics.nesting_depth,
+            parameters: metrics.parameters,
+            globals_used: metrics.globals_used.len(),
+        }
+    };
+
+    let main_chunk = output_metrics(metrics.main_chunk);
+    let functions = metrics.functions.into_iter().map(output_metrics).collect();
+
+    Some(FileMetrics {
+        path: path.display().to_string(),
+        totals,
+        main_chunk,
+        functions,
+    })
+}
+
+fn read_metrics(
+    path: &Path,
+    lua_version: LuaVersion,
+    mut reader: impl Read,
+) -> Option<FileMetrics> {
+    let mut buffer = Vec::new();
+    if let Err(error) = reader.read_to_end(&mut buffer) {
+        crate::error(&format!(
+            "Couldn't read contents of file {}: {error}",
+            path.display()
+        ));
+
+        return None;
+    }
+
+    file_metrics(path, &String::from_utf8_lossy(&buffer), lua_version)
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+fn write_csv(mut writer: impl Write, files: &[FileMetrics]) -> io::Result<()> {
+    writeln!(
+        writer,
+        "path,kind,name,line,column,cyclomatic_complexity,statements,nesting_depth,parameters,globals_used"
+    )?;
+
+    for file in files {
+        let path = csv_field(&file.path);
+
+        let totals = &file.totals;
+        writeln!(
+            writer,
+            "{path},file,,,,{},{},{},{},{}",
+            totals.cyclomatic_complexity,
+            totals.statements,
+            totals.max_nesting_depth,
+            totals.parameters,
+            totals.globals_used,
+        )?;
+
+        let main_chunk = &file.main_chunk;
+        writeln!(
+            writer,
+            "{path},main_chunk,,{},{},{},{},{},{},{}",
+            main_chunk.line,
+            main_chunk.column,
+            main_chunk.cyclomatic_complexity,
+            main_chunk.statements,
+            main_chunk.nesting_depth,
+            main_chunk.parameters,
+            main_chunk.globals_used,
+        )?;
+
+        for function in &file.functions {
+            writeln!(
+                writer,
+                "{path},function,{},{},{},{},{},{},{},{}",
+                csv_field(function.name.as_deref().unwrap_or("")),
+                function.line,
+                function.column,
+                function.cyclomatic_complexity,
+                function.statements,
+                function.nesting_depth,
+                function.parameters,
+                function.globals_used,
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints the metrics of every file, sorted by path. Returns false if any file couldn't be read or parsed.
+pub fn print_metrics(
+    options: &opts::Options,
+    files: &[OsString],
+    exclude_set: &globset::GlobSet,
+    lua_version: LuaVersion,
+    format: MetricsFormat,
+) -> bool {
+    let pool = ThreadPool::new(options.num_threads);
+    let (sender, receiver) = mpsc::channel();
+    let mut expected = 0;
+
+    collect_inputs(options, files, exclude_set, |input| {
+        let sender = sender.clone();
+        expected += 1;
+
+        pool.execute(move || {
+            let metrics = match input {
+                Input::Stdin(filename) => read_metrics(&filename, lua_version, io::stdin().lock()),
+                Input::File(path) => match fs::File::open(&path) {
+                    Ok(file) => read_metrics(&path, lua_version, file),
+                    Err(error) => {
+                        crate::error(&format!("Couldn't open file {}: {error}", path.display()));
+                        None
+                    }
+                },
+            };
+
+            sender.send(metrics).ok();
+        });
+    });
+
+    drop(sender);
+    pool.join();
+
+    let results = receiver.iter().collect::<Vec<_>>();
+    let success = results.len() == expected && results.iter().all(Option::is_some);
+
+    let mut file_metrics = results.into_iter().flatten().collect::<Vec<_>>();
+    file_metrics.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+
+    match format {
+        MetricsFormat::Json => {
+            serde_json::to_writer(
+                &mut stdout,
+                &MetricsOutput {
+                    files: file_metrics,
+                },
+            )
+            .expect("couldn't write metrics");
+
+            writeln!(stdout).expect("couldn't write metrics");
+        }
+
+        MetricsFormat::Csv => {
+            write_csv(&mut stdout, &file_metrics).expect("couldn't write metrics");
+        }
+    }
+
+    success
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_output() {
+        let metrics = file_metrics(
+            Path::new("a,b.lua"),
+            "local function add(a, b)\n\tif a then return a + b end\nend\n",
+            LuaVersion::lua51(),
+        )
+        .unwrap();
+
+        let mut output = Vec::new();
+        write_csv(&mut output, &[metrics]).unwrap();
+
+        pretty_assertions::assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "path,kind,name,line,column,cyclomatic_complexity,statements,nesting_depth,parameters,globals_used\n\
+            \"a,b.lua\",file,,,,3,3,1,2,0\n\
+            \"a,b.lua\",main_chunk,,1,1,1,1,0,0,0\n\
+            \"a,b.lua\",function,add,1,7,2,2,1,2,0\n"
+        );
+    }
+
+    #[test]
+    fn test_top_level_totals() {
+        let metrics = file_metrics(
+            Path::new("init.lua"),
+            "if enabled then\n\tprint(\"enabled\")\nend\n",
+            LuaVersion::lua51(),
+        )
+        .unwrap();
+
+        assert_eq!(metrics.totals.functions, 0);
+        assert_eq!(metrics.totals.cyclomatic_complexity, 2);
+        assert_eq!(metrics.totals.statements, 2);
+        assert_eq!(metrics.totals.max_nesting_depth, 1);
+        assert_eq!(metrics.totals.globals_used, 2);
+    }
+}
@@ -0,0 +1,988 @@
+//! `selene migrate-luacheck`, which converts a `.luacheckrc` into a selene.toml and a standard library
+//! for its globals. `.luacheckrc` is Lua, but almost always only assigns constants, so those are read
+//! from the syntax tree instead of running it.
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write as _,
+    fs,
+    path::Path,
+};
+
+use full_moon::{
+    ast,
+    node::Node,
+    tokenizer::{StringLiteralQuoteType, Symbol, TokenType},
+    LuaVersion,
+};
+use selene_lib::{
+    lint_defaults,
+    standard_library::{
+        Argument, ArgumentConstraints, ArgumentType, Field, FieldKind, FunctionBehavior, Observes,
+        Required, StandardLibrary,
+    },
+};
+
+use crate::init::{write_new_file, BUSTED_GLOBALS};
+
+const CONFIG_FILE: &str = "selene.toml";
+
+// The name of the standard library the globals of .luacheckrc are written to
+const GENERATED_STD_NAME: &str = "luacheck";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum LuaValue {
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Table(LuaTable),
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LuaTable {
+    // Values without keys, such as the strings of `globals = { "a", "b" }`
+    pub array: Vec<LuaValue>,
+    pub fields: BTreeMap<String, LuaValue>,
+}
+
+impl LuaTable {
+    fn strings(&self) -> impl Iterator<Item = &str> {
+        self.array.iter().filter_map(|value| match value {
+            LuaValue::String(string) => Some(string.as_str()),
+            _ => None,
+        })
+    }
+}
+
+fn evaluate(expression: &ast::Expression) -> Option<LuaValue> {
+    match expression {
+        ast::Expression::Parentheses { expression, .. } => evaluate(expression),
+
+        ast::Expression::String(token) => match token.token_type() {
+            TokenType::StringLiteral {
+                literal,
+                quote_type: StringLiteralQuoteType::Brackets,
+                ..
+            } => {
+                // Lua skips the first newline of long strings, which have no escapes
+                let literal = literal.as_str();
+                let literal = literal
+                    .strip_prefix("\r\n")
+                    .or_else(|| literal.strip_prefix('\n'))
+                    .unwrap_or(literal);
+
+                Some(LuaValue::String(literal.to_owned()))
+            }
+
+            TokenType::StringLiteral { literal, .. } => unescape(literal).map(LuaValue::String),
+
+            _ => None,
+        },
+
+        ast::Expression::Number(token) => {
+            parse_number(&token.token().to_string()).map(LuaValue::Number)
+        }
+
+        ast::Expression::Symbol(token) => match token.token_type() {
+            TokenType::Symbol {
+                symbol: Symbol::True,
+            } => Some(LuaValue::Bool(true)),
+            TokenType::Symbol {
+                symbol: Symbol::False,
+            } => Some(LuaValue::Bool(false)),
+            _ => None,
+        },
+
+        ast::Expression::UnaryOperator {
+            unop: ast::UnOp::Minus(_),
+            expression,
+        } => match evaluate(expression)? {
+            LuaValue::Number(number) => Some(LuaValue::Number(-number)),
+            _ => None,
+        },
+
+        ast::Expression::TableConstructor(table_constructor) => {
+            let mut table = LuaTable::default();
+
+            for field in table_constructor.fields() {
+                match field {
+                    ast::Field::NoKey(value) => table.array.push(evaluate(value)?),
+
+                    ast::Field::NameKey { key, value, .. } => {
+                        table
+                            .fields
+                            .insert(key.token().to_string(), evaluate(value)?);
+                    }
+
+                    ast::Field::ExpressionKey { key, value, .. } => {
+                        let LuaValue::String(key) = evaluate(key)? else {
+                            return None;
+                        };
+
+                        table.fields.insert(key, evaluate(value)?);
+                    }
+
+                    _ => return None,
+                }
+            }
+
+            Some(LuaValue::Table(table))
+        }
+
+        _ => None,
+    }
+}
+
+// Decodes the escapes of a quoted string, such as `\"` and `\65`.
+// Returns None for escapes Lua would reject, or that don't make valid UTF-8.
+fn unescape(literal: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(literal.len());
+    let mut chars = literal.chars().peekable();
+
+    while let Some(char) = chars.next() {
+        if char != '\\' {
+            let mut buffer = [0; 4];
+            bytes.extend_from_slice(char.encode_utf8(&mut buffer).as_bytes());
+            continue;
+        }
+
+        match chars.next()? {
+            'a' => bytes.push(0x07),
+            'b' => bytes.push(0x08),
+            'f' => bytes.push(0x0C),
+            'n' | '\n' => bytes.push(b'\n'),
+            'r' => bytes.push(b'\r'),
+            't' => bytes.push(b'\t'),
+            'v' => bytes.push(0x0B),
+            escaped @ ('\\' | '"' | '\'') => bytes.push(escaped as u8),
+
+            // Skips the whitespace after it, including newlines
+            'z' => while chars.next_if(|char| char.is_ascii_whitespace()).is_some() {},
+
+            'x' => {
+                let digits = [chars.next()?, chars.next()?].iter().collect::<String>();
+                bytes.push(u8::from_str_radix(&digits, 16).ok()?);
+            }
+
+            'u' => {
+                if chars.next()? != '{' {
+                    return None;
+                }
+
+                let mut digits = String::new();
+                loop {
+                    match chars.next()? {
+                        '}' => break,
+                        digit => digits.push(digit),
+                    }
+                }
+
+                let char = char::from_u32(u32::from_str_radix(&digits, 16).ok()?)?;
+                let mut buffer = [0; 4];
+                bytes.extend_from_slice(char.encode_utf8(&mut buffer).as_bytes());
+            }
+
+            digit if digit.is_ascii_digit() => {
+                let mut digits = digit.to_string();
+                while digits.len() < 3 {
+                    match chars.next_if(char::is_ascii_digit) {
+                        Some(digit) => digits.push(digit),
+                        None => break,
+                    }
+                }
+
+                bytes.push(digits.parse().ok()?);
+            }
+
+            _ => return None,
+        }
+    }
+
+    String::from_utf8(bytes).ok()
+}
+
+// Parses decimal numbers, and hexadecimal integers such as `0x78`.
+// Returns None for anything else, such as hexadecimal floats.
+fn parse_number(text: &str) -> Option<f64> {
+    match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16)
+            .ok()
+            .map(|number| number as f64),
+        None => text.parse().ok(),
+    }
+}
+
+// The keys being assigned to, such as `["files", "spec", "std"]` for `files["spec"].std`
+fn variable_path(var: &ast::Var) -> Option<Vec<String>> {
+    match var {
+        ast::Var::Name(name) => Some(vec![name.token().to_string()]),
+
+        ast::Var::Expression(var_expression) => {
+            let ast::Prefix::Name(name) = var_expression.prefix() else {
+                return None;
+            };
+
+            let mut path = vec![name.token().to_string()];
+
+            for suffix in var_expression.suffixes() {
+                match suffix {
+                    ast::Suffix::Index(ast::Index::Dot { name, .. }) => {
+                        path.push(name.token().to_string());
+                    }
+
+                    ast::Suffix::Index(ast::Index::Brackets { expression, .. }) => {
+                        let LuaValue::String(key) = evaluate(expression)? else {
+                            return None;
+                        };
+
+                        path.push(key);
+                    }
+
+                    _ => return None,
+                }
+            }
+
+            Some(path)
+        }
+
+        _ => None,
+    }
+}
+
+fn set_path(table: &mut LuaTable, path: &[String], value: LuaValue) {
+    let (key, rest) = path.split_first().expect("paths always have a key");
+
+    if rest.is_empty() {
+        table.fields.insert(key.clone(), value);
+        return;
+    }
+
+    let entry = table
+        .fields
+        .entry(key.clone())
+        .or_insert_with(|| LuaValue::Table(LuaTable::default()));
+
+    if !matches!(entry, LuaValue::Table(_)) {
+        *entry = LuaValue::Table(LuaTable::default());
+    }
+
+    if let LuaValue::Table(entry) = entry {
+        set_path(entry, rest, value);
+    }
+}
+
+/// Reads the options a `.luacheckrc` sets. Statements that aren't assignments of constants
+/// are skipped, and described in the second value.
+pub fn read_luacheckrc(code: &str) -> Result<(LuaTable, Vec<String>), String> {
+    let ast = full_moon::parse_fallible(code, LuaVersion::lua51())
+        .into_result()
+        .map_err(|errors| match errors.first() {
+            Some(error) => error.to_string(),
+            None => "unknown error".to_owned(),
+        })?;
+
+    let mut options = LuaTable::default();
+    let mut skipped = Vec::new();
+
+    for stmt in ast.nodes().stmts() {
+        let assignments = match stmt {
+            ast::Stmt::Assignment(assignment)
+                if assignment.variables().len() == assignment.expressions().len() =>
+            {
+                assignment
+                    .variables()
+                    .iter()
+                    .zip(assignment.expressions())
+                    .map(|(var, expression)| Some((variable_path(var)?, evaluate(expression)?)))
+                    .collect::<Option<Vec<_>>>()
+            }
+
+            _ => None,
+        };
+
+        match assignments {
+            Some(assignments) => {
+                for (path, value) in assignments {
+                    set_path(&mut options, &path, value);
+                }
+            }
+
+            None => {
+                let line = stmt
+                    .start_position()
+                    .map(|position| position.line())
+                    .unwrap_or_default();
+
+                skipped.push(format!(
+                    "line {line} isn't an assignment of a constant, so it was skipped"
+                ));
+            }
+        }
+    }
+
+    Ok((options, skipped))
+}
+
+/// The selene standard library closest to a luacheck one, such as `lua51` for `lua51c`.
+pub fn selene_std(luacheck_std: &str) -> Option<&'static str> {
+    match luacheck_std {
+        "min" | "lua51" | "lua51c" => Some("lua51"),
+        "lua52" | "lua52c" => Some("lua52"),
+        "lua53" | "lua53c" => Some("lua53"),
+        "max" | "lua54" | "lua54c" => Some("lua54"),
+        // OpenResty runs on LuaJIT
+        "luajit" | "ngx_lua" => Some("luajit"),
+        _ => None,
+    }
+}
+
+// Luacheck's patterns are Lua patterns matched against the start of a warning code,
+// but in practice only ever use digits and `.`
+fn code_matches(pattern: &str, code: &str) -> bool {
+    pattern.len() <= code.len()
+        && pattern
+            .chars()
+            .zip(code.chars())
+            .all(|(pattern, code)| pattern == '.' || pattern == code)
+}
+
+// The lints that every code matching `pattern` is covered by, and the lints that only some are
+fn lints_for_code_pattern(pattern: &str) -> (Vec<&'static str>, Vec<&'static str>) {
+    let mut fully = Vec::new();
+    let mut partially = Vec::new();
+
+    // Every lint lists the luacheck warnings it's closest to
+    for lint in lint_defaults() {
+        let matched = lint
+            .luacheck_codes
+            .iter()
+            .filter(|code| code_matches(pattern, code))
+            .count();
+
+        if matched == 0 {
+            continue;
+        }
+
+        if matched == lint.luacheck_codes.len() {
+            fully.push(lint.name);
+        } else {
+            partially.push(lint.name);
+        }
+    }
+
+    (fully, partially)
+}
+
+#[derive(Debug, Default)]
+struct Migration {
+    std: Option<&'static str>,
+    exclude: Vec<String>,
+    lints: BTreeMap<&'static str, &'static str>,
+    maximum_complexity: Option<u16>,
+    globals: BTreeMap<String, Field>,
+    // Options that have no selene equivalent, and what to do instead
+    unsupported: Vec<String>,
+}
+
+// Luacheck doesn't know what globals are, so they can be anything
+fn any_field() -> Field {
+    Field::from_field_kind(FieldKind::Any)
+}
+
+// Most read-only globals are functions or tables, such as `describe` or `vim`, but there's no
+// field that can be called and indexed without also being overwritten. Functions that take
+// anything can't be overwritten, and their fields are matched by `*`.
+fn read_only_field() -> Field {
+    Field::from_field_kind(FieldKind::Function(FunctionBehavior {
+        arguments: vec![Argument {
+            required: Required::NotRequired,
+            argument_type: ArgumentType::Vararg,
+            observes: Observes::default(),
+            deprecated: None,
+            constraints: ArgumentConstraints::default(),
+        }],
+        method: false,
+        must_use: false,
+    }))
+}
+
+impl Migration {
+    fn add_global(&mut self, name: String, read_only: bool) {
+        if read_only {
+            self.globals.insert(format!("{name}.*"), any_field());
+            self.globals.insert(name, read_only_field());
+        } else {
+            self.globals.insert(name, any_field());
+        }
+    }
+
+    // Adds globals from `globals = { "a", b = { fields = { ... } } }`, under `prefix` for fields.
+    // `read_globals` are read-only, unless a definition says otherwise with `read_only = false`.
+    fn add_globals(
+        &mut self,
+        prefix: Option<&str>,
+        definitions: &LuaValue,
+        read_only: bool,
+    ) -> bool {
+        let LuaValue::Table(definitions) = definitions else {
+            return false;
+        };
+
+        let full_name = |name: &str| match prefix {
+            Some(prefix) => format!("{prefix}.{name}"),
+            None => name.to_owned(),
+        };
+
+        let mut added = false;
+
+        for name in definitions.strings() {
+            self.add_global(full_name(name), read_only);
+            added = true;
+        }
+
+        for (name, definition) in &definitions.fields {
+            let name = full_name(name);
+            let mut read_only = read_only;
+            let mut added_fields = false;
+
+            if let LuaValue::Table(definition) = definition {
+                if let Some(LuaValue::Bool(definition_read_only)) =
+                    definition.fields.get("read_only")
+                {
+                    read_only = *definition_read_only;
+                }
+
+                if let Some(fields) = definition.fields.get("fields") {
+                    added_fields = self.add_globals(Some(&name), fields, read_only);
+                }
+
+                if definition.fields.get("other_fields") == Some(&LuaValue::Bool(true)) {
+                    self.globals.insert(format!("{name}.*"), any_field());
+                    added_fields = true;
+                }
+            }
+
+            // Globals with fields are defined by their fields
+            if !added_fields {
+                self.add_global(name, read_only);
+            }
+
+            added = true;
+        }
+
+        added
+    }
+
+    // Applies `std = "lua51+busted"`. In `files` sections, the Lua version can't change,
+    // and stds can start with `+` to add to the std of the whole project.
+    fn apply_std(&mut self, std: &str, custom_stds: Option<&LuaTable>, section: Option<&str>) {
+        for segment in std.split('+').filter(|segment| !segment.is_empty()) {
+            if let Some(selene_std) = selene_std(segment) {
+                match section {
+                    None => {
+                        self.std.get_or_insert(selene_std);
+                    }
+
+                    Some(section) => self.unsupported.push(format!(
+                        "{section} uses the {segment} std, but selene uses the same Lua version for every file"
+                    )),
+                }
+
+                continue;
+            }
+
+            if segment == "busted" {
+                for global in BUSTED_GLOBALS {
+                    self.globals
+                        .insert((*global).to_owned(), Field::from_field_kind(FieldKind::Any));
+                }
+            } else if let Some(LuaValue::Table(custom_std)) =
+                custom_stds.and_then(|custom_stds| custom_stds.fields.get(segment))
+            {
+                for (key, read_only) in [("globals", false), ("read_globals", true)] {
+                    if let Some(definitions) = custom_std.fields.get(key) {
+                        self.add_globals(None, definitions, read_only);
+                    }
+                }
+            } else {
+                self.unsupported.push(format!(
+                    "the {segment} std has no selene equivalent, so its globals need to be added to {GENERATED_STD_NAME}.yml by hand"
+                ));
+                continue;
+            }
+
+            if let Some(section) = section {
+                self.unsupported.push(format!(
+                    "{section} uses the {segment} std, which was added for every file, since selene's config applies to every file"
+                ));
+            }
+        }
+    }
+
+    // Turns `ignore = { "21", "631" }` into lints to allow, or returns the lints that would be allowed
+    // for `files` sections, which can't be
+    fn ignore_codes(
+        &mut self,
+        patterns: &LuaValue,
+        section: Option<&str>,
+    ) -> BTreeSet<&'static str> {
+        let mut ignored_lints = BTreeSet::new();
+
+        let LuaValue::Table(patterns) = patterns else {
+            return ignored_lints;
+        };
+
+        for pattern in patterns.strings() {
+            let (code, name) = pattern.split_once('/').unwrap_or((pattern, ""));
+
+            if code.is_empty()
+                || !code
+                    .starts_with(|character: char| character.is_ascii_digit() || character == '.')
+            {
+                self.unsupported.push(format!(
+                    "ignore \"{pattern}\" ignores variables by name, which selene can't, see the ignore_pattern option of unused_variable and shadowing"
+                ));
+                continue;
+            }
+
+            if !name.is_empty() {
+                self.unsupported.push(format!(
+                    "ignore \"{pattern}\" only ignores some variable names, which selene can't, see the ignore_pattern option of unused_variable and shadowing"
+                ));
+                continue;
+            }
+
+            let (fully, partially) = lints_for_code_pattern(code);
+
+            for lint in &partially {
+                self.unsupported.push(format!(
+                    "ignore \"{pattern}\" only ignores some of what {lint} checks, so it was left on"
+                ));
+            }
+
+            if fully.is_empty() && partially.is_empty() {
+                self.unsupported.push(format!(
+                    "ignore \"{pattern}\" doesn't ignore anything selene checks"
+                ));
+            }
+
+            for lint in fully {
+                if section.is_none() {
+                    self.lints.insert(lint, "allow");
+                }
+
+                ignored_lints.insert(lint);
+            }
+        }
+
+        ignored_lints
+    }
+
+    fn apply_files_section(
+        &mut self,
+        pattern: &str,
+        options: &LuaValue,
+        custom_stds: Option<&LuaTable>,
+    ) {
+        let LuaValue::Table(options) = options else {
+            return;
+        };
+
+        let section = format!("files[\"{pattern}\"]");
+
+        for (key, value) in &options.fields {
+            match (key.as_str(), value) {
+                ("std", LuaValue::String(std)) => self.apply_std(std, custom_stds, Some(&section)),
+
+                ("globals" | "read_globals" | "new_globals" | "new_read_globals", _) => {
+                    if self.add_globals(None, value, key.ends_with("read_globals")) {
+                        self.unsupported.push(format!(
+                            "{section} has {key}, which were added for every file, since selene's config applies to every file"
+                        ));
+                    }
+                }
+
+                ("ignore", _) => {
+                    let lints = self.ignore_codes(value, Some(&section));
+                    if !lints.is_empty() {
+                        let lints = lints.into_iter().collect::<Vec<_>>().join(", ");
+                        self.unsupported.push(format!(
+                            "{section} ignores warnings, but selene's config applies to every file, so add `--# selene: allow({lints})` to the top of those files instead"
+                        ));
+                    }
+                }
+
+                _ => self.unsupported.push(format!(
+                    "{section} has {key}, which has no selene equivalent"
+                )),
+            }
+        }
+    }
+}
+
+fn migrate(options: &LuaTable) -> Migration {
+    let mut migration = Migration::default();
+
+    let custom_stds = match options.fields.get("stds") {
+        Some(LuaValue::Table(custom_stds)) => Some(custom_stds),
+        _ => None,
+    };
+
+    for (key, value) in &options.fields {
+        match (key.as_str(), value) {
+            ("std", LuaValue::String(std)) => migration.apply_std(std, custom_stds, None),
+
+            ("globals" | "read_globals" | "new_globals" | "new_read_globals", _) => {
+                migration.add_globals(None, value, key.ends_with("read_globals"));
+            }
+
+            // Only used through `std`
+            ("stds", _) => {}
+
+            ("ignore", _) => {
+                migration.ignore_codes(value, None);
+            }
+
+            ("unused", LuaValue::Bool(false)) => {
+                migration.lints.insert("unused_variable", "allow");
+            }
+
+            ("redefined", LuaValue::Bool(false)) => {
+                migration.lints.insert("shadowing", "allow");
+            }
+
+            ("unused" | "redefined", LuaValue::Bool(true)) => {}
+
+            // Off by default in selene as well
+            ("max_cyclomatic_complexity", LuaValue::Bool(false)) => {}
+
+            // selene's maximum is a whole number, so anything else would make selene.toml fail to load
+            ("max_cyclomatic_complexity", LuaValue::Number(maximum_complexity))
+                if maximum_complexity.fract() == 0.0
+                    && (0.0..=u16::MAX as f64).contains(maximum_complexity) =>
+            {
+                migration.maximum_complexity = Some(*maximum_complexity as u16);
+                migration.lints.insert("high_cyclomatic_complexity", "warn");
+            }
+
+            ("max_cyclomatic_complexity", LuaValue::Number(maximum_complexity)) => {
+                migration.unsupported.push(format!(
+                    "max_cyclomatic_complexity is {maximum_complexity}, but selene's maximum must be a whole number from 0 to {}",
+                    u16::MAX
+                ));
+            }
+
+            ("exclude_files", LuaValue::Table(patterns)) => {
+                // Luacheck's patterns are relative to .luacheckrc, while selene's are matched
+                // against the paths given to it, which may start with `./` or a directory
+                migration.exclude.extend(patterns.strings().map(|pattern| {
+                    if pattern.starts_with("**") {
+                        pattern.to_owned()
+                    } else {
+                        format!("**/{}", pattern.trim_start_matches("./"))
+                    }
+                }));
+            }
+
+            ("files", LuaValue::Table(sections)) => {
+                for (pattern, section) in &sections.fields {
+                    migration.apply_files_section(pattern, section, custom_stds);
+                }
+            }
+
+            (
+                "max_line_length"
+                | "max_code_line_length"
+                | "max_string_line_length"
+                | "max_comment_line_length",
+                _,
+            ) => migration.unsupported.push(format!(
+                "{key}: selene doesn't check line length, use a formatter such as StyLua instead"
+            )),
+
+            ("unused_args" | "unused_secondaries" | "self", _) => {
+                migration.unsupported.push(format!(
+                    "{key}: see the allow_unused_self and ignore_pattern options of unused_variable instead"
+                ));
+            }
+
+            ("allow_defined" | "allow_defined_top" | "module", _) => {
+                migration.unsupported.push(format!(
+                    "{key}: selene doesn't define globals by assigning to them, add them to {GENERATED_STD_NAME}.yml instead"
+                ));
+            }
+
+            ("include_files", _) => migration.unsupported.push(
+                "include_files: pass the files to check to selene, or use --pattern instead"
+                    .to_owned(),
+            ),
+
+            _ => migration
+                .unsupported
+                .push(format!("{key} has no selene equivalent")),
+        }
+    }
+
+    migration
+}
+
+fn toml_string(text: &str) -> String {
+    toml::Value::String(text.to_owned()).to_string()
+}
+
+fn config_contents(migration: &Migration, luacheckrc_path: &Path, std: &str) -> String {
+    let mut contents = String::new();
+
+    writeln!(
+        contents,
+        "# Migrated from {} by `selene migrate-luacheck`",
+        luacheckrc_path.display()
+    )
+    .unwrap();
+    writeln!(contents, "std = {}", toml_string(std)).unwrap();
+
+    if !migration.exclude.is_empty() {
+        let exclude = migration
+            .exclude
+            .iter()
+            .map(|pattern| toml_string(pattern))
+            .collect::<Vec<_>>();
+
+        writeln!(contents, "exclude = [{}]", exclude.join(", ")).unwrap();
+    }
+
+    if !migration.lints.is_empty() {
+        writeln!(contents, "\n[lints]").unwrap();
+
+        for (lint, severity) in &migration.lints {
+            writeln!(contents, "{lint} = \"{severity}\"").unwrap();
+        }
+    }
+
+    if let Some(maximum_complexity) = migration.maximum_complexity {
+        writeln!(contents, "\n[config]").unwrap();
+        writeln!(
+            contents,
+            "high_cyclomatic_complexity = {{ maximum_complexity = {maximum_complexity} }}"
+        )
+        .unwrap();
+    }
+
+    contents
+}
+
+/// Writes a selene.toml, and a standard library for its globals if it has any, from the `.luacheckrc` at `path`.
+pub fn migrate_luacheck(directory: &Path, path: &Path, force: bool) -> Result<(), String> {
+    let code = fs::read_to_string(path)
+        .map_err(|error| format!("Couldn't read {}: {error}", path.display()))?;
+
+    let (options, skipped) = read_luacheckrc(&code)
+        .map_err(|error| format!("Couldn't parse {}: {error}", path.display()))?;
+
+    let mut migration = migrate(&options);
+    migration.unsupported.extend(skipped);
+
+    let mut std = migration.std.unwrap_or("lua51").to_owned();
+    let mut written = vec![CONFIG_FILE.to_owned()];
+
+    if !migration.globals.is_empty() {
+        let mut library = StandardLibrary::default();
+        library.name = Some(GENERATED_STD_NAME.to_owned());
+        library.globals = std::mem::take(&mut migration.globals);
+
+        let std_file = format!("{GENERATED_STD_NAME}.yml");
+        write_new_file(
+            &directory.join(&std_file),
+            &format!(
+                "# Globals from {}, migrated by `selene migrate-luacheck`.\n{}",
+                path.display(),
+                serde_yaml::to_string(&library).expect("couldn't serialize standard library")
+            ),
+            force,
+        )?;
+
+        std = format!("{std}+{GENERATED_STD_NAME}");
+        written.push(std_file);
+    }
+
+    write_new_file(
+        &directory.join(CONFIG_FILE),
+        &config_contents(&migration, path, &std),
+        force,
+    )?;
+
+    println!("Created {} from {}", written.join(" and "), path.display());
+
+    if !migration.unsupported.is_empty() {
+        println!("\nThese options weren't migrated:");
+
+        for unsupported in &migration.unsupported {
+            println!("- {unsupported}");
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use selene_lib::{Checker, CheckerConfig};
+
+    const LUACHECKRC: &str = r#"
+std = "lua53+busted+love"
+globals = { "game" }
+read_globals = {
+    "jit",
+    vim = { fields = { api = { other_fields = true }, fn = {} } },
+}
+ignore = { "21", "212/self", "631" }
+max_cyclomatic_complexity = 20
+max_line_length = 120
+exclude_files = { "lua_modules/**" }
+files["spec"] = { ignore = { "113" } }
+files["scripts"].std = "+ngx_lua"
+stds.project = { read_globals = { "project" } }
+local unused = 1
+"#;
+
+    #[test]
+    fn test_migrate() {
+        let (options, skipped) = read_luacheckrc(LUACHECKRC).unwrap();
+        assert_eq!(
+            skipped,
+            vec!["line 15 isn't an assignment of a constant, so it was skipped"]
+        );
+
+        let migration = migrate(&options);
+
+        assert_eq!(migration.std, Some("lua53"));
+        assert_eq!(migration.exclude, vec!["**/lua_modules/**"]);
+        assert_eq!(migration.maximum_complexity, Some(20));
+        assert_eq!(
+            migration.lints.into_iter().collect::<Vec<_>>(),
+            vec![
+                ("high_cyclomatic_complexity", "warn"),
+                ("unused_variable", "allow")
+            ]
+        );
+
+        for global in ["game", "jit", "vim.api.*", "vim.fn", "describe"] {
+            assert!(
+                migration.globals.contains_key(global),
+                "{global} wasn't migrated"
+            );
+        }
+
+        assert!(!migration.globals.contains_key("vim"));
+
+        assert!(!migration.globals.contains_key("project"));
+
+        let unsupported = migration.unsupported.join("\n");
+        for expected in [
+            "the love std",
+            "\"212/self\"",
+            "\"631\" doesn't ignore anything",
+            "max_line_length",
+            "--# selene: allow(undefined_variable)",
+            "files[\"scripts\"] uses the ngx_lua std",
+        ] {
+            assert!(
+                unsupported.contains(expected),
+                "{expected} wasn't reported in:\n{unsupported}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_migrated_std() {
+        let (options, _) = read_luacheckrc(LUACHECKRC).unwrap();
+        let migration = migrate(&options);
+
+        let mut migrated = StandardLibrary::default();
+        migrated.globals = migration.globals;
+
+        let mut library = StandardLibrary::from_name("lua51").unwrap();
+        library.extend(migrated);
+
+        let checker: Checker<toml::value::Value> =
+            Checker::new(CheckerConfig::default(), library).unwrap();
+
+        let lint = |code: &str| {
+            let ast = full_moon::parse(code).unwrap();
+            checker
+                .test_on(&ast)
+                .into_iter()
+                .map(|diagnostic| diagnostic.diagnostic.message)
+                .collect::<Vec<_>>()
+        };
+
+        // read_globals can be called and indexed, like globals
+        assert_eq!(
+            lint(
+                r#"
+                describe("x", function() end)
+                describe.skip("y")
+                print(jit.version, jit.off(), vim.api.nvim_command("w"), vim.fn.expand("%"))
+                game = 1
+                game.x = 2
+                "#
+            ),
+            Vec::<String>::new()
+        );
+
+        // But they can't be overwritten
+        assert_eq!(lint("jit = nil").len(), 1);
+    }
+
+    #[test]
+    fn test_fractional_maximum_complexity() {
+        let (options, _) = read_luacheckrc("max_cyclomatic_complexity = 12.5").unwrap();
+        let migration = migrate(&options);
+
+        assert_eq!(migration.maximum_complexity, None);
+        assert!(migration.lints.is_empty());
+        assert!(migration.unsupported[0].contains("max_cyclomatic_complexity is 12.5"));
+    }
+
+    #[test]
+    fn test_read_literals() {
+        let (options, skipped) = read_luacheckrc(
+            r#"
+globals = { "a\"b", 'c\65\x44\u{45}', "f\z   g", [[
+h]] }
+max_line_length = 0x78
+std = "lua\q"
+"#,
+        )
+        .unwrap();
+
+        let LuaValue::Table(globals) = &options.fields["globals"] else {
+            panic!("globals isn't a table");
+        };
+
+        assert_eq!(
+            globals.strings().collect::<Vec<_>>(),
+            vec!["a\"b", "cADE", "fg", "h"]
+        );
+        assert_eq!(options.fields["max_line_length"], LuaValue::Number(120.0));
+
+        // Invalid escapes are skipped, rather than read wrong
+        assert_eq!(
+            skipped,
+            vec!["line 5 isn't an assignment of a constant, so it was skipped"]
+        );
+    }
+
+    #[test]
+    fn test_lints_for_code_pattern() {
+        assert_eq!(
+            lints_for_code_pattern("2"),
+            (vec!["unused_variable"], vec![])
+        );
+        assert_eq!(
+            lints_for_code_pattern("212"),
+            (vec![], vec!["unused_variable"])
+        );
+        assert_eq!(lints_for_code_pattern("4.1"), (vec![], vec!["shadowing"]));
+    }
+}
@@ -61,6 +61,21 @@ pub struct Options {
     #[structopt(long, hidden(true))]
     pub ranges: bool,
 
+    // Only used in Luacheck mode
+    #[structopt(long, hidden(true))]
+    pub codes: bool,
+
+    // Only used in Luacheck mode. Luacheck defaults to `default`, but existing consumers
+    // expect plain output when they don't pass one. Formatters selene doesn't support, such as
+    // `visual_studio`, are plain as well, since unknown luacheck options are ignored.
+    #[structopt(
+        long,
+        hidden(true),
+        parse(from_str = LuacheckFormatter::from_name),
+        default_value = "plain",
+    )]
+    pub formatter: LuacheckFormatter,
+
     #[structopt(parse(from_os_str), min_values(1), index(1), required(true))]
     pub files: Vec<OsString>,
 
@@ -69,6 +84,44 @@ pub struct Options {
 
     #[structopt(long)]
     pub no_exclude: bool,
+
+    /// Only checks files that changed since the given git revision, and only reports diagnostics
+    /// on lines that changed
+    #[structopt(long)]
+    pub diff_base: Option<String>,
+
+    /// With --diff-base, also reports diagnostics whose secondary labels are on changed lines
+    #[structopt(long, requires = "diff-base")]
+    pub diff_include_secondary: bool,
+
+    /// Checks the files again whenever they change, keeping the config and standard library loaded.
+    /// The config and standard library are reloaded when they change
+    #[structopt(long, conflicts_with = "diff-base")]
+    pub watch: bool,
+
+    /// The path of the code read from stdin, which is used to find selene.toml, for `exclude`,
+    /// lints that depend on the path of the file, and output
+    #[structopt(long, parse(from_os_str))]
+    pub stdin_filename: Option<PathBuf>,
+
+    /// Check files matched by .gitignore, .ignore, and .seleneignore files
+    #[structopt(long)]
+    pub no_ignore: bool,
+
+    /// The order to display files in. `auto` sorts by path when stdout isn't a terminal,
+    /// and otherwise displays files as soon as they are checked
+    #[structopt(
+        long,
+        possible_values = &OutputOrder::variants(),
+        case_insensitive = true,
+        default_value = "auto",
+    )]
+    pub output_order: OutputOrder,
+
+    /// Writes the report to the given file instead of stdout, while still displaying diagnostics.
+    /// Only used with the checkstyle, junit, and gitlab display styles
+    #[structopt(long, parse(from_os_str))]
+    pub output: Option<PathBuf>,
 }
 
 impl Options {
@@ -110,6 +163,111 @@ pub enum Command {
 
     /// Prints the capabilities of the current build
     Capabilities,
+
+    /// Creates a selene.toml for the project in the current directory, picking a standard library
+    /// and files to exclude by looking at the project
+    Init {
+        /// Also writes globals.yml, a standard library defining the globals the project uses
+        /// that aren't defined anywhere, and adds it to `std`
+        #[structopt(long)]
+        generate_std: bool,
+
+        /// Overwrites selene.toml and globals.yml if they already exist
+        #[structopt(long)]
+        force: bool,
+    },
+
+    /// Creates a selene.toml and a standard library for its globals from a .luacheckrc,
+    /// and prints the options that have no selene equivalent
+    MigrateLuacheck {
+        /// The .luacheckrc to migrate
+        #[structopt(parse(from_os_str), default_value = ".luacheckrc")]
+        path: PathBuf,
+
+        /// Overwrites selene.toml and luacheck.yml if they already exist
+        #[structopt(long)]
+        force: bool,
+    },
+
+    /// Commands for inspecting standard libraries
+    Std(StdCommand),
+
+    /// Prints per-function and per-file metrics, such as cyclomatic complexity, for the given files
+    Metrics {
+        /// The format to print the metrics in
+        #[structopt(
+            long,
+            possible_values = &MetricsFormat::variants(),
+            case_insensitive = true,
+            default_value = "json",
+        )]
+        format: MetricsFormat,
+
+        /// A glob to match files with to measure
+        #[structopt(long, number_of_values = 1)]
+        pattern: Vec<String>,
+
+        #[structopt(parse(from_os_str), min_values(1), required(true))]
+        files: Vec<OsString>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+pub enum StdCommand {
+    /// Prints the file every `+` separated part of a standard library resolves to
+    Which {
+        /// The standard library to resolve, such as `lua51+custom` [default: the std of selene.toml]
+        name: Option<String>,
+    },
+
+    /// Prints the standard library with every base and `+` separated part merged in, as yml
+    Dump {
+        /// The standard library to dump [default: the std of selene.toml]
+        name: Option<String>,
+
+        /// Writes to the given file instead of stdout
+        #[structopt(long, parse(from_os_str))]
+        output: Option<PathBuf>,
+    },
+
+    /// Prints how a global such as `math.floor` is defined, and which standard library defines it
+    Show {
+        /// The path to the global, such as `math.floor`
+        path: String,
+
+        /// The standard library to look in [default: the std of selene.toml]
+        #[structopt(long)]
+        std: Option<String>,
+    },
+
+    /// Converts a standard library into type definitions for luau-lsp (`.d.luau`) or lua-language-server (`---@meta`)
+    Export {
+        /// The format to export to
+        #[structopt(
+            long,
+            possible_values = &ExportFormat::variants(),
+            case_insensitive = true,
+        )]
+        format: ExportFormat,
+
+        /// The standard library to export [default: the std of selene.toml]
+        name: Option<String>,
+
+        /// Writes to the given file instead of stdout
+        #[structopt(long, parse(from_os_str))]
+        output: Option<PathBuf>,
+    },
+
+    /// Prints the globals and structs added (+), removed (-), and changed (~) between two standard libraries.
+    /// Exits with 1 if they differ.
+    Diff {
+        /// A standard library name, or the path to a standard library file
+        a: String,
+
+        /// A standard library name, or the path to a standard library file
+        b: String,
+    },
 }
 
 arg_enum! {
@@ -128,6 +286,67 @@ arg_enum! {
         Json2,
         Rich,
         Quiet,
+        Checkstyle,
+        Junit,
+        Gitlab,
+        Github,
+    }
+}
+
+impl DisplayStyle {
+    /// Whether this is a report that is written once every file has been checked,
+    /// rather than printed as diagnostics come in
+    pub fn is_report(self) -> bool {
+        matches!(
+            self,
+            DisplayStyle::Checkstyle | DisplayStyle::Junit | DisplayStyle::Gitlab
+        )
+    }
+}
+
+arg_enum! {
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub enum LuacheckFormatter {
+        Plain,
+        Default,
+        Tap,
+        JUnit,
+    }
+}
+
+impl LuacheckFormatter {
+    fn from_name(name: &str) -> Self {
+        name.parse().unwrap_or(LuacheckFormatter::Plain)
+    }
+
+    /// Whether this is written once every file has been checked, rather than as each file is checked
+    pub fn is_report(self) -> bool {
+        self != LuacheckFormatter::Plain
+    }
+}
+
+arg_enum! {
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub enum OutputOrder {
+        Auto,
+        Sorted,
+        Completion,
+    }
+}
+
+arg_enum! {
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub enum MetricsFormat {
+        Json,
+        Csv,
+    }
+}
+
+arg_enum! {
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub enum ExportFormat {
+        Luau,
+        LuaLs,
     }
 }
 
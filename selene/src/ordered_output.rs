@@ -0,0 +1,74 @@
+//! Files are checked in parallel, so they finish in a different order every run.
+//! To keep output stable, each file's output is buffered and printed in the order
+//! the files were given, as soon as every file before it has finished.
+use std::{collections::BTreeMap, sync::Mutex};
+
+use termcolor::{Buffer, BufferWriter, ColorChoice};
+
+pub struct OrderedOutput {
+    buffer_writer: BufferWriter,
+    // When not sorted, output is printed as soon as each file finishes
+    sorted: bool,
+    state: Mutex<OrderedOutputState>,
+}
+
+struct OrderedOutputState {
+    // The index of the next file to print
+    next: usize,
+    // Output of files that finished before the files in front of them
+    pending: BTreeMap<usize, Buffer>,
+}
+
+impl OrderedOutput {
+    pub fn new(color_choice: ColorChoice, sorted: bool) -> Self {
+        Self {
+            buffer_writer: BufferWriter::stdout(color_choice),
+            sorted,
+            state: Mutex::new(OrderedOutputState {
+                next: 0,
+                pending: BTreeMap::new(),
+            }),
+        }
+    }
+
+    pub fn buffer(&self) -> Buffer {
+        self.buffer_writer.buffer()
+    }
+
+    /// Gives the output of the file at `index`, printing it along with any files
+    /// after it that were waiting on it.
+    pub fn finish(&self, index: usize, buffer: Buffer) {
+        if !self.sorted {
+            self.print(&buffer);
+            return;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        state.pending.insert(index, buffer);
+
+        loop {
+            let next = state.next;
+            let Some(buffer) = state.pending.remove(&next) else {
+                break;
+            };
+
+            self.print(&buffer);
+            state.next += 1;
+        }
+    }
+
+    /// Prints everything still waiting, which only happens if a file never finished,
+    /// such as when checking it panicked.
+    pub fn flush(&self) {
+        let mut state = self.state.lock().unwrap();
+
+        for (_, buffer) in std::mem::take(&mut state.pending) {
+            self.print(&buffer);
+        }
+    }
+
+    // Errors are ignored, since they only happen when stdout is closed, such as when piped into `head`
+    fn print(&self, buffer: &Buffer) {
+        self.buffer_writer.print(buffer).ok();
+    }
+}
@@ -0,0 +1,336 @@
+//! Report formats that CI systems can read natively, such as checkstyle XML.
+//! Unlike the other display styles, these can't be streamed as files are checked,
+//! so diagnostics are collected here and the report is written once at the end.
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::{self, Write},
+    sync::Mutex,
+};
+
+use codespan_reporting::diagnostic::{Diagnostic as CodespanDiagnostic, Severity};
+use serde::Serialize;
+
+use crate::opts::DisplayStyle;
+
+lazy_static::lazy_static! {
+    static ref DIAGNOSTICS: Mutex<Vec<ReportDiagnostic>> = Mutex::new(Vec::new());
+}
+
+struct ReportDiagnostic {
+    path: String,
+    severity: Severity,
+    code: String,
+    message: String,
+    notes: Vec<String>,
+    // 1-indexed, as every report format expects
+    start_line: usize,
+    start_column: usize,
+    end_line: usize,
+    // The source code the diagnostic points to, which is used instead of the position
+    // in fingerprints so that they don't change when unrelated lines are added.
+    source_text: String,
+}
+
+impl ReportDiagnostic {
+    fn full_message(&self) -> String {
+        let mut message = self.message.clone();
+
+        for note in &self.notes {
+            message.push('\n');
+            message.push_str(note);
+        }
+
+        message
+    }
+}
+
+// `./src/init.lua` should be reported as `src/init.lua`
+pub fn report_path(path: &str) -> String {
+    path.strip_prefix("./")
+        .or_else(|| path.strip_prefix(".\\"))
+        .unwrap_or(path)
+        .to_owned()
+}
+
+pub fn record_diagnostic(
+    diagnostic: &CodespanDiagnostic<codespan::FileId>,
+    files: &codespan::Files<&str>,
+) {
+    let label = diagnostic.labels.first().expect("no labels passed");
+
+    let start = files
+        .location(label.file_id, label.range.start as u32)
+        .expect("unable to determine start location for label");
+    let end = files
+        .location(label.file_id, label.range.end as u32)
+        .expect("unable to determine end location for label");
+
+    let report_diagnostic = ReportDiagnostic {
+        path: report_path(&files.name(label.file_id).to_string_lossy()),
+        severity: diagnostic.severity,
+        code: diagnostic.code.clone().unwrap_or_default(),
+        message: diagnostic.message.clone(),
+        notes: diagnostic.notes.clone(),
+        start_line: start.line.to_usize() + 1,
+        start_column: start.column.to_usize() + 1,
+        end_line: end.line.to_usize() + 1,
+        source_text: files
+            .source(label.file_id)
+            .get(label.range.clone())
+            .unwrap_or_default()
+            .to_owned(),
+    };
+
+    DIAGNOSTICS.lock().unwrap().push(report_diagnostic);
+}
+
+/// Writes every recorded diagnostic in the given report format.
+/// Diagnostics are sorted by path and position, since files are checked in parallel.
+pub fn write_report(display_style: DisplayStyle, writer: &mut impl Write) -> io::Result<()> {
+    let mut diagnostics = std::mem::take(&mut *DIAGNOSTICS.lock().unwrap());
+    diagnostics.sort_by(|a, b| {
+        (&a.path, a.start_line, a.start_column).cmp(&(&b.path, b.start_line, b.start_column))
+    });
+
+    match display_style {
+        DisplayStyle::Checkstyle => write_checkstyle(&diagnostics, writer),
+        DisplayStyle::Junit => write_junit(&diagnostics, writer),
+        DisplayStyle::Gitlab => write_gitlab(&diagnostics, writer),
+        _ => unreachable!("{display_style} is not a report display style"),
+    }
+}
+
+fn by_path(diagnostics: &[ReportDiagnostic]) -> BTreeMap<&str, Vec<&ReportDiagnostic>> {
+    let mut by_path: BTreeMap<_, Vec<_>> = BTreeMap::new();
+
+    for diagnostic in diagnostics {
+        by_path
+            .entry(diagnostic.path.as_str())
+            .or_default()
+            .push(diagnostic);
+    }
+
+    by_path
+}
+
+pub fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Newlines in attributes would otherwise be normalized to spaces
+            '\n' => escaped.push_str("&#10;"),
+            _ => escaped.push(character),
+        }
+    }
+
+    escaped
+}
+
+fn write_checkstyle(diagnostics: &[ReportDiagnostic], writer: &mut impl Write) -> io::Result<()> {
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(writer, r#"<checkstyle version="4.3">"#)?;
+
+    for (path, diagnostics) in by_path(diagnostics) {
+        writeln!(writer, r#"  <file name="{}">"#, escape_xml(path))?;
+
+        for diagnostic in diagnostics {
+            writeln!(
+                writer,
+                r#"    <error line="{}" column="{}" severity="{}" message="{}" source="selene.{}" />"#,
+                diagnostic.start_line,
+                diagnostic.start_column,
+                match diagnostic.severity {
+                    Severity::Bug | Severity::Error => "error",
+                    Severity::Warning => "warning",
+                    Severity::Note | Severity::Help => "info",
+                },
+                escape_xml(&diagnostic.full_message()),
+                escape_xml(&diagnostic.code),
+            )?;
+        }
+
+        writeln!(writer, "  </file>")?;
+    }
+
+    writeln!(writer, "</checkstyle>")
+}
+
+// Every file with diagnostics is a test suite, and every diagnostic is a failing test case
+fn write_junit(diagnostics: &[ReportDiagnostic], writer: &mut impl Write) -> io::Result<()> {
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        writer,
+        r#"<testsuites name="selene" tests="{0}" failures="{0}">"#,
+        diagnostics.len()
+    )?;
+
+    for (path, diagnostics) in by_path(diagnostics) {
+        let path = escape_xml(path);
+
+        writeln!(
+            writer,
+            r#"  <testsuite name="{path}" tests="{0}" failures="{0}">"#,
+            diagnostics.len()
+        )?;
+
+        for diagnostic in diagnostics {
+            writeln!(
+                writer,
+                r#"    <testcase name="{}:{}:{}" classname="{path}">"#,
+                escape_xml(&diagnostic.code),
+                diagnostic.start_line,
+                diagnostic.start_column,
+            )?;
+
+            writeln!(
+                writer,
+                r#"      <failure type="{}" message="{}">{path}:{}:{}: {}</failure>"#,
+                escape_xml(&diagnostic.code),
+                escape_xml(&diagnostic.message),
+                diagnostic.start_line,
+                diagnostic.start_column,
+                escape_xml(&diagnostic.full_message()),
+            )?;
+
+            writeln!(writer, "    </testcase>")?;
+        }
+
+        writeln!(writer, "  </testsuite>")?;
+    }
+
+    writeln!(writer, "</testsuites>")
+}
+
+#[derive(Serialize)]
+struct GitlabIssue<'a> {
+    description: String,
+    check_name: &'a str,
+    fingerprint: String,
+    severity: &'static str,
+    location: GitlabLocation<'a>,
+}
+
+#[derive(Serialize)]
+struct GitlabLocation<'a> {
+    path: &'a str,
+    lines: GitlabLines,
+}
+
+#[derive(Serialize)]
+struct GitlabLines {
+    begin: usize,
+    end: usize,
+}
+
+// FNV-1a, since the hashers in std aren't guaranteed to be the same between Rust versions,
+// and fingerprints have to stay the same for GitLab to know which issues are new.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+// Based on the lint, path, and flagged source text, so that fingerprints don't change when
+// code elsewhere in the file moves. The same text flagged more than once in a file
+// is told apart by the order it appears in.
+fn gitlab_fingerprints(diagnostics: &[ReportDiagnostic]) -> Vec<String> {
+    let mut occurrences = HashMap::new();
+
+    diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let key = (&diagnostic.code, &diagnostic.path, &diagnostic.source_text);
+            let occurrence = occurrences.entry(key).or_insert(0);
+            *occurrence += 1;
+
+            let input = format!(
+                "{}\0{}\0{}\0{}",
+                diagnostic.code, diagnostic.path, diagnostic.source_text, occurrence
+            );
+
+            format!("{:016x}", fnv1a(input.as_bytes()))
+        })
+        .collect()
+}
+
+fn write_gitlab(diagnostics: &[ReportDiagnostic], writer: &mut impl Write) -> io::Result<()> {
+    let issues = diagnostics
+        .iter()
+        .zip(gitlab_fingerprints(diagnostics))
+        .map(|(diagnostic, fingerprint)| GitlabIssue {
+            description: diagnostic.full_message(),
+            check_name: &diagnostic.code,
+            fingerprint,
+            severity: match diagnostic.severity {
+                Severity::Bug | Severity::Error => "major",
+                Severity::Warning => "minor",
+                Severity::Note | Severity::Help => "info",
+            },
+            location: GitlabLocation {
+                path: &diagnostic.path,
+                lines: GitlabLines {
+                    begin: diagnostic.start_line,
+                    end: diagnostic.end_line,
+                },
+            },
+        })
+        .collect::<Vec<_>>();
+
+    serde_json::to_writer_pretty(&mut *writer, &issues)?;
+    writeln!(writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnostic(path: &str, start_line: usize, source_text: &str) -> ReportDiagnostic {
+        ReportDiagnostic {
+            path: path.to_owned(),
+            severity: Severity::Warning,
+            code: "unused_variable".to_owned(),
+            message: "x is defined, but never used".to_owned(),
+            notes: Vec::new(),
+            start_line,
+            start_column: 7,
+            end_line: start_line,
+            source_text: source_text.to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_gitlab_fingerprints() {
+        let fingerprints = gitlab_fingerprints(&[
+            diagnostic("src/a.lua", 1, "x"),
+            diagnostic("src/a.lua", 5, "x"),
+            diagnostic("src/b.lua", 1, "x"),
+        ]);
+
+        // Moving a diagnostic doesn't change its fingerprint
+        assert_eq!(
+            fingerprints,
+            gitlab_fingerprints(&[
+                diagnostic("src/a.lua", 10, "x"),
+                diagnostic("src/a.lua", 20, "x"),
+                diagnostic("src/b.lua", 30, "x"),
+            ])
+        );
+
+        assert_ne!(fingerprints[0], fingerprints[1]);
+        assert_ne!(fingerprints[0], fingerprints[2]);
+    }
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(
+            escape_xml("`a < b` & \"c\"\nnote"),
+            "`a &lt; b` &amp; &quot;c&quot;&#10;note"
+        );
+    }
+}
@@ -31,6 +31,8 @@ pub enum ApiMember {
         name: String,
         #[serde(rename = "Tags")]
         tags: Option<Vec<String>>,
+        #[serde(rename = "Security", default)]
+        security: ApiSecurityContext,
     },
 
     Event {
@@ -38,6 +40,8 @@ pub enum ApiMember {
         name: String,
         #[serde(rename = "Tags")]
         tags: Option<Vec<String>>,
+        #[serde(rename = "Security", default)]
+        security: ApiSecurityContext,
     },
 
     Function {
@@ -47,6 +51,8 @@ pub enum ApiMember {
         tags: Option<Vec<String>>,
         #[serde(rename = "Parameters")]
         parameters: Vec<ApiParameter>,
+        #[serde(rename = "Security", default)]
+        security: ApiSecurityContext,
     },
 
     Property {
@@ -70,7 +76,9 @@ pub struct ApiParameter {}
 #[derive(Debug)]
 pub enum ApiValueType {
     Class { name: String },
-    DataType { value: ApiDataType },
+    DataType { name: String, value: ApiDataType },
+    Enum { name: String },
+    Primitive { value: ApiPrimitiveType },
     Other,
 }
 
@@ -108,7 +116,14 @@ impl<'de> Visitor<'de> for ApiValueTypeVisitor {
             "Class" => ApiValueType::Class { name },
 
             "DataType" => ApiValueType::DataType {
-                value: ApiDataType::deserialize(name.into_deserializer())?,
+                value: ApiDataType::deserialize(name.clone().into_deserializer())?,
+                name,
+            },
+
+            "Enum" => ApiValueType::Enum { name },
+
+            "Primitive" => ApiValueType::Primitive {
+                value: ApiPrimitiveType::deserialize(name.into_deserializer())?,
             },
 
             _ => ApiValueType::Other,
@@ -188,19 +203,38 @@ impl<'de> Deserialize<'de> for ApiDataType {
     }
 }
 
-#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[derive(Clone, Default, PartialEq, Eq, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct ApiPropertySecurity {
-    read: ApiPropertySecurityContext,
-    write: ApiPropertySecurityContext,
+    pub read: ApiSecurityContext,
+    pub write: ApiSecurityContext,
 }
 
-#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize)]
-pub enum ApiPropertySecurityContext {
+#[derive(Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(from = "String")]
+pub enum ApiSecurityContext {
     #[default]
     None,
-    #[serde(other)]
-    Secure,
+    Secure(String),
+}
+
+impl ApiSecurityContext {
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            ApiSecurityContext::None => None,
+            ApiSecurityContext::Secure(name) => Some(name),
+        }
+    }
+}
+
+impl From<String> for ApiSecurityContext {
+    fn from(context: String) -> Self {
+        if context == "None" {
+            ApiSecurityContext::None
+        } else {
+            ApiSecurityContext::Secure(context)
+        }
+    }
 }
 
 #[derive(Deserialize)]
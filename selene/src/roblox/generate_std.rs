@@ -94,7 +94,7 @@ impl RobloxGenerator {
 
         for member in &class.members {
             let (name, tags, field) = match &member {
-                ApiMember::Callback { name, tags } => (
+                ApiMember::Callback { name, tags, .. } => (
                     name,
                     tags,
                     Some(Field::from_field_kind(FieldKind::Property(
@@ -102,7 +102,7 @@ impl RobloxGenerator {
                     ))),
                 ),
 
-                ApiMember::Event { name, tags } => (
+                ApiMember::Event { name, tags, .. } => (
                     name,
                     tags,
                     Some(Field::from_field_kind(FieldKind::Struct(
@@ -114,6 +114,7 @@ impl RobloxGenerator {
                     name,
                     tags,
                     parameters,
+                    ..
                 } => (
                     name,
                     tags,
@@ -126,6 +127,7 @@ impl RobloxGenerator {
                                     required: Required::NotRequired,
                                     observes: Observes::ReadWrite,
                                     deprecated: None,
+                                    constraints: ArgumentConstraints::default(),
                                 })
                                 .collect(),
                             method: true,
@@ -161,7 +163,7 @@ impl RobloxGenerator {
                                 Some(Field::from_field_kind(FieldKind::Struct(name.to_owned())))
                             }
 
-                            ApiValueType::DataType { value } => {
+                            ApiValueType::DataType { value, .. } => {
                                 // See comment on `has_custom_methods` for why we're taking
                                 // such a lax approach here.
                                 if value.has_custom_methods() {
@@ -267,6 +269,7 @@ impl RobloxGenerator {
                         required: Required::Required(None),
                         observes: Observes::ReadWrite,
                         deprecated: None,
+                        constraints: ArgumentConstraints::default(),
                     },
                     Argument {
                         argument_type: ArgumentType::Display("Instance".to_string()),
@@ -276,6 +279,7 @@ impl RobloxGenerator {
                             message: "set the instance's parent separately".to_owned(),
                             replace: vec![],
                         }),
+                        constraints: ArgumentConstraints::default(),
                     },
                 ],
                 method: false,
@@ -287,7 +291,7 @@ impl RobloxGenerator {
     }
 
     fn write_get_service(&mut self, api: &ApiDump) {
-        let service_names = api
+        let service_names: Vec<String> = api
             .classes
             .iter()
             .filter_map(|class| {
@@ -299,6 +303,11 @@ impl RobloxGenerator {
             })
             .collect();
 
+        // Lets locals assigned from `GetService` be typed as their service
+        for service_name in &service_names {
+            self.write_class_struct(api, service_name);
+        }
+
         let data_model = self.std.structs.get_mut("DataModel").unwrap();
 
         *data_model.get_mut("GetService").unwrap() =
@@ -308,6 +317,7 @@ impl RobloxGenerator {
                     required: Required::Required(None),
                     observes: Observes::ReadWrite,
                     deprecated: None,
+                    constraints: ArgumentConstraints::default(),
                 }],
                 method: true,
                 must_use: true,
@@ -318,11 +328,59 @@ impl RobloxGenerator {
         for class in &api.classes {
             let mut events = Vec::new();
             let mut properties = Vec::new();
+            let mut property_types = BTreeMap::new();
+            let mut security = BTreeMap::new();
 
             for member in &class.members {
+                let (name, read_security) = match member {
+                    ApiMember::Callback { name, security, .. }
+                    | ApiMember::Event { name, security, .. }
+                    | ApiMember::Function { name, security, .. } => (name, security),
+                    ApiMember::Property { name, security, .. } => (name, &security.read),
+                    ApiMember::Unknown => continue,
+                };
+
+                if let Some(read_security) = read_security.name() {
+                    security.insert(name.to_owned(), read_security.to_owned());
+                }
+
                 match member {
                     ApiMember::Event { name, .. } => events.push(name.to_owned()),
-                    ApiMember::Property { name, .. } => properties.push(name.to_owned()),
+                    ApiMember::Property {
+                        name,
+                        tags,
+                        security,
+                        value_type,
+                    } => {
+                        properties.push(name.to_owned());
+
+                        let value_type = match value_type {
+                            ApiValueType::Class { name } => RobloxValueType::Class(name.to_owned()),
+                            ApiValueType::DataType { name, .. } => {
+                                RobloxValueType::DataType(name.to_owned())
+                            }
+                            ApiValueType::Enum { name } => RobloxValueType::Enum(name.to_owned()),
+                            ApiValueType::Primitive { value } => match value {
+                                ApiPrimitiveType::Bool => RobloxValueType::Bool,
+                                ApiPrimitiveType::Double
+                                | ApiPrimitiveType::Float
+                                | ApiPrimitiveType::Int
+                                | ApiPrimitiveType::Int64 => RobloxValueType::Number,
+                                ApiPrimitiveType::String => RobloxValueType::String,
+                                ApiPrimitiveType::Unknown => continue,
+                            },
+                            ApiValueType::Other => continue,
+                        };
+
+                        property_types.insert(
+                            name.to_owned(),
+                            RobloxProperty {
+                                value_type,
+                                read_only: tags.iter().flatten().any(|tag| tag == "ReadOnly"),
+                                security: security.write.name().map(ToOwned::to_owned),
+                            },
+                        );
+                    }
                     _ => {}
                 }
             }
@@ -333,6 +391,8 @@ impl RobloxGenerator {
                     superclass: class.superclass.clone(),
                     events,
                     properties,
+                    property_types,
+                    security,
                 },
             );
         }
@@ -139,76 +139,253 @@ fn collect_roblox_standard_library<V>(
     unreachable!()
 }
 
+/// The environment variable that can specify extra directories to search for standard libraries,
+/// separated the same way as `PATH`.
+pub const STD_PATH_ENV_VAR: &str = "SELENE_STD_PATH";
+
+/// Where a standard library name resolved to.
+#[derive(Debug, PartialEq, Eq)]
+pub enum StandardLibrarySource {
+    File(PathBuf),
+    BuiltIn,
+    Roblox,
+}
+
+impl Display for StandardLibrarySource {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StandardLibrarySource::File(path) => write!(formatter, "{}", path.display()),
+            StandardLibrarySource::BuiltIn => write!(formatter, "built-in"),
+            StandardLibrarySource::Roblox => write!(formatter, "generated Roblox standard library"),
+        }
+    }
+}
+
+/// The directories that are searched for `name.toml`, `name.yml`, or `name.yaml`, in order of precedence:
+/// the current directory, the directory of the config file passed with --config, the `std-paths`
+/// of the config, the directories in `SELENE_STD_PATH`, and finally the user's selene config directory.
+/// If none of these have the file, then the built-in standard libraries are used.
+pub fn search_directories<V>(
+    config: &CheckerConfig<V>,
+    directory: &Path,
+    config_directory: &Option<PathBuf>,
+) -> Vec<PathBuf> {
+    let mut directories = vec![directory.to_path_buf()];
+
+    if let Some(config_directory) = config_directory {
+        directories.push(config_directory.clone());
+    }
+
+    // std-paths are relative to the config file, not to wherever selene is being run from
+    let relative_to = config_directory.as_deref().unwrap_or(directory);
+    directories.extend(
+        config
+            .std_paths
+            .iter()
+            .map(|std_path| relative_to.join(std_path)),
+    );
+
+    if let Some(env_paths) = std::env::var_os(STD_PATH_ENV_VAR) {
+        directories
+            .extend(std::env::split_paths(&env_paths).filter(|path| !path.as_os_str().is_empty()));
+    }
+
+    if let Some(user_config_directory) = dirs::config_dir() {
+        directories.push(user_config_directory.join("selene").join("std"));
+    }
+
+    directories
+}
+
+fn find_file(directories: &[PathBuf], standard_library_name: &str) -> Option<PathBuf> {
+    for directory in directories {
+        for extension in ["toml", "yml", "yaml"] {
+            let file = directory.join(format!("{standard_library_name}.{extension}"));
+            if file.exists() {
+                return Some(file);
+            }
+        }
+    }
+
+    None
+}
+
+/// Finds where a single standard library name (not one joined with `+`) would be loaded from,
+/// without reading it.
+pub fn resolve_standard_library<V>(
+    config: &CheckerConfig<V>,
+    standard_library_name: &str,
+    directory: &Path,
+    config_directory: &Option<PathBuf>,
+) -> Option<StandardLibrarySource> {
+    let directories = search_directories(config, directory, config_directory);
+
+    if let Some(file) = find_file(&directories, standard_library_name) {
+        Some(StandardLibrarySource::File(file))
+    } else if StandardLibrary::from_name(standard_library_name).is_some() {
+        Some(StandardLibrarySource::BuiltIn)
+    } else if cfg!(feature = "roblox") && standard_library_name == "roblox" {
+        Some(StandardLibrarySource::Roblox)
+    } else {
+        None
+    }
+}
+
+pub fn read_file(file: &Path) -> Result<StandardLibrary, StandardLibraryError> {
+    let content = fs::read_to_string(file).map_err(|error| StandardLibraryError::Io {
+        source: error,
+        path: file.to_path_buf(),
+    })?;
+
+    if file.extension().and_then(|extension| extension.to_str()) == Some("toml") {
+        let v1_library: v1::StandardLibrary =
+            toml::from_str(&content).map_err(|error| StandardLibraryError::Toml {
+                source: error,
+                path: file.to_path_buf(),
+            })?;
+
+        Ok(v1_library.into())
+    } else {
+        serde_yaml::from_str(&content).map_err(|error| StandardLibraryError::Yml {
+            source: error,
+            path: file.to_path_buf(),
+        })
+    }
+}
+
 fn from_name<V>(
     config: &CheckerConfig<V>,
     standard_library_name: &str,
     directory: &Path,
     config_directory: &Option<PathBuf>,
 ) -> Result<Option<StandardLibrary>, StandardLibraryError> {
-    let mut library: Option<StandardLibrary> = None;
+    let directories = search_directories(config, directory, config_directory);
 
-    let mut directories = vec![directory];
-    if let Some(directory) = config_directory {
-        directories.push(directory);
-    };
+    match find_file(&directories, standard_library_name) {
+        Some(file) => from_file(config, &file, directory, config_directory).map(Some),
+        None => Ok(StandardLibrary::from_name(standard_library_name)),
+    }
+}
 
-    for directory in directories {
-        let toml_file = directory.join(format!("{standard_library_name}.toml"));
-        if toml_file.exists() {
-            let content =
-                fs::read_to_string(&toml_file).map_err(|error| StandardLibraryError::Io {
-                    source: error,
-                    path: toml_file.clone(),
-                })?;
-
-            let v1_library: v1::StandardLibrary =
-                toml::from_str(&content).map_err(|error| StandardLibraryError::Toml {
-                    source: error,
-                    path: toml_file.clone(),
-                })?;
-
-            library = Some(v1_library.into());
-            break;
-        } else {
-            let mut yaml_file = directory.join(format!("{standard_library_name}.yml"));
-            if !yaml_file.exists() {
-                yaml_file = directory.join(format!("{standard_library_name}.yaml"));
-            }
+/// Reads a standard library file, extended with its base.
+pub fn from_file<V>(
+    config: &CheckerConfig<V>,
+    file: &Path,
+    directory: &Path,
+    config_directory: &Option<PathBuf>,
+) -> Result<StandardLibrary, StandardLibraryError> {
+    let mut library = read_file(file)?;
 
-            if yaml_file.exists() {
-                let content =
-                    fs::read_to_string(&yaml_file).map_err(|error| StandardLibraryError::Io {
-                        source: error,
-                        path: yaml_file.clone(),
-                    })?;
-
-                library = Some(serde_yaml::from_str(&content).map_err(|error| {
-                    StandardLibraryError::Yml {
-                        source: error,
-                        path: yaml_file.clone(),
-                    }
-                })?);
-
-                break;
-            }
+    if let Some(base_name) = &library.base {
+        if let Some(base) = collect_standard_library(config, base_name, directory, config_directory)
+            .map_err(|_| StandardLibraryError::BaseStd {
+                name: base_name.clone(),
+            })?
+        {
+            library.extend_base(base);
         }
     }
 
-    match library {
-        Some(mut library) => {
-            if let Some(base_name) = &library.base {
-                if let Some(base) =
-                    collect_standard_library(config, base_name, directory, config_directory)
-                        .map_err(|_| StandardLibraryError::BaseStd {
-                            name: base_name.clone(),
-                        })?
-                {
-                    library.extend(base);
-                }
-            }
+    Ok(library)
+}
+
+/// A standard library that was merged into another, without its own base merged into it.
+pub struct Contributor {
+    pub name: String,
+    pub source: StandardLibrarySource,
+    pub library: StandardLibrary,
+}
+
+/// Collects every standard library that makes up `standard_library_name`, including bases, in order
+/// of precedence. When two define the same global, the one that comes first is the one that is used.
+pub fn collect_contributors<V>(
+    config: &CheckerConfig<V>,
+    standard_library_name: &str,
+    directory: &Path,
+    config_directory: &Option<PathBuf>,
+) -> Result<Vec<Contributor>, StandardLibraryError> {
+    let mut contributors = Vec::new();
 
-            Ok(Some(library))
+    for segment in standard_library_name.split('+') {
+        let Some(source) = resolve_standard_library(config, segment, directory, config_directory)
+        else {
+            return Err(StandardLibraryError::NotFound {
+                name: segment.to_owned(),
+            });
+        };
+
+        let library = match &source {
+            StandardLibrarySource::File(file) => read_file(file)?,
+            StandardLibrarySource::BuiltIn => StandardLibrary::from_name(segment)
+                .expect("resolved to a built-in standard library that doesn't exist"),
+            StandardLibrarySource::Roblox => collect_roblox_standard_library(config, directory)
+                .map_err(StandardLibraryError::Roblox)?,
+        };
+
+        // Built-in and Roblox standard libraries already have their base merged in
+        let base = match source {
+            StandardLibrarySource::File(_) => library.base.clone(),
+            _ => None,
+        };
+
+        contributors.push(Contributor {
+            name: segment.to_owned(),
+            source,
+            library,
+        });
+
+        if let Some(base) = base {
+            contributors.extend(collect_contributors(
+                config,
+                &base,
+                directory,
+                config_directory,
+            )?);
         }
-        None => Ok(StandardLibrary::from_name(standard_library_name)),
+    }
+
+    Ok(contributors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_std_paths() {
+        let directory = Path::new("./tests/std_paths");
+        let config = CheckerConfig::<toml::Value> {
+            std_paths: vec!["shared".to_owned()],
+            ..CheckerConfig::default()
+        };
+
+        assert_eq!(
+            resolve_standard_library(&config, "custom", directory, &None),
+            Some(StandardLibrarySource::File(
+                directory.join("shared").join("custom.yml")
+            ))
+        );
+
+        // The project directory takes precedence over std-paths
+        assert_eq!(
+            resolve_standard_library(&config, "overridden", directory, &None),
+            Some(StandardLibrarySource::File(
+                directory.join("overridden.yml")
+            ))
+        );
+
+        assert_eq!(
+            resolve_standard_library(&config, "lua51", directory, &None),
+            Some(StandardLibrarySource::BuiltIn)
+        );
+
+        let library = collect_standard_library(&config, "custom+overridden", directory, &None)
+            .unwrap()
+            .unwrap();
+
+        assert!(library.find_global(&["shared_global"]).is_some());
+        assert!(library.find_global(&["from_project"]).is_some());
+        assert!(library.find_global(&["from_shared"]).is_none());
+        assert!(library.find_global(&["print"]).is_some());
     }
 }
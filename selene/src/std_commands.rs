@@ -0,0 +1,420 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use selene_lib::{
+    standard_library::{Field, FieldKind, StandardLibrary},
+    CheckerConfig,
+};
+
+use crate::{
+    opts::ExportFormat,
+    standard_library::{
+        collect_contributors, collect_standard_library, from_file, read_file,
+        resolve_standard_library, search_directories, StandardLibrarySource,
+    },
+    std_export,
+};
+
+/// Prints where every `+` separated segment of a standard library name resolves to, alongside
+/// the bases of any files. Returns false if any of them could not be found.
+pub fn which<V>(
+    config: &CheckerConfig<V>,
+    standard_library_name: &str,
+    directory: &Path,
+    config_directory: &Option<PathBuf>,
+) -> bool {
+    fn print_segments<V>(
+        config: &CheckerConfig<V>,
+        standard_library_name: &str,
+        directory: &Path,
+        config_directory: &Option<PathBuf>,
+        depth: usize,
+    ) -> bool {
+        let mut found_all = true;
+        let indent = "  ".repeat(depth);
+
+        for segment in standard_library_name.split('+') {
+            let Some(source) =
+                resolve_standard_library(config, segment, directory, config_directory)
+            else {
+                println!("{indent}{segment}: not found");
+                found_all = false;
+                continue;
+            };
+
+            println!("{indent}{segment}: {source}");
+
+            if let StandardLibrarySource::File(file) = source {
+                match read_file(&file) {
+                    Ok(StandardLibrary {
+                        base: Some(base), ..
+                    }) => {
+                        println!("{indent}  base:");
+                        found_all &=
+                            print_segments(config, &base, directory, config_directory, depth + 2);
+                    }
+
+                    Ok(_) => {}
+
+                    Err(error) => {
+                        crate::error(&error.to_string());
+                        found_all = false;
+                    }
+                }
+            }
+        }
+
+        found_all
+    }
+
+    let found_all = print_segments(
+        config,
+        standard_library_name,
+        directory,
+        config_directory,
+        0,
+    );
+
+    if !found_all {
+        println!("\nsearched, in order:");
+        for directory in search_directories(config, directory, config_directory) {
+            println!("  {}", directory.display());
+        }
+        println!("  built-in standard libraries");
+    }
+
+    found_all
+}
+
+fn collect_for_command<V>(
+    config: &CheckerConfig<V>,
+    standard_library_name: &str,
+    directory: &Path,
+    config_directory: &Option<PathBuf>,
+) -> Option<StandardLibrary> {
+    match collect_standard_library(config, standard_library_name, directory, config_directory) {
+        Ok(Some(library)) => Some(library),
+        Ok(None) => {
+            crate::error("Standard library was empty.");
+            None
+        }
+        Err(error) => {
+            crate::error(&format!("Could not collect standard library: {error}"));
+            None
+        }
+    }
+}
+
+fn write_output(contents: &str, output: Option<&Path>) -> bool {
+    match output {
+        Some(output) => {
+            if let Err(error) = fs::write(output, contents) {
+                crate::error(&format!("Couldn't write to {}: {error}", output.display()));
+                return false;
+            }
+        }
+
+        None => print!("{contents}"),
+    }
+
+    true
+}
+
+/// Writes the fully merged standard library as yml, either to `output` or to stdout.
+pub fn dump<V>(
+    config: &CheckerConfig<V>,
+    standard_library_name: &str,
+    directory: &Path,
+    config_directory: &Option<PathBuf>,
+    output: Option<&Path>,
+) -> bool {
+    let Some(mut library) =
+        collect_for_command(config, standard_library_name, directory, config_directory)
+    else {
+        return false;
+    };
+
+    // Everything has already been merged in
+    library.base = None;
+
+    write_output(
+        &serde_yaml::to_string(&library).expect("couldn't serialize standard library"),
+        output,
+    )
+}
+
+/// Writes the fully merged standard library as type definitions, either to `output` or to stdout.
+pub fn export<V>(
+    config: &CheckerConfig<V>,
+    standard_library_name: &str,
+    format: ExportFormat,
+    directory: &Path,
+    config_directory: &Option<PathBuf>,
+    output: Option<&Path>,
+) -> bool {
+    let Some(library) =
+        collect_for_command(config, standard_library_name, directory, config_directory)
+    else {
+        return false;
+    };
+
+    write_output(
+        &std_export::export(&library, standard_library_name, format),
+        output,
+    )
+}
+
+// Finds the key in `globals` that `names` would be found through, such as `math.floor` or `x.*`.
+// Also returns how many of the names the key covers, which is less than all of them when the key is
+// `any` or a struct.
+fn find_global_key<'a>(
+    globals: &'a BTreeMap<String, Field>,
+    names: &[&str],
+) -> Option<(&'a str, usize)> {
+    for length in (1..=names.len()).rev() {
+        let matching_key = globals.iter().find(|(key, _)| {
+            let segments = key.split('.').collect::<Vec<_>>();
+            segments.len() == length
+                && segments
+                    .iter()
+                    .zip(names)
+                    .all(|(segment, name)| segment == name || *segment == "*")
+        });
+
+        if let Some((key, field)) = matching_key {
+            if length == names.len()
+                || matches!(field.field_kind, FieldKind::Any | FieldKind::Struct(_))
+            {
+                return Some((key, length));
+            }
+        }
+    }
+
+    None
+}
+
+/// Prints the field found for a global path such as `math.floor`, and which standard library defined it.
+pub fn show<V>(
+    config: &CheckerConfig<V>,
+    standard_library_name: &str,
+    global_path: &str,
+    directory: &Path,
+    config_directory: &Option<PathBuf>,
+) -> bool {
+    let (library, contributors) = match (
+        collect_standard_library(config, standard_library_name, directory, config_directory),
+        collect_contributors(config, standard_library_name, directory, config_directory),
+    ) {
+        (Ok(Some(library)), Ok(contributors)) => (library, contributors),
+        (Ok(None), _) => {
+            crate::error("Standard library was empty.");
+            return false;
+        }
+        (Err(error), _) | (_, Err(error)) => {
+            crate::error(&format!("Could not collect standard library: {error}"));
+            return false;
+        }
+    };
+
+    let names = global_path.split('.').collect::<Vec<_>>();
+
+    let Some(field) = library.find_global(&names) else {
+        crate::error(&format!(
+            "`{global_path}` is not defined in `{standard_library_name}`"
+        ));
+        return false;
+    };
+
+    println!("{global_path}");
+
+    let defined_by = |has_definition: &dyn Fn(&StandardLibrary) -> bool| {
+        contributors
+            .iter()
+            .find(|contributor| has_definition(&contributor.library))
+            .map(|contributor| match &contributor.source {
+                StandardLibrarySource::File(file) => {
+                    format!("`{}` ({})", contributor.name, file.display())
+                }
+                source => format!("`{}` ({source})", contributor.name),
+            })
+            .unwrap_or_else(|| "unknown".to_owned())
+    };
+
+    match find_global_key(&library.globals, &names) {
+        Some((key, length)) if length == names.len() => {
+            println!(
+                "defined by {} as `{key}`",
+                defined_by(&|library| library.globals.contains_key(key))
+            );
+        }
+
+        Some((key, _)) => match &library.globals[key].field_kind {
+            FieldKind::Struct(struct_name) => {
+                println!(
+                    "field of struct `{struct_name}`, which is defined by {}",
+                    defined_by(&|library| library.structs.contains_key(struct_name))
+                );
+            }
+
+            _ => println!(
+                "covered by `{key}`, which is defined by {}",
+                defined_by(&|library| library.globals.contains_key(key))
+            ),
+        },
+
+        // Fields such as `x` when only `x.y` is defined
+        None => println!("implicitly defined by its fields"),
+    }
+
+    print!(
+        "{}",
+        serde_yaml::to_string(field).expect("couldn't serialize field")
+    );
+
+    true
+}
+
+fn load_for_diff<V>(
+    config: &CheckerConfig<V>,
+    name_or_file: &str,
+    directory: &Path,
+    config_directory: &Option<PathBuf>,
+) -> Option<StandardLibrary> {
+    let file = Path::new(name_or_file);
+
+    let result = if file.is_file() {
+        from_file(config, file, directory, config_directory).map(Some)
+    } else {
+        collect_standard_library(config, name_or_file, directory, config_directory)
+    };
+
+    match result {
+        Ok(Some(library)) => Some(library),
+        Ok(None) => {
+            crate::error(&format!("Standard library `{name_or_file}` was empty."));
+            None
+        }
+        Err(error) => {
+            crate::error(&format!(
+                "Could not collect standard library `{name_or_file}`: {error}"
+            ));
+            None
+        }
+    }
+}
+
+fn diff_maps<T: PartialEq>(
+    before: &BTreeMap<String, T>,
+    after: &BTreeMap<String, T>,
+) -> Vec<(char, String)> {
+    let mut changes = Vec::new();
+
+    for (key, value) in before {
+        match after.get(key) {
+            None => changes.push(('-', key.to_owned())),
+            Some(after_value) if after_value != value => changes.push(('~', key.to_owned())),
+            Some(_) => {}
+        }
+    }
+
+    for key in after.keys() {
+        if !before.contains_key(key) {
+            changes.push(('+', key.to_owned()));
+        }
+    }
+
+    changes.sort_by(|(_, a), (_, b)| a.cmp(b));
+    changes
+}
+
+/// Prints the globals and structs that were added (+), removed (-), or changed (~) going from `a` to `b`.
+/// Each can either be a standard library name, or a path to a standard library file.
+/// Returns false if they differ, or if either couldn't be loaded.
+pub fn diff<V>(
+    config: &CheckerConfig<V>,
+    a: &str,
+    b: &str,
+    directory: &Path,
+    config_directory: &Option<PathBuf>,
+) -> bool {
+    let (Some(before), Some(after)) = (
+        load_for_diff(config, a, directory, config_directory),
+        load_for_diff(config, b, directory, config_directory),
+    ) else {
+        return false;
+    };
+
+    let mut same = true;
+
+    for (change, name) in diff_maps(&before.globals, &after.globals) {
+        println!("{change} global `{name}`");
+        same = false;
+    }
+
+    for (change, name) in diff_maps(&before.structs, &after.structs) {
+        println!("{change} struct `{name}`");
+        same = false;
+
+        if change == '~' {
+            for (field_change, field_name) in
+                diff_maps(&before.structs[&name], &after.structs[&name])
+            {
+                println!("    {field_change} field `{field_name}`");
+            }
+        }
+    }
+
+    same
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_global_key() {
+        let library = StandardLibrary::from_name("lua51").unwrap();
+
+        assert_eq!(
+            find_global_key(&library.globals, &["math", "floor"]),
+            Some(("math.floor", 2))
+        );
+
+        assert_eq!(find_global_key(&library.globals, &["math"]), None);
+
+        let mut globals = BTreeMap::new();
+        globals.insert("x.*".to_owned(), Field::from_field_kind(FieldKind::Any));
+
+        assert_eq!(
+            find_global_key(&globals, &["x", "y", "z"]),
+            Some(("x.*", 2))
+        );
+    }
+
+    #[test]
+    fn test_diff_maps() {
+        let before = BTreeMap::from([
+            ("a".to_owned(), 1),
+            ("b".to_owned(), 2),
+            ("c".to_owned(), 3),
+        ]);
+
+        let after = BTreeMap::from([
+            ("b".to_owned(), 2),
+            ("c".to_owned(), 4),
+            ("d".to_owned(), 5),
+        ]);
+
+        assert_eq!(
+            diff_maps(&before, &after),
+            vec![
+                ('-', "a".to_owned()),
+                ('~', "c".to_owned()),
+                ('+', "d".to_owned()),
+            ]
+        );
+    }
+}
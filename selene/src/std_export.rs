@@ -0,0 +1,724 @@
+use std::{collections::BTreeMap, fmt::Write};
+
+use selene_lib::standard_library::{
+    Argument, ArgumentType, Field, FieldKind, FunctionBehavior, PropertyWritability, Required,
+    StandardLibrary,
+};
+
+use crate::opts::ExportFormat;
+
+// Luau's `continue`, `export`, and `type` aren't here, since they can still be used as names
+const LUA_KEYWORDS: &[&str] = &[
+    "and", "break", "do", "else", "elseif", "end", "false", "for", "function", "goto", "if", "in",
+    "local", "nil", "not", "or", "repeat", "return", "then", "true", "until", "while",
+];
+
+// Types that exist without being defined by the standard library, and can be used for `display` arguments
+const LUAU_BUILTIN_TYPES: &[&str] = &["buffer", "thread", "vector"];
+const LUALS_BUILTIN_TYPES: &[&str] = &["lightuserdata", "thread", "userdata"];
+
+#[derive(Default)]
+struct Node<'a> {
+    field: Option<&'a Field>,
+    children: BTreeMap<&'a str, Node<'a>>,
+}
+
+impl<'a> Node<'a> {
+    fn field_kind(&self) -> Option<&'a FieldKind> {
+        self.field.map(|field| &field.field_kind)
+    }
+
+    fn is_removed(&self) -> bool {
+        matches!(self.field_kind(), Some(FieldKind::Removed))
+    }
+
+    fn wildcard(&self) -> Option<&Node<'a>> {
+        self.children.get("*").filter(|node| !node.is_removed())
+    }
+
+    fn named_children(&self) -> impl Iterator<Item = (&'a str, &Node<'a>)> {
+        self.children
+            .iter()
+            .filter(|(name, node)| **name != "*" && !node.is_removed())
+            .map(|(name, node)| (*name, node))
+    }
+
+    // Globals such as `_G` are tables that can have anything put into them
+    fn is_open_table(&self) -> bool {
+        matches!(
+            self.field_kind(),
+            Some(FieldKind::Any | FieldKind::Property(PropertyWritability::NewFields))
+        )
+    }
+
+    // Whether this is written as a table of its children rather than by its own field kind
+    fn is_table(&self) -> bool {
+        match self.field_kind() {
+            None | Some(FieldKind::Property(PropertyWritability::NewFields)) => true,
+            Some(FieldKind::Any | FieldKind::Property(_)) => !self.children.is_empty(),
+            _ => false,
+        }
+    }
+}
+
+fn build_tree(fields: &BTreeMap<String, Field>) -> Node<'_> {
+    let mut root = Node::default();
+
+    for (name, field) in fields {
+        let mut current = &mut root;
+        for segment in name.split('.') {
+            current = current.children.entry(segment).or_default();
+        }
+
+        current.field = Some(field);
+    }
+
+    root
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    matches!(chars.next(), Some(first) if first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|char| char.is_ascii_alphanumeric() || char == '_')
+        && !LUA_KEYWORDS.contains(&name)
+}
+
+// Quotes text as a Lua string. Control characters are written as decimal escapes, since Lua 5.1
+// has no `\x` or `\u{}` escapes.
+fn quote(text: &str) -> String {
+    let mut quoted = String::from("\"");
+
+    for char in text.chars() {
+        match char {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            char if char.is_ascii_control() => {
+                write!(quoted, "\\{:03}", char as u32).unwrap();
+            }
+            char => quoted.push(char),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+fn deprecated_message(field: Option<&Field>) -> Option<&str> {
+    field
+        .and_then(|field| field.deprecated.as_ref())
+        .map(|deprecated| deprecated.message.as_str())
+}
+
+fn is_read_only(node: &Node) -> bool {
+    matches!(
+        node.field_kind(),
+        Some(FieldKind::Property(PropertyWritability::ReadOnly))
+    )
+}
+
+fn argument_name(index: usize, argument: &Argument) -> String {
+    match argument.argument_type {
+        ArgumentType::Vararg => "...".to_owned(),
+        _ => format!("arg{}", index + 1),
+    }
+}
+
+fn is_optional(argument: &Argument) -> bool {
+    argument.required == Required::NotRequired
+        && !matches!(
+            argument.argument_type,
+            ArgumentType::Any | ArgumentType::Nil | ArgumentType::Vararg
+        )
+}
+
+/// Converts a standard library into a type definition file for another tool.
+/// Only the information selene has is kept, so every function returns `any`.
+pub fn export(library: &StandardLibrary, name: &str, format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Luau => LuauExporter::new(library).export(name),
+        ExportFormat::LuaLs => LuaLsExporter::new(library).export(name),
+    }
+}
+
+// Writes a definitions file, as used by luau-lsp
+struct LuauExporter<'a> {
+    library: &'a StandardLibrary,
+    output: String,
+}
+
+impl<'a> LuauExporter<'a> {
+    fn new(library: &'a StandardLibrary) -> Self {
+        Self {
+            library,
+            output: String::new(),
+        }
+    }
+
+    fn export(mut self, name: &str) -> String {
+        writeln!(
+            self.output,
+            "-- Generated by `selene std export` from the `{name}` standard library"
+        )
+        .unwrap();
+
+        for (struct_name, fields) in &self.library.structs {
+            if is_identifier(struct_name) {
+                self.write_class(struct_name, &build_tree(fields));
+            }
+        }
+
+        let globals = build_tree(&self.library.globals);
+        for (global_name, node) in globals.named_children() {
+            if is_identifier(global_name) {
+                self.write_global(global_name, node);
+            }
+        }
+
+        self.output
+    }
+
+    fn write_comments(&mut self, node: &Node, indent: &str) {
+        if let Some(message) = deprecated_message(node.field) {
+            writeln!(self.output, "{indent}--- @deprecated {message}").unwrap();
+        }
+
+        if is_read_only(node) {
+            writeln!(self.output, "{indent}--- read-only").unwrap();
+        }
+    }
+
+    fn write_class(&mut self, struct_name: &str, fields: &Node) {
+        writeln!(self.output, "\ndeclare class {struct_name}").unwrap();
+
+        for (field_name, node) in fields.named_children() {
+            if !is_identifier(field_name) {
+                continue;
+            }
+
+            self.write_comments(node, "\t");
+
+            match node.field_kind() {
+                Some(FieldKind::Function(behavior)) if behavior.method => {
+                    let mut parameters = vec!["self".to_owned()];
+                    parameters.extend(self.parameters(behavior, false));
+
+                    writeln!(
+                        self.output,
+                        "\tfunction {field_name}({}): ...any",
+                        parameters.join(", ")
+                    )
+                    .unwrap();
+                }
+
+                _ => {
+                    let field_type = self.node_type(node, 1);
+                    writeln!(self.output, "\t{field_name}: {field_type}").unwrap();
+                }
+            }
+        }
+
+        writeln!(self.output, "end").unwrap();
+    }
+
+    fn write_global(&mut self, global_name: &str, node: &Node) {
+        self.output.push('\n');
+        self.write_comments(node, "");
+
+        match node.field_kind() {
+            Some(FieldKind::Function(behavior)) if !behavior.method => {
+                writeln!(
+                    self.output,
+                    "declare function {global_name}({}): ...any",
+                    self.parameters(behavior, false).join(", ")
+                )
+                .unwrap();
+            }
+
+            _ => {
+                let global_type = self.node_type(node, 0);
+                writeln!(self.output, "declare {global_name}: {global_type}").unwrap();
+            }
+        }
+    }
+
+    fn argument_type(&self, argument_type: &ArgumentType) -> String {
+        match argument_type {
+            ArgumentType::Any | ArgumentType::Vararg => "any".to_owned(),
+            ArgumentType::Bool => "boolean".to_owned(),
+            ArgumentType::Constant(constants) => constants
+                .iter()
+                .map(|constant| quote(constant))
+                .collect::<Vec<_>>()
+                .join(" | "),
+            ArgumentType::Display(display) => {
+                if self.library.structs.contains_key(display)
+                    || LUAU_BUILTIN_TYPES.contains(&display.as_str())
+                {
+                    display.to_owned()
+                } else {
+                    "any".to_owned()
+                }
+            }
+            ArgumentType::Function => "(...any) -> ...any".to_owned(),
+            ArgumentType::Nil => "nil".to_owned(),
+            ArgumentType::Number => "number".to_owned(),
+            ArgumentType::String => "string".to_owned(),
+            ArgumentType::Table => "{ [any]: any }".to_owned(),
+        }
+    }
+
+    // Function types write varargs as `...any`, while declarations write them as `...: any`
+    fn parameters(&self, behavior: &FunctionBehavior, in_type: bool) -> Vec<String> {
+        behavior
+            .arguments
+            .iter()
+            .enumerate()
+            .map(|(index, argument)| {
+                if argument.argument_type == ArgumentType::Vararg {
+                    return if in_type { "...any" } else { "...: any" }.to_owned();
+                }
+
+                let mut argument_type = self.argument_type(&argument.argument_type);
+                if is_optional(argument) {
+                    if argument_type.contains(' ') {
+                        argument_type = format!("({argument_type})");
+                    }
+
+                    argument_type.push('?');
+                }
+
+                format!("{}: {argument_type}", argument_name(index, argument))
+            })
+            .collect()
+    }
+
+    fn node_type(&self, node: &Node, depth: usize) -> String {
+        if node.is_table() {
+            return self.table_type(node, depth);
+        }
+
+        match node.field_kind() {
+            Some(FieldKind::Function(behavior)) => {
+                let mut parameters = Vec::new();
+                if behavior.method {
+                    parameters.push("self: any".to_owned());
+                }
+
+                parameters.extend(self.parameters(behavior, true));
+                format!("({}) -> ...any", parameters.join(", "))
+            }
+
+            Some(FieldKind::Struct(struct_name)) if is_identifier(struct_name) => {
+                struct_name.to_owned()
+            }
+
+            _ => "any".to_owned(),
+        }
+    }
+
+    fn table_type(&self, node: &Node, depth: usize) -> String {
+        let indent = "\t".repeat(depth + 1);
+        let mut table = "{\n".to_owned();
+
+        for (name, child) in node.named_children() {
+            if let Some(message) = deprecated_message(child.field) {
+                writeln!(table, "{indent}--- @deprecated {message}").unwrap();
+            }
+
+            let key = if is_identifier(name) {
+                name.to_owned()
+            } else {
+                format!("[{}]", quote(name))
+            };
+
+            writeln!(
+                table,
+                "{indent}{key}: {},",
+                self.node_type(child, depth + 1)
+            )
+            .unwrap();
+        }
+
+        if let Some(wildcard) = node.wildcard() {
+            writeln!(
+                table,
+                "{indent}[string]: {},",
+                self.node_type(wildcard, depth + 1)
+            )
+            .unwrap();
+        } else if node.is_open_table() {
+            writeln!(table, "{indent}[any]: any,").unwrap();
+        }
+
+        table.push_str(&"\t".repeat(depth));
+        table.push('}');
+        table
+    }
+}
+
+// Writes a `---@meta` file, as used by lua-language-server
+struct LuaLsExporter<'a> {
+    library: &'a StandardLibrary,
+    output: String,
+}
+
+impl<'a> LuaLsExporter<'a> {
+    fn new(library: &'a StandardLibrary) -> Self {
+        Self {
+            library,
+            output: String::new(),
+        }
+    }
+
+    fn export(mut self, name: &str) -> String {
+        writeln!(self.output, "---@meta").unwrap();
+        writeln!(
+            self.output,
+            "-- Generated by `selene std export` from the `{name}` standard library"
+        )
+        .unwrap();
+
+        for (struct_name, fields) in &self.library.structs {
+            if is_identifier(struct_name) {
+                self.write_class(struct_name, &build_tree(fields));
+            }
+        }
+
+        let globals = build_tree(&self.library.globals);
+        for (global_name, node) in globals.named_children() {
+            let path = if is_identifier(global_name) {
+                global_name.to_owned()
+            } else {
+                format!("_G[{}]", quote(global_name))
+            };
+
+            self.write_global(&path, node);
+        }
+
+        self.output
+    }
+
+    fn write_comments(&mut self, node: &Node) {
+        if let Some(message) = deprecated_message(node.field) {
+            writeln!(self.output, "--- {message}").unwrap();
+            writeln!(self.output, "---@deprecated").unwrap();
+        }
+
+        if is_read_only(node) {
+            writeln!(self.output, "--- read-only").unwrap();
+        }
+    }
+
+    fn write_class(&mut self, struct_name: &str, fields: &Node) {
+        writeln!(self.output, "\n---@class {struct_name}").unwrap();
+
+        for (field_name, node) in fields.named_children() {
+            let field_name = if is_identifier(field_name) {
+                field_name.to_owned()
+            } else {
+                format!("[{}]", quote(field_name))
+            };
+
+            let field_type = match node.field_kind() {
+                Some(FieldKind::Function(behavior)) => {
+                    self.function_type(behavior, Some(struct_name))
+                }
+
+                _ => self.node_type(node),
+            };
+
+            write!(self.output, "---@field {field_name} {field_type}").unwrap();
+
+            if let Some(message) = deprecated_message(node.field) {
+                write!(self.output, " Deprecated: {message}").unwrap();
+            }
+
+            self.output.push('\n');
+        }
+
+        if let Some(wildcard) = fields.wildcard() {
+            writeln!(
+                self.output,
+                "---@field [string] {}",
+                self.node_type(wildcard)
+            )
+            .unwrap();
+        }
+
+        writeln!(self.output, "local {struct_name} = {{}}").unwrap();
+    }
+
+    fn write_global(&mut self, path: &str, node: &Node) {
+        self.output.push('\n');
+        self.write_comments(node);
+
+        if node.is_table() {
+            if let Some(wildcard) = node.wildcard() {
+                writeln!(
+                    self.output,
+                    "---@type {{ [string]: {} }}",
+                    self.node_type(wildcard)
+                )
+                .unwrap();
+            }
+
+            writeln!(self.output, "{path} = {{}}").unwrap();
+
+            for (name, child) in node.named_children() {
+                let child_path = if is_identifier(name) {
+                    format!("{path}.{name}")
+                } else {
+                    format!("{path}[{}]", quote(name))
+                };
+
+                self.write_global(&child_path, child);
+            }
+
+            return;
+        }
+
+        match node.field_kind() {
+            Some(FieldKind::Function(behavior)) => {
+                let names = behavior
+                    .arguments
+                    .iter()
+                    .enumerate()
+                    .map(|(index, argument)| argument_name(index, argument))
+                    .collect::<Vec<_>>();
+
+                for (index, argument) in behavior.arguments.iter().enumerate() {
+                    writeln!(
+                        self.output,
+                        "---@param {}{} {}",
+                        names[index],
+                        if is_optional(argument) { "?" } else { "" },
+                        self.argument_type(&argument.argument_type)
+                    )
+                    .unwrap();
+                }
+
+                if behavior.must_use {
+                    writeln!(self.output, "---@nodiscard").unwrap();
+                }
+
+                writeln!(self.output, "---@return any").unwrap();
+
+                let is_plain_path = !path.contains('[');
+                match path.rsplit_once('.') {
+                    Some((table, name)) if behavior.method && is_plain_path => writeln!(
+                        self.output,
+                        "function {table}:{name}({}) end",
+                        names.join(", ")
+                    ),
+
+                    _ if is_plain_path && !behavior.method => {
+                        writeln!(self.output, "function {path}({}) end", names.join(", "))
+                    }
+
+                    _ => {
+                        let mut parameters = names;
+                        if behavior.method {
+                            parameters.insert(0, "self".to_owned());
+                        }
+
+                        writeln!(
+                            self.output,
+                            "{path} = function({}) end",
+                            parameters.join(", ")
+                        )
+                    }
+                }
+                .unwrap();
+            }
+
+            _ => {
+                writeln!(self.output, "---@type {}", self.node_type(node)).unwrap();
+                writeln!(self.output, "{path} = nil").unwrap();
+            }
+        }
+    }
+
+    fn argument_type(&self, argument_type: &ArgumentType) -> String {
+        match argument_type {
+            ArgumentType::Any | ArgumentType::Vararg => "any".to_owned(),
+            ArgumentType::Bool => "boolean".to_owned(),
+            ArgumentType::Constant(constants) => constants
+                .iter()
+                .map(|constant| quote(constant))
+                .collect::<Vec<_>>()
+                .join("|"),
+            ArgumentType::Display(display) => {
+                if self.library.structs.contains_key(display)
+                    || LUALS_BUILTIN_TYPES.contains(&display.as_str())
+                {
+                    display.to_owned()
+                } else {
+                    "any".to_owned()
+                }
+            }
+            ArgumentType::Function => "function".to_owned(),
+            ArgumentType::Nil => "nil".to_owned(),
+            ArgumentType::Number => "number".to_owned(),
+            ArgumentType::String => "string".to_owned(),
+            ArgumentType::Table => "table".to_owned(),
+        }
+    }
+
+    fn function_type(&self, behavior: &FunctionBehavior, self_type: Option<&str>) -> String {
+        let mut parameters = Vec::new();
+        if behavior.method {
+            parameters.push(format!("self: {}", self_type.unwrap_or("any")));
+        }
+
+        for (index, argument) in behavior.arguments.iter().enumerate() {
+            parameters.push(format!(
+                "{}{}: {}",
+                argument_name(index, argument),
+                if is_optional(argument) { "?" } else { "" },
+                self.argument_type(&argument.argument_type)
+            ));
+        }
+
+        format!("fun({}): any", parameters.join(", "))
+    }
+
+    fn node_type(&self, node: &Node) -> String {
+        if node.is_table() {
+            return "table".to_owned();
+        }
+
+        match node.field_kind() {
+            Some(FieldKind::Function(behavior)) => self.function_type(behavior, None),
+            Some(FieldKind::Struct(struct_name)) => struct_name.to_owned(),
+            _ => "any".to_owned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_library() -> StandardLibrary {
+        serde_yaml::from_str(
+            r#"
+            globals:
+              print:
+                args:
+                  - type: "..."
+              math.floor:
+                args:
+                  - type: number
+                must_use: true
+              math.huge:
+                property: read-only
+              old:
+                args:
+                  - type: string
+                  - required: false
+                    type: [a, b]
+                deprecated:
+                  message: use new instead
+              game:
+                struct: Game
+              removed_thing:
+                removed: true
+            structs:
+              Game:
+                Name:
+                  property: read-only
+                GetService:
+                  method: true
+                  args:
+                    - type: string
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote("a\"b\\c"), r#""a\"b\\c""#);
+        assert_eq!(quote("line\nbell\x07"), r#""line\nbell\007""#);
+        assert_eq!(quote("ünicode"), r#""ünicode""#);
+    }
+
+    #[test]
+    fn test_is_identifier() {
+        assert!(is_identifier("type"));
+        assert!(is_identifier("continue"));
+        assert!(!is_identifier("end"));
+        assert!(!is_identifier("1st"));
+    }
+
+    #[test]
+    fn test_luau() {
+        pretty_assertions::assert_eq!(
+            export(&test_library(), "test", ExportFormat::Luau),
+            r#"-- Generated by `selene std export` from the `test` standard library
+
+declare class Game
+	function GetService(self, arg1: string): ...any
+	--- read-only
+	Name: any
+end
+
+declare game: Game
+
+declare math: {
+	floor: (arg1: number) -> ...any,
+	huge: any,
+}
+
+--- @deprecated use new instead
+declare function old(arg1: string, arg2: ("a" | "b")?): ...any
+
+declare function print(...: any): ...any
+"#
+        );
+    }
+
+    #[test]
+    fn test_luals() {
+        pretty_assertions::assert_eq!(
+            export(&test_library(), "test", ExportFormat::LuaLs),
+            r#"---@meta
+-- Generated by `selene std export` from the `test` standard library
+
+---@class Game
+---@field GetService fun(self: Game, arg1: string): any
+---@field Name any
+local Game = {}
+
+---@type Game
+game = nil
+
+math = {}
+
+---@param arg1 number
+---@nodiscard
+---@return any
+function math.floor(arg1) end
+
+--- read-only
+---@type any
+math.huge = nil
+
+--- use new instead
+---@deprecated
+---@param arg1 string
+---@param arg2? "a"|"b"
+---@return any
+function old(arg1, arg2) end
+
+---@param ... any
+---@return any
+function print(...) end
+"#
+        );
+    }
+}
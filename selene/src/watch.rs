@@ -0,0 +1,374 @@
+//! `selene --watch`, which keeps the checker and standard library loaded between runs,
+//! and only checks files again when they change.
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    sync::{mpsc, Arc},
+    time::Duration,
+};
+
+use full_moon::LuaVersion;
+use notify::{EventKind, RecursiveMode, Watcher};
+use selene_lib::{Checker, CheckerConfig};
+use termcolor::{Buffer, BufferWriter, StandardStream};
+use threadpool::ThreadPool;
+
+use crate::{
+    build_exclude_set, collect_changed_inputs, collect_inputs, get_color, load_config,
+    load_standard_library, log_total_text, lua_version, opts, read_file,
+    standard_library::{self, StandardLibrarySource},
+    FileCounts, IgnoreFiles, Input,
+};
+
+// Saving a file often sends several events at once, so they're collected for a short time
+// before checking anything, to avoid checking the same file several times.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+// Everything that is rebuilt when the config or a standard library changes
+struct LintSetup {
+    checker: Arc<Checker<toml::value::Value>>,
+    exclude_set: globset::GlobSet,
+    lua_version: LuaVersion,
+    // The config file and the standard library files that were read, to know when to reload
+    config_paths: HashSet<PathBuf>,
+    standard_library_files: HashSet<PathBuf>,
+}
+
+impl LintSetup {
+    fn load(options: &opts::Options, current_dir: &Path) -> Result<Self, String> {
+        let (config, config_directory) = load_config(options)?;
+
+        let standard_library = load_standard_library(&config, current_dir, &config_directory)?;
+        let exclude_set = build_exclude_set(&config)?;
+        let lua_version = lua_version(&standard_library);
+
+        let config_paths = match &options.config {
+            Some(config_file) => HashSet::from([absolute_path(Path::new(config_file))]),
+            None => HashSet::from([absolute_path(Path::new("selene.toml"))]),
+        };
+
+        let mut standard_library_files = HashSet::new();
+        collect_standard_library_files(
+            &config,
+            config.std(),
+            current_dir,
+            &config_directory,
+            &mut standard_library_files,
+        );
+
+        let checker = Checker::new(config, standard_library).map_err(|error| error.to_string())?;
+
+        Ok(LintSetup {
+            checker: Arc::new(checker),
+            exclude_set,
+            lua_version,
+            config_paths,
+            standard_library_files,
+        })
+    }
+
+    fn is_config_path(&self, path: &Path) -> bool {
+        self.config_paths.contains(path) || self.standard_library_files.contains(path)
+    }
+}
+
+// Collects the files that a standard library and its bases were read from. A file added to a
+// directory that's searched earlier isn't noticed until something else makes selene reload.
+fn collect_standard_library_files(
+    config: &CheckerConfig<toml::value::Value>,
+    standard_library_name: &str,
+    current_dir: &Path,
+    config_directory: &Option<PathBuf>,
+    files: &mut HashSet<PathBuf>,
+) {
+    for segment in standard_library_name.split('+') {
+        let Some(StandardLibrarySource::File(file)) = standard_library::resolve_standard_library(
+            config,
+            segment,
+            current_dir,
+            config_directory,
+        ) else {
+            continue;
+        };
+
+        let base = standard_library::read_file(&file)
+            .ok()
+            .and_then(|library| library.base);
+
+        if files.insert(absolute_path(&file)) {
+            if let Some(base) = base {
+                collect_standard_library_files(config, &base, current_dir, config_directory, files);
+            }
+        }
+    }
+}
+
+// Paths from notify are absolute, so everything they're compared to has to be too.
+// Files that don't exist yet, such as a selene.toml that hasn't been made, can't be canonicalized.
+fn absolute_path(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| {
+        std::env::current_dir()
+            .map(|current_dir| current_dir.join(path))
+            .unwrap_or_else(|_| path.to_path_buf())
+    })
+}
+
+struct CheckedFile {
+    // Compared to the paths from notify to know when the file is deleted
+    absolute_path: PathBuf,
+    output: Buffer,
+    counts: FileCounts,
+}
+
+struct WatchState<'a> {
+    options: &'a opts::Options,
+    buffer_writer: BufferWriter,
+    // Keyed by the path as given by collect_inputs, which is what is displayed
+    checked_files: BTreeMap<PathBuf, CheckedFile>,
+    skipped: usize,
+}
+
+impl WatchState<'_> {
+    // Checks the files in parallel, replacing their previous results
+    fn check_files(&mut self, setup: &LintSetup, paths: Vec<PathBuf>) {
+        let pool = ThreadPool::new(self.options.num_threads);
+        let (sender, receiver) = mpsc::channel();
+
+        for path in paths {
+            let checker = Arc::clone(&setup.checker);
+            let lua_version = setup.lua_version;
+            let mut output = self.buffer_writer.buffer();
+            let sender = sender.clone();
+
+            pool.execute(move || {
+                let counts = read_file(&checker, lua_version, &path, &mut output);
+                sender.send((path, output, counts)).ok();
+            });
+        }
+
+        drop(sender);
+
+        for (path, output, counts) in receiver {
+            match counts {
+                Some(counts) => {
+                    let absolute_path = absolute_path(&path);
+                    self.checked_files.insert(
+                        path,
+                        CheckedFile {
+                            absolute_path,
+                            output,
+                            counts,
+                        },
+                    );
+                }
+
+                // The file was deleted or couldn't be read, which was already reported
+                None => {
+                    self.checked_files.remove(&path);
+                }
+            }
+        }
+    }
+
+    // Finds every file to check and checks all of them, for when selene starts or reloads
+    fn check_all(&mut self, setup: &LintSetup) {
+        let mut paths = Vec::new();
+        self.skipped = collect_inputs(
+            self.options,
+            &self.options.files,
+            &setup.exclude_set,
+            |input| {
+                if let Input::File(path) = input {
+                    paths.push(path);
+                }
+            },
+        );
+
+        self.checked_files.clear();
+        self.check_files(setup, paths);
+    }
+
+    // Removes the files that were deleted and checks the files that were made or edited, without
+    // walking everything again. How many files were skipped is only counted again by check_all.
+    // Returns whether any file was checked or removed.
+    fn update(&mut self, setup: &LintSetup, changed: &HashSet<PathBuf>) -> bool {
+        let previous_count = self.checked_files.len();
+        self.checked_files.retain(|_, checked_file| {
+            checked_file.absolute_path.exists()
+                || !changed
+                    .iter()
+                    .any(|path| checked_file.absolute_path.starts_with(path))
+        });
+        let removed_any = self.checked_files.len() != previous_count;
+
+        let mut paths_to_check = HashSet::new();
+        collect_changed_inputs(
+            self.options,
+            &self.options.files,
+            &setup.exclude_set,
+            changed,
+            |path| {
+                paths_to_check.insert(path);
+            },
+        );
+
+        let checked_any = !paths_to_check.is_empty();
+        self.check_files(setup, paths_to_check.into_iter().collect());
+
+        checked_any || removed_any
+    }
+
+    fn redraw(&self) {
+        // Clears the terminal and moves the cursor to the top
+        print!("\x1B[2J\x1B[1;1H");
+
+        let mut total = FileCounts::default();
+
+        for checked_file in self.checked_files.values() {
+            self.buffer_writer.print(&checked_file.output).ok();
+
+            total.errors += checked_file.counts.errors;
+            total.warnings += checked_file.counts.warnings;
+            total.parse_errors += checked_file.counts.parse_errors;
+        }
+
+        if !self.options.no_summary {
+            log_total_text(
+                StandardStream::stdout(get_color()),
+                total.parse_errors,
+                total.errors,
+                total.warnings,
+                self.skipped,
+            )
+            .ok();
+        }
+
+        println!("\nWatching for changes...");
+    }
+}
+
+/// Checks the files, then checks them again as they change until selene is stopped.
+pub fn watch(options: &opts::Options) {
+    let current_dir = std::env::current_dir().unwrap();
+
+    let mut setup = match LintSetup::load(options, &current_dir) {
+        Ok(setup) => setup,
+        Err(error) => {
+            crate::error(&error);
+            std::process::exit(1);
+        }
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = match notify::recommended_watcher(sender) {
+        Ok(watcher) => watcher,
+        Err(error) => {
+            crate::error(&format!("Couldn't watch for changes: {error}"));
+            std::process::exit(1);
+        }
+    };
+
+    let input_roots = options
+        .files
+        .iter()
+        .map(|file| absolute_path(Path::new(file)))
+        .collect::<Vec<_>>();
+
+    for root in &input_roots {
+        let recursive_mode = if root.is_dir() {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+
+        if let Err(error) = watcher.watch(root, recursive_mode) {
+            crate::error(&format!("Couldn't watch {}: {error}", root.display()));
+            std::process::exit(1);
+        }
+    }
+
+    let watch_config = |watcher: &mut notify::RecommendedWatcher, setup: &LintSetup| {
+        let config_directories = setup
+            .config_paths
+            .iter()
+            .chain(&setup.standard_library_files)
+            .filter_map(|path| path.parent());
+
+        // Directories that don't exist or are already being watched are fine to skip
+        for directory in config_directories {
+            if directory.is_dir() {
+                watcher.watch(directory, RecursiveMode::NonRecursive).ok();
+            }
+        }
+    };
+
+    watch_config(&mut watcher, &setup);
+
+    let mut state = WatchState {
+        options,
+        buffer_writer: BufferWriter::stdout(get_color()),
+        checked_files: BTreeMap::new(),
+        skipped: 0,
+    };
+
+    state.check_all(&setup);
+    state.redraw();
+
+    while let Ok(event) = receiver.recv() {
+        let mut changed = HashSet::new();
+        let mut add_event = |event: notify::Result<notify::Event>| {
+            if let Ok(event) = event {
+                if !matches!(event.kind, EventKind::Access(_)) {
+                    changed.extend(event.paths);
+                }
+            }
+        };
+
+        add_event(event);
+        while let Ok(event) = receiver.recv_timeout(DEBOUNCE) {
+            add_event(event);
+        }
+
+        // The directories of the config and standard libraries can have anything else in them too,
+        // such as a file selene's output is being written to
+        changed.retain(|path| {
+            setup.is_config_path(path) || input_roots.iter().any(|root| path.starts_with(root))
+        });
+
+        if changed.is_empty() {
+            continue;
+        }
+
+        if changed.iter().any(|path| setup.is_config_path(path)) {
+            match LintSetup::load(options, &current_dir) {
+                Ok(new_setup) => {
+                    setup = new_setup;
+                    watch_config(&mut watcher, &setup);
+
+                    state.check_all(&setup);
+                    state.redraw();
+                }
+
+                // Keep the old config, so that a typo doesn't stop selene while editing
+                Err(error) => {
+                    state.redraw();
+                    crate::error(&error);
+                }
+            }
+
+            continue;
+        }
+
+        // Changing an ignore file can change which files are checked anywhere below it
+        if changed.iter().any(|path| IgnoreFiles::is_ignore_file(path)) {
+            state.check_all(&setup);
+            state.redraw();
+            continue;
+        }
+
+        if state.update(&setup, &changed) {
+            state.redraw();
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use std::{
+    io::Write,
+    path::Path,
+    process::{Command, Output, Stdio},
+};
+
+fn selene(directory: &Path, args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_selene"))
+        .args(["--display-style=quiet", "--no-summary"])
+        .args(args)
+        .current_dir(directory)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("couldn't run selene");
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).replace('\\', "/")
+}
+
+#[test]
+fn test_stdin_filename_config() {
+    // selene.toml is found next to the file, and its std is resolved relative to it
+    let output = selene(
+        Path::new("tests/stdin_filename"),
+        &["--stdin-filename", "./project/src/a.lua", "-"],
+        "custom_global(1)\nlocal unused = 1\n",
+    );
+
+    assert_eq!(
+        stdout(&output),
+        "project/src/a.lua:2:7: warning[unused_variable]: unused is assigned a value, but never used\n"
+    );
+}
+
+#[test]
+fn test_stdin_filename_exclude() {
+    let output = selene(
+        Path::new("tests/stdin_filename/project"),
+        &["--stdin-filename", "./vendor/a.lua", "-"],
+        "local unused = 1\n",
+    );
+
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "");
+
+    let output = selene(
+        Path::new("tests/stdin_filename/project"),
+        &["--stdin-filename", "./src/a.lua", "-"],
+        "local unused = 1\n",
+    );
+
+    assert_eq!(
+        stdout(&output),
+        "src/a.lua:1:7: warning[unused_variable]: unused is assigned a value, but never used\n"
+    );
+}
+
+#[test]
+fn test_diff_base() {
+    let directory = std::env::temp_dir().join(format!("selene-diff-base-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&directory);
+    std::fs::create_dir_all(&directory).unwrap();
+
+    let git = |args: &[&str]| {
+        let status = Command::new("git")
+            .args([
+                "-c",
+                "user.name=selene",
+                "-c",
+                "user.email=selene@example.com",
+            ])
+            .args(["-c", "commit.gpgsign=false"])
+            .args(args)
+            .current_dir(&directory)
+            .stdout(Stdio::null())
+            .status()
+            .expect("couldn't run git");
+
+        assert!(status.success(), "git {args:?} failed");
+    };
+
+    std::fs::write(directory.join("a.lua"), "local a = 1\n").unwrap();
+    git(&["init"]);
+    git(&["add", "a.lua"]);
+    git(&["commit", "-m", "initial"]);
+
+    // Only the new line is reported, and untracked files count as changed everywhere
+    std::fs::write(directory.join("a.lua"), "local a = 1\nlocal b = 2\n").unwrap();
+    std::fs::write(directory.join("c.lua"), "local c = 3\n").unwrap();
+
+    let output = selene(&directory, &["--diff-base", "HEAD", "."], "");
+    std::fs::remove_dir_all(&directory).ok();
+
+    assert_eq!(
+        stdout(&output),
+        "a.lua:2:7: warning[unused_variable]: b is assigned a value, but never used\n\
+         c.lua:1:7: warning[unused_variable]: c is assigned a value, but never used\n"
+    );
+}